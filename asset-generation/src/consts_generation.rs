@@ -0,0 +1,725 @@
+use crate::{get_consts_path, AppError, AppResult, StaticUrl};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+const GENERATED_HEADER: &str =
+    "// Generated by asset-generation from Data Dragon and CommunityDragon, do not edit by hand.\n// Run `cargo run --bin asset-generation -- --consts` to update it.\n\n";
+
+/// Display names of the queues shown in the filters, the others use their riot description.
+const QUEUE_NAMES: &[(u16, &str)] = &[
+    (400, "Normal Draft Pick"),
+    (430, "Normal Blind Pick"),
+    (420, "Ranked Solo/Duo"),
+    (440, "Ranked Flex"),
+    (450, "ARAM"),
+    (900, "ARURF"),
+    (1020, "One for All"),
+    (1700, "Arena"),
+    (1710, "Arena"),
+    (1810, "Swarm"),
+    (1820, "Swarm"),
+    (1830, "Swarm"),
+    (1840, "Swarm"),
+    (1900, "Pick URF"),
+    (1400, "Ultimate Spellbook"),
+    (940, "Nexus Siege"),
+    (700, "Clash"),
+    (490, "Normal Quickplay"),
+    (1300, "Nexus Blitz"),
+];
+
+/// Queues of `QUEUE_OPTIONS`, in display order.
+const QUEUE_OPTION_IDS: &[u16] = &[
+    400, 430, 420, 440, 450, 900, 1020, 1700, 1900, 1400, 940, 700, 490, 1300,
+];
+
+/// Stat shards are only listed in the CommunityDragon perks.
+const STAT_SHARD_IDS: std::ops::Range<u16> = 5000..6000;
+
+#[derive(Deserialize)]
+struct JsonData<T> {
+    data: HashMap<String, T>,
+}
+
+#[derive(Deserialize)]
+struct JsonChampion {
+    id: String,
+    key: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct JsonItem {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct JsonSummonerSpell {
+    id: String,
+    key: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct JsonPerkName {
+    id: u16,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct JsonPerkStyle {
+    id: u16,
+    key: String,
+    slots: Vec<JsonPerkSlot>,
+}
+
+#[derive(Deserialize)]
+struct JsonPerkSlot {
+    runes: Vec<JsonPerkRune>,
+}
+
+#[derive(Deserialize)]
+struct JsonPerkRune {
+    id: u16,
+    key: String,
+}
+
+#[derive(Deserialize)]
+struct JsonQueue {
+    #[serde(rename = "queueId")]
+    queue_id: u16,
+    map: String,
+    description: Option<String>,
+    notes: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JsonMap {
+    #[serde(rename = "mapId")]
+    map_id: u8,
+    #[serde(rename = "mapName")]
+    map_name: String,
+    notes: Option<String>,
+}
+
+/// Variant of a generated enum.
+struct ConstEntry {
+    id: u16,
+    identifier: String,
+    name: String,
+}
+
+/// Writes the `Champion`, `Item`, `Perk`, `SummonerSpell`, `Queue` and `Map` files of `common::consts`.
+/// Variants already checked in keep their identifier, new ids get one built from their name.
+pub async fn generate_consts(version: String) -> AppResult<()> {
+    let (champions, items, summoner_spells, perks, queues, maps) = tokio::join!(
+        generate_champions(version.clone()),
+        generate_items(version.clone()),
+        generate_summoner_spells(version.clone()),
+        generate_perks(version),
+        generate_queues(),
+        generate_maps(),
+    );
+    let files = vec![champions?, items?, summoner_spells?, perks?, queues?, maps?];
+    let status = std::process::Command::new("rustfmt")
+        .arg("--edition")
+        .arg("2021")
+        .args(files.iter().map(|file| get_consts_path().join(file)))
+        .status();
+    if !matches!(status, Ok(status) if status.success()) {
+        eprintln!("Failed to run rustfmt on the generated consts");
+    }
+    Ok(())
+}
+
+async fn generate_champions(version: String) -> AppResult<&'static str> {
+    let json: JsonData<JsonChampion> =
+        serde_json::from_str(StaticUrl::Champions { version }.get().await?.as_str())?;
+    let mut aliases = HashMap::new();
+    let mut entries = Vec::new();
+    for champion in json.data.into_values() {
+        let id = parse_id(&champion.key)?;
+        aliases.insert(id, champion.id);
+        entries.push((id, champion.name));
+    }
+    let mut entries = merge_existing("champion.rs", "Champion", entries, |_, name| {
+        to_identifier(name)
+    })
+    .await?;
+    entries.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+    let alias = |entry: &ConstEntry| {
+        aliases
+            .get(&entry.id)
+            .cloned()
+            .unwrap_or_else(|| entry.identifier.clone())
+    };
+
+    let mut out = String::from(GENERATED_HEADER);
+    write_enum(&mut out, "Champion", "u16", false, &entries);
+    out.push_str("impl Champion {\n");
+    write_str_fn(
+        &mut out,
+        "Champion",
+        "to_str",
+        &entries,
+        Some("UNKNOWN"),
+        |entry| entry.identifier.clone(),
+    );
+    out.push_str("    /// Name shown in game.\n");
+    write_str_fn(
+        &mut out,
+        "Champion",
+        "name",
+        &entries,
+        Some("Unknown"),
+        |entry| entry.name.clone(),
+    );
+    out.push_str("    /// Data Dragon id, used in the asset urls and the match participants.\n");
+    write_str_fn(
+        &mut out,
+        "Champion",
+        "alias",
+        &entries,
+        Some("UNKNOWN"),
+        alias,
+    );
+    out.push_str("}\n\n");
+    write_from(&mut out, "Champion", "u16", &entries, "Champion::UNKNOWN");
+    out.push_str("pub static CHAMPION_OPTIONS: &[(u16, &str)] = &[\n");
+    for entry in entries.iter() {
+        let _ = writeln!(
+            out,
+            "    (Champion::{0} as u16, Champion::{0}.to_str()),",
+            entry.identifier
+        );
+    }
+    out.push_str("];\n");
+    write_consts_file("champion.rs", out).await
+}
+
+/// Items keep a `u32` newtype, riot adds and removes ids every patch (arena items included), only
+/// their names are generated.
+async fn generate_items(version: String) -> AppResult<&'static str> {
+    let json: JsonData<JsonItem> =
+        serde_json::from_str(StaticUrl::Items { version }.get().await?.as_str())?;
+    let mut entries = json
+        .data
+        .into_iter()
+        .map(|(id, item)| {
+            id.parse::<u32>()
+                .map(|id| (id, item.name))
+                .map_err(|_| AppError::CustomError(format!("Invalid id: {}", id)))
+        })
+        .collect::<AppResult<Vec<_>>>()?;
+    entries.sort_by_key(|(id, _)| *id);
+
+    let mut out = String::from(GENERATED_HEADER);
+    out.push_str(
+        "use std::fmt::Formatter;\n\n#[derive(Copy, Clone, Default)]\npub struct Item(pub u32);\n\n",
+    );
+    out.push_str(
+        "impl Item {\n    /// Name shown in game, `None` for the ids missing from Data Dragon.\n",
+    );
+    out.push_str("    pub fn name(&self) -> Option<&'static str> {\n        ITEM_NAMES\n            .binary_search_by_key(&self.0, |(id, _)| *id)\n            .ok()\n            .map(|index| ITEM_NAMES[index].1)\n    }\n}\n\n");
+    out.push_str("/// Sorted by id.\npub static ITEM_NAMES: &[(u32, &str)] = &[\n");
+    for (id, name) in entries.iter() {
+        let _ = writeln!(out, "    ({}, {:?}),", id, name);
+    }
+    out.push_str("];\n\n");
+    out.push_str(
+        "impl TryFrom<u32> for Item {\n    type Error = ();\n\n    fn try_from(value: u32) -> Result<Self, Self::Error> {\n        if value == 0 {\n            Err(())\n        } else {\n            Ok(Item(value))\n        }\n    }\n}\n\n",
+    );
+    out.push_str(
+        "impl std::fmt::Display for Item {\n    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {\n        write!(f, \"{}\", self.0)\n    }\n}\n",
+    );
+    write_consts_file("item.rs", out).await
+}
+
+async fn generate_summoner_spells(version: String) -> AppResult<&'static str> {
+    let json: JsonData<JsonSummonerSpell> =
+        serde_json::from_str(StaticUrl::SummonerSpells { version }.get().await?.as_str())?;
+    let mut identifiers = HashMap::new();
+    let mut entries = Vec::new();
+    for spell in json.data.into_values() {
+        let id = parse_id(&spell.key)?;
+        identifiers.insert(id, spell.id);
+        entries.push((id, spell.name));
+    }
+    // the data dragon id is the identifier, it is also the image name
+    let mut entries = merge_existing("summoner_spell.rs", "SummonerSpell", entries, |id, _| {
+        identifiers[&id].clone()
+    })
+    .await?;
+    entries.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+
+    let mut out = String::from("use std::fmt::Formatter;\n\n");
+    out.insert_str(0, GENERATED_HEADER);
+    write_enum(&mut out, "SummonerSpell", "u16", false, &entries);
+    out.push_str("impl SummonerSpell {\n    /// Name shown in game.\n");
+    write_str_fn(
+        &mut out,
+        "SummonerSpell",
+        "name",
+        &entries,
+        Some("Unknown"),
+        |entry| entry.name.clone(),
+    );
+    out.push_str("}\n\n");
+    write_from(
+        &mut out,
+        "SummonerSpell",
+        "u16",
+        &entries,
+        "SummonerSpell::UNKNOWN",
+    );
+    out.push_str("pub static SUMMONER_SPELL_OPTIONS: &[u16] = &[\n");
+    for entry in entries.iter() {
+        let _ = writeln!(out, "    SummonerSpell::{} as u16,", entry.identifier);
+    }
+    out.push_str("];\n\n");
+    write_display(&mut out, "SummonerSpell", &entries);
+    write_consts_file("summoner_spell.rs", out).await
+}
+
+async fn generate_perks(version: String) -> AppResult<&'static str> {
+    let (perks, styles) = tokio::join!(StaticUrl::Perks.get(), StaticUrl::Perks2 { version }.get());
+    let perks: Vec<JsonPerkName> = serde_json::from_str(perks?.as_str())?;
+    let styles: Vec<JsonPerkStyle> = serde_json::from_str(styles?.as_str())?;
+    let names = perks
+        .iter()
+        .map(|perk| (perk.id, perk.name.clone()))
+        .collect::<HashMap<_, _>>();
+    let name = |id: u16, key: &str| names.get(&id).cloned().unwrap_or_else(|| key.to_string());
+
+    // stat shards first, then each style followed by its runes
+    let mut keys = HashMap::new();
+    let mut entries = perks
+        .iter()
+        .filter(|perk| STAT_SHARD_IDS.contains(&perk.id))
+        .map(|perk| {
+            keys.insert(perk.id, format!("Stats{}", to_identifier(&perk.name)));
+            (perk.id, perk.name.clone())
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|(id, _)| *id);
+    for style in styles.iter() {
+        keys.insert(style.id, style.key.clone());
+        entries.push((style.id, name(style.id, &style.key)));
+        for rune in style.slots.iter().flat_map(|slot| slot.runes.iter()) {
+            keys.insert(rune.id, rune.key.clone());
+            entries.push((rune.id, name(rune.id, &rune.key)));
+        }
+    }
+    let entries = merge_existing("perk.rs", "Perk", entries, |id, _| keys[&id].clone()).await?;
+
+    let mut out = String::from("use std::fmt::Formatter;\n\n");
+    out.insert_str(0, GENERATED_HEADER);
+    write_enum(&mut out, "Perk", "u16", false, &entries);
+    out.push_str("impl Perk {\n    /// Name shown in game.\n");
+    write_str_fn(
+        &mut out,
+        "Perk",
+        "name",
+        &entries,
+        Some("Unknown"),
+        |entry| entry.name.clone(),
+    );
+    out.push_str("}\n\n");
+    write_from(&mut out, "Perk", "u16", &entries, "Perk::UNKNOWN");
+    write_display(&mut out, "Perk", &entries);
+    write_consts_file("perk.rs", out).await
+}
+
+async fn generate_queues() -> AppResult<&'static str> {
+    let queues: Vec<JsonQueue> = serde_json::from_str(StaticUrl::Queues.get().await?.as_str())?;
+    let mut arena = HashSet::new();
+    let mut swarm = HashSet::new();
+    let mut entries = Vec::new();
+    for queue in queues.iter() {
+        let description = queue
+            .description
+            .as_deref()
+            .unwrap_or(queue.map.as_str())
+            .trim_end_matches(" games")
+            .to_string();
+        let mentions = |word: &str| queue.map.contains(word) || description.contains(word);
+        if mentions("Arena") || queue.map == "Rings of Wrath" {
+            arena.insert(queue.queue_id);
+        }
+        if mentions("Swarm") || mentions("Strawberry") {
+            swarm.insert(queue.queue_id);
+        }
+        entries.push((queue.queue_id, description));
+    }
+    let by_id = queues
+        .iter()
+        .map(|queue| (queue.queue_id, queue))
+        .collect::<HashMap<_, _>>();
+    // discriminants are implicit, the checked in order is kept so that they stay stable
+    let mut entries = merge_existing("queue.rs", "Queue", entries, |id, _| {
+        queue_identifier(by_id[&id])
+    })
+    .await?;
+    for entry in entries.iter_mut() {
+        if let Some((_, name)) = QUEUE_NAMES.iter().find(|(id, _)| *id == entry.id) {
+            entry.name = name.to_string();
+        }
+    }
+
+    let mut out = String::from("use bitcode::{Decode, Encode};\n\n");
+    out.insert_str(0, GENERATED_HEADER);
+    write_enum(&mut out, "Queue", "u8", true, &entries);
+    out.push_str("impl Queue {\n");
+    write_str_fn(&mut out, "Queue", "to_str", &entries, None, |entry| {
+        entry.name.clone()
+    });
+    let write_matches = |out: &mut String, doc: &str, fn_name: &str, ids: &HashSet<u16>| {
+        let variants = entries
+            .iter()
+            .filter(|entry| ids.contains(&entry.id))
+            .map(|entry| format!("Queue::{}", entry.identifier))
+            .collect::<Vec<_>>();
+        let body = if variants.is_empty() {
+            "false".to_string()
+        } else {
+            format!("matches!(self, {})", variants.join(" | "))
+        };
+        let _ = writeln!(
+            out,
+            "    /// {}\n    pub const fn {}(&self) -> bool {{\n        {}\n    }}\n",
+            doc, fn_name, body
+        );
+    };
+    write_matches(
+        &mut out,
+        "Arena (Cherry): 2 players subteams ranked by placement.",
+        "is_arena",
+        &arena,
+    );
+    write_matches(
+        &mut out,
+        "Swarm (Strawberry): PvE, all players in the same team.",
+        "is_swarm",
+        &swarm,
+    );
+    out.push_str("    pub fn to_u16(&self) -> u16 {\n        match self {\n");
+    for entry in entries.iter() {
+        let _ = writeln!(
+            out,
+            "            Queue::{} => {},",
+            entry.identifier, entry.id
+        );
+    }
+    out.push_str(
+        "        }\n    }\n\n    pub fn from_u16(value: u16) -> Self {\n        match value {\n",
+    );
+    for entry in entries.iter() {
+        let _ = writeln!(
+            out,
+            "            {} => Queue::{},",
+            entry.id, entry.identifier
+        );
+    }
+    out.push_str("            _ => Queue::Custom,\n        }\n    }\n}\n\n");
+    out.push_str("pub static QUEUE_OPTIONS: &[(u8, &str)] = &[\n");
+    for id in QUEUE_OPTION_IDS {
+        if let Some(entry) = entries.iter().find(|entry| entry.id == *id) {
+            let _ = writeln!(
+                out,
+                "    (Queue::{0} as u8, Queue::{0}.to_str()),",
+                entry.identifier
+            );
+        }
+    }
+    out.push_str("];\n\nimpl From<u8> for Queue {\n    fn from(value: u8) -> Self {\n        match value {\n");
+    for (index, entry) in entries.iter().enumerate() {
+        let _ = writeln!(out, "            {} => Queue::{},", index, entry.identifier);
+    }
+    out.push_str("            _ => Queue::Custom,\n        }\n    }\n}\n");
+    write_consts_file("queue.rs", out).await
+}
+
+async fn generate_maps() -> AppResult<&'static str> {
+    let maps: Vec<JsonMap> = serde_json::from_str(StaticUrl::Maps.get().await?.as_str())?;
+    let entries = maps
+        .iter()
+        .map(|map| {
+            // variants of the same map are told apart by their notes
+            let duplicated = maps
+                .iter()
+                .filter(|other| other.map_name == map.map_name)
+                .count()
+                > 1;
+            let name = match map.notes.as_deref() {
+                Some(notes) if duplicated && notes != "Current Version" => {
+                    format!("{} {}", map.map_name, notes)
+                }
+                _ => map.map_name.clone(),
+            };
+            (map.map_id as u16, name)
+        })
+        .collect();
+    let mut entries =
+        merge_existing("map.rs", "Map", entries, |_, name| to_identifier(name)).await?;
+    entries.sort_by_key(|entry| entry.id);
+
+    let mut out = String::from("use bitcode::{Decode, Encode};\n\n");
+    out.insert_str(0, GENERATED_HEADER);
+    out.push_str(
+        "#[repr(u8)]\n#[derive(Debug, Clone, Copy, Encode, Decode, Eq, PartialEq, Hash)]\n",
+    );
+    write_variants(&mut out, "Map", &entries);
+    out.push_str("impl Map {\n");
+    write_str_fn(
+        &mut out,
+        "Map",
+        "get_static_name",
+        &entries,
+        Some("Unknown"),
+        |entry| entry.name.clone(),
+    );
+    out.push_str("}\n\n");
+    write_from(&mut out, "Map", "u8", &entries, "Map::UNKNOWN");
+    write_consts_file("map.rs", out).await
+}
+
+/// Merges the source `(id, name)` with the variants already checked in: their identifier and
+/// order are kept, ids missing from the source stay since stored matches may reference them.
+/// New ids are appended sorted, with the identifier given by `new_identifier`.
+async fn merge_existing(
+    file: &str,
+    enum_name: &str,
+    source: Vec<(u16, String)>,
+    new_identifier: impl Fn(u16, &str) -> String,
+) -> AppResult<Vec<ConstEntry>> {
+    let existing = existing_identifiers(file, enum_name).await?;
+    let mut names = source.iter().cloned().collect::<HashMap<_, _>>();
+    let mut entries = existing
+        .into_iter()
+        .map(|(id, identifier)| ConstEntry {
+            id,
+            name: names.remove(&id).unwrap_or_else(|| identifier.clone()),
+            identifier,
+        })
+        .collect::<Vec<_>>();
+    let mut new_entries = source
+        .into_iter()
+        .filter_map(|(id, _)| names.remove_entry(&id))
+        .map(|(id, name)| ConstEntry {
+            id,
+            identifier: new_identifier(id, &name),
+            name,
+        })
+        .collect::<Vec<_>>();
+    new_entries.sort_by_key(|entry| entry.id);
+    entries.extend(new_entries);
+    let mut identifiers = HashSet::new();
+    for entry in entries.iter_mut() {
+        // two names may give the same identifier
+        if !identifiers.insert(entry.identifier.clone()) {
+            entry.identifier = format!("{}{}", entry.identifier, entry.id);
+            identifiers.insert(entry.identifier.clone());
+        }
+    }
+    Ok(entries)
+}
+
+/// `(id, identifier)` of the checked in file, read from its `id => Enum::Identifier` and
+/// `Enum::Identifier => id` match arms. The first arm of an identifier wins, so the `to_u16` of
+/// `Queue` is read before its `From<u8>`.
+async fn existing_identifiers(file: &str, enum_name: &str) -> AppResult<Vec<(u16, String)>> {
+    let path = get_consts_path().join(file);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = tokio::fs::read_to_string(path).await?;
+    let prefix = format!("{}::", enum_name);
+    let mut seen = HashSet::new();
+    Ok(content
+        .lines()
+        .filter_map(|line| {
+            let (left, right) = line.trim().trim_end_matches(',').split_once(" => ")?;
+            match (left.parse::<u16>(), right.parse::<u16>()) {
+                (Ok(id), _) => Some((id, right.strip_prefix(prefix.as_str())?)),
+                (_, Ok(id)) => Some((id, left.strip_prefix(prefix.as_str())?)),
+                _ => None,
+            }
+        })
+        .filter(|(_, identifier)| *identifier != "UNKNOWN" && is_identifier(identifier))
+        .filter(|(_, identifier)| seen.insert(identifier.to_string()))
+        .map(|(id, identifier)| (id, identifier.to_string()))
+        .collect())
+}
+
+fn write_enum(
+    out: &mut String,
+    enum_name: &str,
+    repr: &str,
+    implicit: bool,
+    entries: &[ConstEntry],
+) {
+    let _ = writeln!(out, "#[repr({})]", repr);
+    if implicit {
+        out.push_str("#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Encode, Decode)]\n");
+        let _ = writeln!(out, "pub enum {} {{", enum_name);
+        for entry in entries.iter() {
+            let _ = writeln!(out, "    {},", entry.identifier);
+        }
+        out.push_str("}\n\n");
+    } else {
+        out.push_str("#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]\n");
+        write_variants(out, enum_name, entries);
+    }
+}
+
+/// Variants with the id as discriminant, after an `UNKNOWN = 0` fallback.
+fn write_variants(out: &mut String, enum_name: &str, entries: &[ConstEntry]) {
+    let _ = writeln!(out, "pub enum {} {{\n    UNKNOWN = 0,", enum_name);
+    for entry in entries.iter() {
+        if entry.identifier.contains('_') {
+            out.push_str("    #[allow(non_camel_case_types)]\n");
+        }
+        let _ = writeln!(out, "    {} = {},", entry.identifier, entry.id);
+    }
+    out.push_str("}\n\n");
+}
+
+/// `unknown` is the value of the `UNKNOWN` variant, for the enums having one.
+fn write_str_fn(
+    out: &mut String,
+    enum_name: &str,
+    fn_name: &str,
+    entries: &[ConstEntry],
+    unknown: Option<&str>,
+    value: impl Fn(&ConstEntry) -> String,
+) {
+    let _ = writeln!(
+        out,
+        "    pub const fn {}(&self) -> &'static str {{\n        match self {{",
+        fn_name
+    );
+    for entry in entries.iter() {
+        let _ = writeln!(
+            out,
+            "            {}::{} => {:?},",
+            enum_name,
+            entry.identifier,
+            value(entry)
+        );
+    }
+    if let Some(unknown) = unknown {
+        let _ = writeln!(out, "            {}::UNKNOWN => {:?},", enum_name, unknown);
+    }
+    out.push_str("        }\n    }\n\n");
+}
+
+fn write_from(
+    out: &mut String,
+    enum_name: &str,
+    repr: &str,
+    entries: &[ConstEntry],
+    fallback: &str,
+) {
+    let _ = writeln!(
+        out,
+        "impl From<{1}> for {0} {{\n    fn from(value: {1}) -> Self {{\n        match value {{",
+        enum_name, repr
+    );
+    for entry in entries.iter() {
+        let _ = writeln!(
+            out,
+            "            {} => {}::{},",
+            entry.id, enum_name, entry.identifier
+        );
+    }
+    let _ = writeln!(
+        out,
+        "            _ => {},\n        }}\n    }}\n}}\n",
+        fallback
+    );
+}
+
+/// Displays the identifier, summoner spell ones are also their image name.
+fn write_display(out: &mut String, enum_name: &str, entries: &[ConstEntry]) {
+    let _ = writeln!(
+        out,
+        "impl std::fmt::Display for {} {{\n    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {{\n        let str = match self {{\n            {0}::UNKNOWN => \"UNKNOWN\",",
+        enum_name
+    );
+    for entry in entries.iter() {
+        let _ = writeln!(
+            out,
+            "            {}::{} => {:?},",
+            enum_name, entry.identifier, entry.identifier
+        );
+    }
+    out.push_str("        };\n        write!(f, \"{}\", str)\n    }\n}\n");
+}
+
+async fn write_consts_file(file: &'static str, content: String) -> AppResult<&'static str> {
+    tokio::fs::write(get_consts_path().join(file), content).await?;
+    println!("Generated: {}", file);
+    Ok(file)
+}
+
+/// `Summoner's Rift` -> `SummonersRift`, `Kai'Sa` -> `KaiSa`, `5v5 ARAM` -> `5v5Aram`.
+fn to_identifier(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let word = word.replace('\'', "");
+            let mut chars = word.chars();
+            let first = chars.next().map(|c| c.to_ascii_uppercase());
+            let rest = chars.as_str();
+            let rest = if rest.len() >= 2 && !rest.chars().any(|c| c.is_ascii_lowercase()) {
+                rest.to_ascii_lowercase()
+            } else {
+                rest.to_string()
+            };
+            first.into_iter().collect::<String>() + rest.as_str()
+        })
+        .collect()
+}
+
+/// Map and description, the map is dropped when the description already starts with it.
+/// Deprecated queues are suffixed with their id since they share the description of their
+/// replacement.
+fn queue_identifier(queue: &JsonQueue) -> String {
+    let description = queue.description.as_deref().unwrap_or_default();
+    let description = description.trim_end_matches(" games");
+    let mut identifier = if description.is_empty() || description.starts_with(queue.map.as_str()) {
+        to_identifier(if description.is_empty() {
+            queue.map.trim_end_matches(" games")
+        } else {
+            description
+        })
+    } else {
+        format!(
+            "{}{}",
+            to_identifier(&queue.map),
+            to_identifier(description)
+        )
+    };
+    if queue
+        .notes
+        .as_deref()
+        .is_some_and(|notes| notes.contains("Deprecated"))
+    {
+        let _ = write!(identifier, "Deprecated{}", queue.queue_id);
+    }
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert_str(0, "Queue");
+    }
+    identifier
+}
+
+fn is_identifier(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_alphabetic())
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn parse_id(key: &str) -> AppResult<u16> {
+    key.parse()
+        .map_err(|_| AppError::CustomError(format!("Invalid id: {}", key)))
+}
@@ -1,5 +1,5 @@
 use clap::Parser;
-use common::consts::champion::CHAMPION_OPTIONS;
+use common::consts::champion::{Champion, CHAMPION_OPTIONS};
 use common::consts::summoner_spell::{SummonerSpell, SUMMONER_SPELL_OPTIONS};
 use common::AssetType;
 use futures::{future, StreamExt};
@@ -15,6 +15,9 @@ use std::time::Instant;
 use image::imageops::FilterType;
 use thiserror::Error;
 
+pub mod consts_generation;
+pub mod metadata_generation;
+
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 pub struct Args {
@@ -38,9 +41,21 @@ pub struct Args {
     #[arg(long, default_value_t = false)]
     pub champions: bool,
 
+    /// force rebuild arena augments sprite and css
+    #[arg(long, default_value_t = false)]
+    pub augments: bool,
+
     /// force rebuild logo
     #[arg(long, default_value_t = false)]
     pub logo: bool,
+
+    /// regenerate the common consts enums, rerun afterward to download the new images
+    #[arg(long, default_value_t = false)]
+    pub consts: bool,
+
+    /// force rebuild the icon tooltips metadata
+    #[arg(long, default_value_t = false)]
+    pub metadata: bool,
 }
 
 pub type AppResult<T> = Result<T, AppError>;
@@ -101,6 +116,10 @@ pub fn get_css_path() -> PathBuf {
     Path::new("ruche").join("style")
 }
 
+pub fn get_consts_path() -> PathBuf {
+    Path::new("common").join("src").join("consts")
+}
+
 pub fn get_temp_path() -> PathBuf {
     Path::new("asset-generation").join("tmp")
 }
@@ -111,12 +130,13 @@ pub struct ImageToDownload {
     path: PathBuf,
 }
 
-pub async fn download_images() -> AppResult<(bool, bool, bool, bool, bool)> {
+pub async fn download_images() -> AppResult<(bool, bool, bool, bool, bool, bool)> {
     let version = get_current_version().await?;
-    let (item_images, profile_icons_images, perks) = tokio::join!(
+    let (item_images, profile_icons_images, perks, augments) = tokio::join!(
         get_items(version.clone()),
         update_profile_icons_image(version.clone()),
-        get_perks(version.clone())
+        get_perks(version.clone()),
+        get_augments()
     );
 
     let temp_path = get_temp_path();
@@ -130,9 +150,7 @@ pub async fn download_images() -> AppResult<(bool, bool, bool, bool, bool)> {
                     url: format!(
                         "https://cdn.communitydragon.org/{}/champion/{}/square",
                         version.clone(),
-                        riven::consts::Champion::from(*id as i16)
-                            .identifier()
-                            .unwrap()
+                        Champion::from(*id).alias()
                     ),
                     path,
                 });
@@ -164,6 +182,7 @@ pub async fn download_images() -> AppResult<(bool, bool, bool, bool, bool)> {
     let item_images = item_images?;
     let profile_icons_images = profile_icons_images?;
     let perks = perks?;
+    let augments = augments?;
 
     let bool_result = (
         !item_images.is_empty(),
@@ -171,6 +190,7 @@ pub async fn download_images() -> AppResult<(bool, bool, bool, bool, bool)> {
         !perks.is_empty(),
         !champion_images.is_empty(),
         !summoner_spells_images.is_empty(),
+        !augments.is_empty(),
     );
 
     download_and_save_images(
@@ -180,6 +200,7 @@ pub async fn download_images() -> AppResult<(bool, bool, bool, bool, bool)> {
             perks,
             champion_images,
             summoner_spells_images,
+            augments,
         ]
         .into_iter()
         .flatten()
@@ -195,6 +216,7 @@ pub async fn convert_not_found_images_and_rebuild_sprite(
     perks_modified: bool,
     champion_images_modified: bool,
     summoner_spells_images_modified: bool,
+    augments_modified: bool,
 ) -> AppResult<()> {
     let _ = tokio::join!(
         rebuild_css_sprite(AssetType::Item, item_images_modified),
@@ -202,6 +224,7 @@ pub async fn convert_not_found_images_and_rebuild_sprite(
         rebuild_css_sprite(AssetType::Perk, perks_modified),
         rebuild_css_sprite(AssetType::Champion, champion_images_modified),
         rebuild_css_sprite(AssetType::SummonerSpell, summoner_spells_images_modified),
+        rebuild_css_sprite(AssetType::Augment, augments_modified),
     );
     Ok(())
 }
@@ -473,6 +496,30 @@ pub async fn get_perks(version: String) -> AppResult<Vec<ImageToDownload>> {
     Ok(result_perks)
 }
 
+pub async fn get_augments() -> AppResult<Vec<ImageToDownload>> {
+    let raw_arena = StaticUrl::Arena.get().await?;
+    let arena: JsonArena = serde_json::from_str(raw_arena.as_str())?;
+    let temp_path = get_temp_path().join(AssetType::Augment.get_path());
+    Ok(arena
+        .augments
+        .into_iter()
+        .filter(|augment| !augment.icon_small.is_empty())
+        .filter_map(|augment| {
+            let path = temp_path.join(format!("{}.png", augment.id));
+            if !path.exists() {
+                return Some(ImageToDownload {
+                    url: format!(
+                        "https://raw.communitydragon.org/latest/game/{}",
+                        augment.icon_small.to_lowercase()
+                    ),
+                    path,
+                });
+            }
+            None
+        })
+        .collect())
+}
+
 pub async fn get_items(version: String) -> AppResult<Vec<ImageToDownload>> {
     let raw_items = StaticUrl::Items {
         version: version.clone(),
@@ -542,6 +589,7 @@ pub enum StaticUrl {
     SummonerSpells { version: String },
     Perks,
     Perks2 { version: String },
+    Arena,
     ProfileIcons { version: String },
     Maps,
     Queues,
@@ -557,6 +605,7 @@ impl StaticUrl {
             StaticUrl::SummonerSpells { version } => format!("https://ddragon.leagueoflegends.com/cdn/{}/data/en_US/summoner.json", version),
             StaticUrl::Perks => "https://raw.communitydragon.org/latest/plugins/rcp-be-lol-game-data/global/default/v1/perks.json".to_string(),
             StaticUrl::Perks2 { version } => format!("https://ddragon.leagueoflegends.com/cdn/{}/data/en_US/runesReforged.json", version),
+            StaticUrl::Arena => "https://raw.communitydragon.org/latest/cdragon/arena/en_us.json".to_string(),
             StaticUrl::ProfileIcons { version } => format!("https://ddragon.leagueoflegends.com/cdn/{}/data/en_US/profileicon.json", version).to_string(),
             StaticUrl::Maps => "https://static.developer.riotgames.com/docs/lol/maps.json".to_string(),
             StaticUrl::Queues => "https://static.developer.riotgames.com/docs/lol/queues.json".to_string(),
@@ -652,6 +701,18 @@ struct JsonPerk2 {
     icon: String,
 }
 
+#[derive(Serialize, Deserialize)]
+struct JsonArena {
+    augments: Vec<JsonAugment>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonAugment {
+    id: i32,
+    #[serde(rename = "iconSmall", default)]
+    icon_small: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProfileIconJson {
     pub id: i32,
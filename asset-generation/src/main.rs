@@ -2,13 +2,18 @@ use clap::Parser;
 use image::ImageFormat;
 use ravif::{Encoder, Img};
 use rgb::FromSlice;
-use asset_generation::{convert_not_found_images_and_rebuild_sprite, download_images, get_assets_path, get_temp_path, Args};
+use asset_generation::consts_generation::generate_consts;
+use asset_generation::metadata_generation::{generate_metadata, is_metadata_outdated};
+use asset_generation::{convert_not_found_images_and_rebuild_sprite, download_images, get_assets_path, get_current_version, get_temp_path, Args};
 
 #[tokio::main]
 async fn main() {
     let args=  Args::parse();
     let start = std::time::Instant::now();
-    let (items_modified, profile_icons_modified, perks_modified, champion_modified, summoner_spells_modified) = download_images().await.unwrap();
+    if args.consts {
+        generate_consts(get_current_version().await.unwrap()).await.unwrap();
+    }
+    let (items_modified, profile_icons_modified, perks_modified, champion_modified, summoner_spells_modified, augments_modified) = download_images().await.unwrap();
 
     convert_not_found_images_and_rebuild_sprite(
         args.items || items_modified,
@@ -16,9 +21,15 @@ async fn main() {
         args.perks||perks_modified,
         args.champions||champion_modified,
         args.summoner_spells||summoner_spells_modified,
+        args.augments||augments_modified,
     )
     .await
     .unwrap();
+    // rebuilt on every new Data Dragon version, like the sprites on new images
+    let version = get_current_version().await.unwrap();
+    if args.metadata || is_metadata_outdated(&version).await {
+        generate_metadata(version).await.unwrap();
+    }
     let dest_path = get_assets_path().join("logo.avif");
     if args.logo || !dest_path.exists(){
         let logo_path = get_temp_path().join("logo.png");
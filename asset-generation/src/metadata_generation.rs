@@ -0,0 +1,218 @@
+use crate::{get_assets_path, get_temp_path, AppError, AppResult, StaticUrl};
+use common::asset_metadata::{
+    AssetMetadata, ChampionMetadata, ItemMetadata, PerkMetadata, SummonerSpellMetadata,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+#[derive(Deserialize)]
+struct JsonData<T> {
+    data: HashMap<String, T>,
+}
+
+#[derive(Deserialize)]
+struct JsonChampion {
+    key: String,
+    name: String,
+    title: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct JsonItem {
+    name: String,
+    #[serde(default)]
+    description: String,
+    gold: JsonItemGold,
+}
+
+#[derive(Deserialize)]
+struct JsonItemGold {
+    total: i32,
+}
+
+#[derive(Deserialize)]
+struct JsonPerk {
+    id: u16,
+    name: String,
+    #[serde(rename = "shortDesc", default)]
+    short_desc: String,
+}
+
+#[derive(Deserialize)]
+struct JsonPerkStyle {
+    id: u16,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct JsonSummonerSpell {
+    key: String,
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    cooldown: Vec<f64>,
+}
+
+/// Data Dragon version of the last generated `metadata.bin`.
+const METADATA_VERSION_FILE: &str = "metadata.version";
+
+/// Whether `metadata.bin` was generated from another Data Dragon version, or never.
+pub async fn is_metadata_outdated(version: &str) -> bool {
+    !get_assets_path().join("metadata.bin").exists()
+        || tokio::fs::read_to_string(get_temp_path().join(METADATA_VERSION_FILE))
+            .await
+            .map_or(true, |generated_version| {
+                generated_version.trim() != version
+            })
+}
+
+/// Writes the bitcode encoded `AssetMetadata` read by the icon tooltips.
+pub async fn generate_metadata(version: String) -> AppResult<()> {
+    let (champions, items, perks, perk_styles, summoner_spells) = tokio::join!(
+        StaticUrl::Champions {
+            version: version.clone()
+        }
+        .get(),
+        StaticUrl::Items {
+            version: version.clone()
+        }
+        .get(),
+        StaticUrl::Perks.get(),
+        StaticUrl::Perks2 {
+            version: version.clone()
+        }
+        .get(),
+        StaticUrl::SummonerSpells {
+            version: version.clone()
+        }
+        .get(),
+    );
+    let champions: JsonData<JsonChampion> = serde_json::from_str(champions?.as_str())?;
+    let items: JsonData<JsonItem> = serde_json::from_str(items?.as_str())?;
+    let perks: Vec<JsonPerk> = serde_json::from_str(perks?.as_str())?;
+    let perk_styles: Vec<JsonPerkStyle> = serde_json::from_str(perk_styles?.as_str())?;
+    let summoner_spells: JsonData<JsonSummonerSpell> =
+        serde_json::from_str(summoner_spells?.as_str())?;
+
+    let mut metadata = AssetMetadata {
+        champions: champions
+            .data
+            .into_values()
+            .map(|champion| {
+                Ok(ChampionMetadata {
+                    id: parse_id(&champion.key)?,
+                    name: champion.name,
+                    title: champion.title,
+                    tags: champion.tags,
+                })
+            })
+            .collect::<AppResult<_>>()?,
+        items: items
+            .data
+            .into_iter()
+            .map(|(id, item)| {
+                let (stats, description) = split_item_description(&item.description);
+                Ok(ItemMetadata {
+                    id: parse_id(&id)?,
+                    name: item.name,
+                    description,
+                    gold: item.gold.total.clamp(0, u16::MAX as i32) as u16,
+                    stats,
+                })
+            })
+            .collect::<AppResult<_>>()?,
+        // the styles are only listed in the runes reforged
+        perks: perks
+            .into_iter()
+            .map(|perk| PerkMetadata {
+                id: perk.id,
+                name: perk.name,
+                description: to_plain_text(&perk.short_desc),
+            })
+            .chain(perk_styles.into_iter().map(|style| PerkMetadata {
+                id: style.id,
+                name: style.name,
+                description: String::new(),
+            }))
+            .collect(),
+        summoner_spells: summoner_spells
+            .data
+            .into_values()
+            .map(|summoner_spell| {
+                Ok(SummonerSpellMetadata {
+                    id: parse_id(&summoner_spell.key)?,
+                    name: summoner_spell.name,
+                    description: to_plain_text(&summoner_spell.description),
+                    cooldown: summoner_spell.cooldown.first().copied().unwrap_or_default() as u16,
+                })
+            })
+            .collect::<AppResult<_>>()?,
+    };
+    // looked up by binary search
+    metadata.champions.sort_by_key(|champion| champion.id);
+    metadata.items.sort_by_key(|item| item.id);
+    metadata.perks.sort_by_key(|perk| perk.id);
+    metadata.perks.dedup_by_key(|perk| perk.id);
+    metadata
+        .summoner_spells
+        .sort_by_key(|summoner_spell| summoner_spell.id);
+
+    let bytes = bitcode::encode(&metadata);
+    tokio::fs::write(get_assets_path().join("metadata.bin"), &bytes).await?;
+    println!("Generated: metadata.bin ({} bytes)", bytes.len());
+    tokio::fs::write(get_temp_path().join(METADATA_VERSION_FILE), version).await?;
+    Ok(())
+}
+
+/// Splits the `<stats>` block of an item description from its passives and actives.
+fn split_item_description(description: &str) -> (Vec<String>, String) {
+    match (description.find("<stats>"), description.find("</stats>")) {
+        (Some(start), Some(end)) if start < end => {
+            let stats = to_plain_text(&description[start + "<stats>".len()..end])
+                .lines()
+                .map(str::to_string)
+                .collect();
+            let rest = format!(
+                "{}{}",
+                &description[..start],
+                &description[end + "</stats>".len()..]
+            );
+            (stats, to_plain_text(&rest))
+        }
+        _ => (Vec::new(), to_plain_text(description)),
+    }
+}
+
+/// Data Dragon and CommunityDragon texts are html like: `<br>` become new lines, the other tags are
+/// dropped.
+fn to_plain_text(text: &str) -> String {
+    let text = text
+        .replace("<br>", "\n")
+        .replace("<br/>", "\n")
+        .replace("<br />", "\n");
+    let mut plain = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => plain.push(c),
+            _ => {}
+        }
+    }
+    plain
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_id<T: FromStr>(key: &str) -> AppResult<T> {
+    key.parse()
+        .map_err(|_| AppError::CustomError(format!("Invalid id: {}", key)))
+}
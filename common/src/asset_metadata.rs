@@ -0,0 +1,75 @@
+use bitcode::{Decode, Encode};
+
+/// Bitcode encoded `AssetMetadata`, written by asset-generation and fetched by the client on the
+/// first hovered icon. `/assets` is cached for a year, the client appends the content hash.
+pub const ASSET_METADATA_PATH: &str = "/assets/metadata.bin";
+
+/// Tooltip data of the icons, every list is sorted by id.
+#[derive(Debug, Clone, Default, Encode, Decode)]
+pub struct AssetMetadata {
+    pub champions: Vec<ChampionMetadata>,
+    pub items: Vec<ItemMetadata>,
+    pub perks: Vec<PerkMetadata>,
+    pub summoner_spells: Vec<SummonerSpellMetadata>,
+}
+
+impl AssetMetadata {
+    pub fn champion(&self, id: u16) -> Option<&ChampionMetadata> {
+        find_by_id(&self.champions, id, |champion| champion.id)
+    }
+
+    pub fn item(&self, id: u32) -> Option<&ItemMetadata> {
+        find_by_id(&self.items, id, |item| item.id)
+    }
+
+    pub fn perk(&self, id: u16) -> Option<&PerkMetadata> {
+        find_by_id(&self.perks, id, |perk| perk.id)
+    }
+
+    pub fn summoner_spell(&self, id: u16) -> Option<&SummonerSpellMetadata> {
+        find_by_id(&self.summoner_spells, id, |summoner_spell| summoner_spell.id)
+    }
+}
+
+#[derive(Debug, Clone, Default, Encode, Decode)]
+pub struct ChampionMetadata {
+    pub id: u16,
+    pub name: String,
+    pub title: String,
+    /// Classes, such as `Fighter` or `Mage`.
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Encode, Decode)]
+pub struct ItemMetadata {
+    pub id: u32,
+    pub name: String,
+    /// Passives and actives, lines are separated by `\n`.
+    pub description: String,
+    /// Total cost, including the components.
+    pub gold: u16,
+    /// One line per stat, such as `40 Attack Damage`.
+    pub stats: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Encode, Decode)]
+pub struct PerkMetadata {
+    pub id: u16,
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Default, Encode, Decode)]
+pub struct SummonerSpellMetadata {
+    pub id: u16,
+    pub name: String,
+    pub description: String,
+    /// In seconds.
+    pub cooldown: u16,
+}
+
+fn find_by_id<T, K: Ord>(list: &[T], id: K, key: impl FnMut(&T) -> K) -> Option<&T> {
+    list.binary_search_by_key(&id, key)
+        .ok()
+        .map(|index| &list[index])
+}
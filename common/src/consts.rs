@@ -1,5 +1,6 @@
 use crate::AssetType;
 
+pub mod augment;
 pub mod champion;
 pub mod game_mode;
 pub mod item;
@@ -76,3 +77,11 @@ impl HasStaticBgAsset for champion::Champion {
         (*self as u16) as i32
     }
 }
+
+impl HasStaticBgAsset for augment::Augment {
+     const ASSET_TYPE: AssetType = AssetType::Augment;
+
+    fn get_id(&self) -> i32 {
+        self.0 as i32
+    }
+}
@@ -0,0 +1,23 @@
+use std::fmt::Formatter;
+
+/// Arena augment id, from the CommunityDragon `cdragon/arena` data.
+#[derive(Copy, Clone, Default)]
+pub struct Augment(pub u16);
+
+impl TryFrom<u16> for Augment {
+    type Error = ();
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        if value == 0 {
+            Err(())
+        } else {
+            Ok(Augment(value))
+        }
+    }
+}
+
+impl std::fmt::Display for Augment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
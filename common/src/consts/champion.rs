@@ -1,3 +1,6 @@
+// Generated by asset-generation from Data Dragon and CommunityDragon, do not edit by hand.
+// Run `cargo run --bin asset-generation -- --consts` to update it.
+
 #[repr(u16)]
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum Champion {
@@ -83,6 +86,7 @@ pub enum Champion {
     Malzahar = 90,
     Maokai = 57,
     MasterYi = 11,
+    Mel = 800,
     Milio = 902,
     MissFortune = 21,
     Mordekaiser = 82,
@@ -163,6 +167,7 @@ pub enum Champion {
     Yasuo = 157,
     Yone = 777,
     Yorick = 83,
+    Yunara = 804,
     Yuumi = 350,
     Zac = 154,
     Zed = 238,
@@ -257,6 +262,7 @@ impl Champion {
             Champion::Malzahar => "Malzahar",
             Champion::Maokai => "Maokai",
             Champion::MasterYi => "MasterYi",
+            Champion::Mel => "Mel",
             Champion::Milio => "Milio",
             Champion::MissFortune => "MissFortune",
             Champion::Mordekaiser => "Mordekaiser",
@@ -337,6 +343,363 @@ impl Champion {
             Champion::Yasuo => "Yasuo",
             Champion::Yone => "Yone",
             Champion::Yorick => "Yorick",
+            Champion::Yunara => "Yunara",
+            Champion::Yuumi => "Yuumi",
+            Champion::Zac => "Zac",
+            Champion::Zed => "Zed",
+            Champion::Zeri => "Zeri",
+            Champion::Ziggs => "Ziggs",
+            Champion::Zilean => "Zilean",
+            Champion::Zoe => "Zoe",
+            Champion::Zyra => "Zyra",
+            Champion::UNKNOWN => "UNKNOWN",
+        }
+    }
+
+    /// Name shown in game.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Champion::Aatrox => "Aatrox",
+            Champion::Ahri => "Ahri",
+            Champion::Akali => "Akali",
+            Champion::Akshan => "Akshan",
+            Champion::Alistar => "Alistar",
+            Champion::Ambessa => "Ambessa",
+            Champion::Amumu => "Amumu",
+            Champion::Anivia => "Anivia",
+            Champion::Annie => "Annie",
+            Champion::Aphelios => "Aphelios",
+            Champion::Ashe => "Ashe",
+            Champion::AurelionSol => "Aurelion Sol",
+            Champion::Aurora => "Aurora",
+            Champion::Azir => "Azir",
+            Champion::Bard => "Bard",
+            Champion::BelVeth => "Bel'Veth",
+            Champion::Blitzcrank => "Blitzcrank",
+            Champion::Brand => "Brand",
+            Champion::Braum => "Braum",
+            Champion::Briar => "Briar",
+            Champion::Caitlyn => "Caitlyn",
+            Champion::Camille => "Camille",
+            Champion::Cassiopeia => "Cassiopeia",
+            Champion::ChoGath => "Cho'Gath",
+            Champion::Corki => "Corki",
+            Champion::Darius => "Darius",
+            Champion::Diana => "Diana",
+            Champion::DrMundo => "Dr. Mundo",
+            Champion::Draven => "Draven",
+            Champion::Ekko => "Ekko",
+            Champion::Elise => "Elise",
+            Champion::Evelynn => "Evelynn",
+            Champion::Ezreal => "Ezreal",
+            Champion::Fiddlesticks => "Fiddlesticks",
+            Champion::Fiora => "Fiora",
+            Champion::Fizz => "Fizz",
+            Champion::Galio => "Galio",
+            Champion::Gangplank => "Gangplank",
+            Champion::Garen => "Garen",
+            Champion::Gnar => "Gnar",
+            Champion::Gragas => "Gragas",
+            Champion::Graves => "Graves",
+            Champion::Gwen => "Gwen",
+            Champion::Hecarim => "Hecarim",
+            Champion::Heimerdinger => "Heimerdinger",
+            Champion::Hwei => "Hwei",
+            Champion::Illaoi => "Illaoi",
+            Champion::Irelia => "Irelia",
+            Champion::Ivern => "Ivern",
+            Champion::Janna => "Janna",
+            Champion::JarvanIV => "Jarvan IV",
+            Champion::Jax => "Jax",
+            Champion::Jayce => "Jayce",
+            Champion::Jhin => "Jhin",
+            Champion::Jinx => "Jinx",
+            Champion::KSante => "K'Sante",
+            Champion::KaiSa => "Kai'Sa",
+            Champion::Kalista => "Kalista",
+            Champion::Karma => "Karma",
+            Champion::Karthus => "Karthus",
+            Champion::Kassadin => "Kassadin",
+            Champion::Katarina => "Katarina",
+            Champion::Kayle => "Kayle",
+            Champion::Kayn => "Kayn",
+            Champion::Kennen => "Kennen",
+            Champion::KhaZix => "Kha'Zix",
+            Champion::Kindred => "Kindred",
+            Champion::Kled => "Kled",
+            Champion::KogMaw => "Kog'Maw",
+            Champion::LeBlanc => "LeBlanc",
+            Champion::LeeSin => "Lee Sin",
+            Champion::Leona => "Leona",
+            Champion::Lillia => "Lillia",
+            Champion::Lissandra => "Lissandra",
+            Champion::Lucian => "Lucian",
+            Champion::Lulu => "Lulu",
+            Champion::Lux => "Lux",
+            Champion::Malphite => "Malphite",
+            Champion::Malzahar => "Malzahar",
+            Champion::Maokai => "Maokai",
+            Champion::MasterYi => "Master Yi",
+            Champion::Mel => "Mel",
+            Champion::Milio => "Milio",
+            Champion::MissFortune => "Miss Fortune",
+            Champion::Mordekaiser => "Mordekaiser",
+            Champion::Morgana => "Morgana",
+            Champion::NAAFIRI => "Naafiri",
+            Champion::Nami => "Nami",
+            Champion::Nasus => "Nasus",
+            Champion::Nautilus => "Nautilus",
+            Champion::Neeko => "Neeko",
+            Champion::Nidalee => "Nidalee",
+            Champion::Nilah => "Nilah",
+            Champion::Nocturne => "Nocturne",
+            Champion::NunuWillump => "Nunu & Willump",
+            Champion::Olaf => "Olaf",
+            Champion::Orianna => "Orianna",
+            Champion::Ornn => "Ornn",
+            Champion::Pantheon => "Pantheon",
+            Champion::Poppy => "Poppy",
+            Champion::Pyke => "Pyke",
+            Champion::Qiyana => "Qiyana",
+            Champion::Quinn => "Quinn",
+            Champion::Rakan => "Rakan",
+            Champion::Rammus => "Rammus",
+            Champion::RekSai => "Rek'Sai",
+            Champion::Rell => "Rell",
+            Champion::RenataGlasc => "Renata Glasc",
+            Champion::Renekton => "Renekton",
+            Champion::Rengar => "Rengar",
+            Champion::Riven => "Riven",
+            Champion::Rumble => "Rumble",
+            Champion::Ryze => "Ryze",
+            Champion::Samira => "Samira",
+            Champion::Sejuani => "Sejuani",
+            Champion::Senna => "Senna",
+            Champion::Seraphine => "Seraphine",
+            Champion::Sett => "Sett",
+            Champion::Shaco => "Shaco",
+            Champion::Shen => "Shen",
+            Champion::Shyvana => "Shyvana",
+            Champion::Singed => "Singed",
+            Champion::Sion => "Sion",
+            Champion::Sivir => "Sivir",
+            Champion::Skarner => "Skarner",
+            Champion::Smolder => "Smolder",
+            Champion::Sona => "Sona",
+            Champion::Soraka => "Soraka",
+            Champion::Swain => "Swain",
+            Champion::Sylas => "Sylas",
+            Champion::Syndra => "Syndra",
+            Champion::TahmKench => "Tahm Kench",
+            Champion::Taliyah => "Taliyah",
+            Champion::Talon => "Talon",
+            Champion::Taric => "Taric",
+            Champion::Teemo => "Teemo",
+            Champion::Thresh => "Thresh",
+            Champion::Tristana => "Tristana",
+            Champion::Trundle => "Trundle",
+            Champion::Tryndamere => "Tryndamere",
+            Champion::TwistedFate => "Twisted Fate",
+            Champion::Twitch => "Twitch",
+            Champion::Udyr => "Udyr",
+            Champion::Urgot => "Urgot",
+            Champion::Varus => "Varus",
+            Champion::Vayne => "Vayne",
+            Champion::Veigar => "Veigar",
+            Champion::VelKoz => "Vel'Koz",
+            Champion::Vex => "Vex",
+            Champion::Vi => "Vi",
+            Champion::Viego => "Viego",
+            Champion::Viktor => "Viktor",
+            Champion::Vladimir => "Vladimir",
+            Champion::Volibear => "Volibear",
+            Champion::Warwick => "Warwick",
+            Champion::Wukong => "Wukong",
+            Champion::Xayah => "Xayah",
+            Champion::Xerath => "Xerath",
+            Champion::XinZhao => "Xin Zhao",
+            Champion::Yasuo => "Yasuo",
+            Champion::Yone => "Yone",
+            Champion::Yorick => "Yorick",
+            Champion::Yunara => "Yunara",
+            Champion::Yuumi => "Yuumi",
+            Champion::Zac => "Zac",
+            Champion::Zed => "Zed",
+            Champion::Zeri => "Zeri",
+            Champion::Ziggs => "Ziggs",
+            Champion::Zilean => "Zilean",
+            Champion::Zoe => "Zoe",
+            Champion::Zyra => "Zyra",
+            Champion::UNKNOWN => "Unknown",
+        }
+    }
+
+    /// Data Dragon id, used in the asset urls and the match participants.
+    pub const fn alias(&self) -> &'static str {
+        match self {
+            Champion::Aatrox => "Aatrox",
+            Champion::Ahri => "Ahri",
+            Champion::Akali => "Akali",
+            Champion::Akshan => "Akshan",
+            Champion::Alistar => "Alistar",
+            Champion::Ambessa => "Ambessa",
+            Champion::Amumu => "Amumu",
+            Champion::Anivia => "Anivia",
+            Champion::Annie => "Annie",
+            Champion::Aphelios => "Aphelios",
+            Champion::Ashe => "Ashe",
+            Champion::AurelionSol => "AurelionSol",
+            Champion::Aurora => "Aurora",
+            Champion::Azir => "Azir",
+            Champion::Bard => "Bard",
+            Champion::BelVeth => "Belveth",
+            Champion::Blitzcrank => "Blitzcrank",
+            Champion::Brand => "Brand",
+            Champion::Braum => "Braum",
+            Champion::Briar => "Briar",
+            Champion::Caitlyn => "Caitlyn",
+            Champion::Camille => "Camille",
+            Champion::Cassiopeia => "Cassiopeia",
+            Champion::ChoGath => "Chogath",
+            Champion::Corki => "Corki",
+            Champion::Darius => "Darius",
+            Champion::Diana => "Diana",
+            Champion::DrMundo => "DrMundo",
+            Champion::Draven => "Draven",
+            Champion::Ekko => "Ekko",
+            Champion::Elise => "Elise",
+            Champion::Evelynn => "Evelynn",
+            Champion::Ezreal => "Ezreal",
+            Champion::Fiddlesticks => "Fiddlesticks",
+            Champion::Fiora => "Fiora",
+            Champion::Fizz => "Fizz",
+            Champion::Galio => "Galio",
+            Champion::Gangplank => "Gangplank",
+            Champion::Garen => "Garen",
+            Champion::Gnar => "Gnar",
+            Champion::Gragas => "Gragas",
+            Champion::Graves => "Graves",
+            Champion::Gwen => "Gwen",
+            Champion::Hecarim => "Hecarim",
+            Champion::Heimerdinger => "Heimerdinger",
+            Champion::Hwei => "Hwei",
+            Champion::Illaoi => "Illaoi",
+            Champion::Irelia => "Irelia",
+            Champion::Ivern => "Ivern",
+            Champion::Janna => "Janna",
+            Champion::JarvanIV => "JarvanIV",
+            Champion::Jax => "Jax",
+            Champion::Jayce => "Jayce",
+            Champion::Jhin => "Jhin",
+            Champion::Jinx => "Jinx",
+            Champion::KSante => "KSante",
+            Champion::KaiSa => "Kaisa",
+            Champion::Kalista => "Kalista",
+            Champion::Karma => "Karma",
+            Champion::Karthus => "Karthus",
+            Champion::Kassadin => "Kassadin",
+            Champion::Katarina => "Katarina",
+            Champion::Kayle => "Kayle",
+            Champion::Kayn => "Kayn",
+            Champion::Kennen => "Kennen",
+            Champion::KhaZix => "Khazix",
+            Champion::Kindred => "Kindred",
+            Champion::Kled => "Kled",
+            Champion::KogMaw => "KogMaw",
+            Champion::LeBlanc => "Leblanc",
+            Champion::LeeSin => "LeeSin",
+            Champion::Leona => "Leona",
+            Champion::Lillia => "Lillia",
+            Champion::Lissandra => "Lissandra",
+            Champion::Lucian => "Lucian",
+            Champion::Lulu => "Lulu",
+            Champion::Lux => "Lux",
+            Champion::Malphite => "Malphite",
+            Champion::Malzahar => "Malzahar",
+            Champion::Maokai => "Maokai",
+            Champion::MasterYi => "MasterYi",
+            Champion::Mel => "Mel",
+            Champion::Milio => "Milio",
+            Champion::MissFortune => "MissFortune",
+            Champion::Mordekaiser => "Mordekaiser",
+            Champion::Morgana => "Morgana",
+            Champion::NAAFIRI => "Naafiri",
+            Champion::Nami => "Nami",
+            Champion::Nasus => "Nasus",
+            Champion::Nautilus => "Nautilus",
+            Champion::Neeko => "Neeko",
+            Champion::Nidalee => "Nidalee",
+            Champion::Nilah => "Nilah",
+            Champion::Nocturne => "Nocturne",
+            Champion::NunuWillump => "Nunu",
+            Champion::Olaf => "Olaf",
+            Champion::Orianna => "Orianna",
+            Champion::Ornn => "Ornn",
+            Champion::Pantheon => "Pantheon",
+            Champion::Poppy => "Poppy",
+            Champion::Pyke => "Pyke",
+            Champion::Qiyana => "Qiyana",
+            Champion::Quinn => "Quinn",
+            Champion::Rakan => "Rakan",
+            Champion::Rammus => "Rammus",
+            Champion::RekSai => "RekSai",
+            Champion::Rell => "Rell",
+            Champion::RenataGlasc => "Renata",
+            Champion::Renekton => "Renekton",
+            Champion::Rengar => "Rengar",
+            Champion::Riven => "Riven",
+            Champion::Rumble => "Rumble",
+            Champion::Ryze => "Ryze",
+            Champion::Samira => "Samira",
+            Champion::Sejuani => "Sejuani",
+            Champion::Senna => "Senna",
+            Champion::Seraphine => "Seraphine",
+            Champion::Sett => "Sett",
+            Champion::Shaco => "Shaco",
+            Champion::Shen => "Shen",
+            Champion::Shyvana => "Shyvana",
+            Champion::Singed => "Singed",
+            Champion::Sion => "Sion",
+            Champion::Sivir => "Sivir",
+            Champion::Skarner => "Skarner",
+            Champion::Smolder => "Smolder",
+            Champion::Sona => "Sona",
+            Champion::Soraka => "Soraka",
+            Champion::Swain => "Swain",
+            Champion::Sylas => "Sylas",
+            Champion::Syndra => "Syndra",
+            Champion::TahmKench => "TahmKench",
+            Champion::Taliyah => "Taliyah",
+            Champion::Talon => "Talon",
+            Champion::Taric => "Taric",
+            Champion::Teemo => "Teemo",
+            Champion::Thresh => "Thresh",
+            Champion::Tristana => "Tristana",
+            Champion::Trundle => "Trundle",
+            Champion::Tryndamere => "Tryndamere",
+            Champion::TwistedFate => "TwistedFate",
+            Champion::Twitch => "Twitch",
+            Champion::Udyr => "Udyr",
+            Champion::Urgot => "Urgot",
+            Champion::Varus => "Varus",
+            Champion::Vayne => "Vayne",
+            Champion::Veigar => "Veigar",
+            Champion::VelKoz => "Velkoz",
+            Champion::Vex => "Vex",
+            Champion::Vi => "Vi",
+            Champion::Viego => "Viego",
+            Champion::Viktor => "Viktor",
+            Champion::Vladimir => "Vladimir",
+            Champion::Volibear => "Volibear",
+            Champion::Warwick => "Warwick",
+            Champion::Wukong => "MonkeyKing",
+            Champion::Xayah => "Xayah",
+            Champion::Xerath => "Xerath",
+            Champion::XinZhao => "XinZhao",
+            Champion::Yasuo => "Yasuo",
+            Champion::Yone => "Yone",
+            Champion::Yorick => "Yorick",
+            Champion::Yunara => "Yunara",
             Champion::Yuumi => "Yuumi",
             Champion::Zac => "Zac",
             Champion::Zed => "Zed",
@@ -434,6 +797,7 @@ impl From<u16> for Champion {
             90 => Champion::Malzahar,
             57 => Champion::Maokai,
             11 => Champion::MasterYi,
+            800 => Champion::Mel,
             902 => Champion::Milio,
             21 => Champion::MissFortune,
             82 => Champion::Mordekaiser,
@@ -514,6 +878,7 @@ impl From<u16> for Champion {
             157 => Champion::Yasuo,
             777 => Champion::Yone,
             83 => Champion::Yorick,
+            804 => Champion::Yunara,
             350 => Champion::Yuumi,
             154 => Champion::Zac,
             238 => Champion::Zed,
@@ -615,6 +980,7 @@ pub static CHAMPION_OPTIONS: &[(u16, &str)] = &[
     (Champion::Malzahar as u16, Champion::Malzahar.to_str()),
     (Champion::Maokai as u16, Champion::Maokai.to_str()),
     (Champion::MasterYi as u16, Champion::MasterYi.to_str()),
+    (Champion::Mel as u16, Champion::Mel.to_str()),
     (Champion::Milio as u16, Champion::Milio.to_str()),
     (Champion::MissFortune as u16, Champion::MissFortune.to_str()),
     (Champion::Mordekaiser as u16, Champion::Mordekaiser.to_str()),
@@ -695,6 +1061,7 @@ pub static CHAMPION_OPTIONS: &[(u16, &str)] = &[
     (Champion::Yasuo as u16, Champion::Yasuo.to_str()),
     (Champion::Yone as u16, Champion::Yone.to_str()),
     (Champion::Yorick as u16, Champion::Yorick.to_str()),
+    (Champion::Yunara as u16, Champion::Yunara.to_str()),
     (Champion::Yuumi as u16, Champion::Yuumi.to_str()),
     (Champion::Zac as u16, Champion::Zac.to_str()),
     (Champion::Zed as u16, Champion::Zed.to_str()),
@@ -1,9 +1,23 @@
+// Generated by asset-generation from Data Dragon and CommunityDragon, do not edit by hand.
+// Run `cargo run --bin asset-generation -- --consts` to update it.
+
 use std::fmt::Formatter;
 
 #[derive(Copy, Clone, Default)]
 pub struct Item(pub u32);
 
+impl Item {
+    /// Name shown in game, `None` for the ids missing from Data Dragon.
+    pub fn name(&self) -> Option<&'static str> {
+        ITEM_NAMES
+            .binary_search_by_key(&self.0, |(id, _)| *id)
+            .ok()
+            .map(|index| ITEM_NAMES[index].1)
+    }
+}
 
+/// Sorted by id.
+pub static ITEM_NAMES: &[(u32, &str)] = &[];
 
 impl TryFrom<u32> for Item {
     type Error = ();
@@ -1,8 +1,12 @@
+// Generated by asset-generation from Data Dragon and CommunityDragon, do not edit by hand.
+// Run `cargo run --bin asset-generation -- --consts` to update it.
+
 use bitcode::{Decode, Encode};
 
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, Encode, Decode, Eq, PartialEq, Hash)]
 pub enum Map {
+    UNKNOWN = 0,
     SummonersRiftOriginalSummerVariant = 1,
     SummonersRiftOriginalAutumnVariant = 2,
     TheProvingGrounds = 3,
@@ -42,6 +46,7 @@ impl Map {
             Map::Convergence => "Convergence",
             Map::Arena => "Arena",
             Map::Swarm => "Swarm",
+            Map::UNKNOWN => "Unknown",
         }
     }
 }
@@ -66,7 +71,7 @@ impl From<u8> for Map {
             22 => Map::Convergence,
             30 => Map::Arena,
             33 => Map::Swarm,
-            _ => panic!("Invalid map id"),
+            _ => Map::UNKNOWN,
         }
     }
 }
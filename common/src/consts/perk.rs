@@ -1,7 +1,10 @@
+// Generated by asset-generation from Data Dragon and CommunityDragon, do not edit by hand.
+// Run `cargo run --bin asset-generation -- --consts` to update it.
+
 use std::fmt::Formatter;
 
 #[repr(u16)]
-#[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum Perk {
     UNKNOWN = 0,
     StatsHealScaling = 5001,
@@ -83,10 +86,95 @@ pub enum Perk {
     GatheringStorm = 8236,
 }
 
+impl Perk {
+    /// Name shown in game.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Perk::StatsHealScaling => "Health Scaling",
+            Perk::StatsArmor => "Armor",
+            Perk::StatsMagicResist => "Magic Resist",
+            Perk::StatsAttackSpeed => "Attack Speed",
+            Perk::StatsAbilityHaste => "Ability Haste",
+            Perk::StatsAdaptiveForce => "Adaptive Force",
+            Perk::StatsMovementSpeed => "Move Speed",
+            Perk::StatsHealth => "Health",
+            Perk::StatsResistScaling => "Resist Scaling",
+            Perk::StatsTenacitySlowResist => "Tenacity and Slow Resist",
+            Perk::Domination => "Domination",
+            Perk::Electrocute => "Electrocute",
+            Perk::DarkHarvest => "Dark Harvest",
+            Perk::HailOfBlades => "Hail of Blades",
+            Perk::CheapShot => "Cheap Shot",
+            Perk::TasteOfBlood => "Taste of Blood",
+            Perk::SuddenImpact => "Sudden Impact",
+            Perk::ZombieWard => "Zombie Ward",
+            Perk::GhostPoro => "Ghost Poro",
+            Perk::EyeballCollection => "Eyeball Collection",
+            Perk::RavenousHunter => "Ravenous Hunter",
+            Perk::IngeniousHunter => "Ingenious Hunter",
+            Perk::RelentlessHunter => "Relentless Hunter",
+            Perk::UltimateHunter => "Ultimate Hunter",
+            Perk::Inspiration => "Inspiration",
+            Perk::GlacialAugment => "Glacial Augment",
+            Perk::UnsealedSpellbook => "Unsealed Spellbook",
+            Perk::FirstStrike => "First Strike",
+            Perk::HextechFlashtraption => "Hextech Flashtraption",
+            Perk::MagicalFootwear => "Magical Footwear",
+            Perk::CashBack => "Cash Back",
+            Perk::PerfectTiming => "Perfect Timing",
+            Perk::TimeWarpTonic => "Time Warp Tonic",
+            Perk::BiscuitDelivery => "Biscuit Delivery",
+            Perk::CosmicInsight => "Cosmic Insight",
+            Perk::ApproachVelocity => "Approach Velocity",
+            Perk::JackOfAllTrades => "Jack Of All Trades",
+            Perk::Precision => "Precision",
+            Perk::PressTheAttack => "Press the Attack",
+            Perk::LethalTempo => "Lethal Tempo",
+            Perk::FleetFootwork => "Fleet Footwork",
+            Perk::Conqueror => "Conqueror",
+            Perk::AbsorbLife => "Absorb Life",
+            Perk::Triumph => "Triumph",
+            Perk::PresenceOfMind => "Presence of Mind",
+            Perk::LegendAlacrity => "Legend: Alacrity",
+            Perk::LegendHaste => "Legend: Haste",
+            Perk::LegendBloodline => "Legend: Bloodline",
+            Perk::CoupDeGrace => "Coup de Grace",
+            Perk::CutDown => "Cut Down",
+            Perk::LastStand => "Last Stand",
+            Perk::Resolve => "Resolve",
+            Perk::GraspOfTheUndying => "Grasp of the Undying",
+            Perk::Aftershock => "Aftershock",
+            Perk::Guardian => "Guardian",
+            Perk::Demolish => "Demolish",
+            Perk::FontOfLife => "Font of Life",
+            Perk::ShieldBash => "Shield Bash",
+            Perk::Conditioning => "Conditioning",
+            Perk::SecondWind => "Second Wind",
+            Perk::BonePlating => "Bone Plating",
+            Perk::Overgrowth => "Overgrowth",
+            Perk::Revitalize => "Revitalize",
+            Perk::Unflinching => "Unflinching",
+            Perk::Sorcery => "Sorcery",
+            Perk::SummonAery => "Summon Aery",
+            Perk::ArcaneComet => "Arcane Comet",
+            Perk::PhaseRush => "Phase Rush",
+            Perk::NullifyingOrb => "Nullifying Orb",
+            Perk::ManaflowBand => "Manaflow Band",
+            Perk::NimbusCloack => "Nimbus Cloak",
+            Perk::Transcendence => "Transcendence",
+            Perk::Celerity => "Celerity",
+            Perk::AbsoluteFocus => "Absolute Focus",
+            Perk::Scorch => "Scorch",
+            Perk::Waterwalking => "Waterwalking",
+            Perk::GatheringStorm => "Gathering Storm",
+            Perk::UNKNOWN => "Unknown",
+        }
+    }
+}
+
 impl From<u16> for Perk {
     fn from(value: u16) -> Self {
         match value {
-            0 => Perk::UNKNOWN,
             5001 => Perk::StatsHealScaling,
             5002 => Perk::StatsArmor,
             5003 => Perk::StatsMagicResist,
@@ -174,81 +262,81 @@ impl std::fmt::Display for Perk {
         let str = match self {
             Perk::UNKNOWN => "UNKNOWN",
             Perk::StatsHealScaling => "StatsHealScaling",
-            Perk::StatsArmor => "StatsArmor ",
+            Perk::StatsArmor => "StatsArmor",
             Perk::StatsMagicResist => "StatsMagicResist",
-            Perk::StatsAttackSpeed => "StatsAttackSpeed ",
+            Perk::StatsAttackSpeed => "StatsAttackSpeed",
             Perk::StatsAbilityHaste => "StatsAbilityHaste",
-            Perk::StatsAdaptiveForce => "StatsAdaptiveForce ",
+            Perk::StatsAdaptiveForce => "StatsAdaptiveForce",
             Perk::StatsMovementSpeed => "StatsMovementSpeed",
-            Perk::StatsHealth => "StatsHealth ",
+            Perk::StatsHealth => "StatsHealth",
             Perk::StatsResistScaling => "StatsResistScaling",
-            Perk::StatsTenacitySlowResist => "StatsTenacitySlowResist ",
+            Perk::StatsTenacitySlowResist => "StatsTenacitySlowResist",
             Perk::Domination => "Domination",
-            Perk::Electrocute => "Electrocute ",
+            Perk::Electrocute => "Electrocute",
             Perk::DarkHarvest => "DarkHarvest",
-            Perk::HailOfBlades => "HailOfBlades ",
+            Perk::HailOfBlades => "HailOfBlades",
             Perk::CheapShot => "CheapShot",
-            Perk::TasteOfBlood => "TasteOfBlood ",
+            Perk::TasteOfBlood => "TasteOfBlood",
             Perk::SuddenImpact => "SuddenImpact",
-            Perk::ZombieWard => "ZombieWard ",
+            Perk::ZombieWard => "ZombieWard",
             Perk::GhostPoro => "GhostPoro",
-            Perk::EyeballCollection => "EyeballCollection ",
+            Perk::EyeballCollection => "EyeballCollection",
             Perk::RavenousHunter => "RavenousHunter",
-            Perk::IngeniousHunter => "IngeniousHunter ",
+            Perk::IngeniousHunter => "IngeniousHunter",
             Perk::RelentlessHunter => "RelentlessHunter",
-            Perk::UltimateHunter => "UltimateHunter ",
+            Perk::UltimateHunter => "UltimateHunter",
             Perk::Inspiration => "Inspiration",
-            Perk::GlacialAugment => "GlacialAugment ",
+            Perk::GlacialAugment => "GlacialAugment",
             Perk::UnsealedSpellbook => "UnsealedSpellbook",
-            Perk::FirstStrike => "FirstStrike ",
+            Perk::FirstStrike => "FirstStrike",
             Perk::HextechFlashtraption => "HextechFlashtraption",
-            Perk::MagicalFootwear => "MagicalFootwear ",
+            Perk::MagicalFootwear => "MagicalFootwear",
             Perk::CashBack => "CashBack",
-            Perk::PerfectTiming => "PerfectTiming ",
+            Perk::PerfectTiming => "PerfectTiming",
             Perk::TimeWarpTonic => "TimeWarpTonic",
-            Perk::BiscuitDelivery => "BiscuitDelivery ",
+            Perk::BiscuitDelivery => "BiscuitDelivery",
             Perk::CosmicInsight => "CosmicInsight",
-            Perk::ApproachVelocity => "ApproachVelocity ",
+            Perk::ApproachVelocity => "ApproachVelocity",
             Perk::JackOfAllTrades => "JackOfAllTrades",
-            Perk::Precision => "Precision ",
+            Perk::Precision => "Precision",
             Perk::PressTheAttack => "PressTheAttack",
-            Perk::LethalTempo => "LethalTempo ",
+            Perk::LethalTempo => "LethalTempo",
             Perk::FleetFootwork => "FleetFootwork",
-            Perk::Conqueror => "Conqueror ",
+            Perk::Conqueror => "Conqueror",
             Perk::AbsorbLife => "AbsorbLife",
-            Perk::Triumph => "Triumph ",
+            Perk::Triumph => "Triumph",
             Perk::PresenceOfMind => "PresenceOfMind",
-            Perk::LegendAlacrity => "LegendAlacrity ",
+            Perk::LegendAlacrity => "LegendAlacrity",
             Perk::LegendHaste => "LegendHaste",
-            Perk::LegendBloodline => "LegendBloodline ",
+            Perk::LegendBloodline => "LegendBloodline",
             Perk::CoupDeGrace => "CoupDeGrace",
-            Perk::CutDown => "CutDown ",
+            Perk::CutDown => "CutDown",
             Perk::LastStand => "LastStand",
-            Perk::Resolve => "Resolve ",
+            Perk::Resolve => "Resolve",
             Perk::GraspOfTheUndying => "GraspOfTheUndying",
-            Perk::Aftershock => "Aftershock ",
+            Perk::Aftershock => "Aftershock",
             Perk::Guardian => "Guardian",
-            Perk::Demolish => "Demolish ",
+            Perk::Demolish => "Demolish",
             Perk::FontOfLife => "FontOfLife",
-            Perk::ShieldBash => "ShieldBash ",
+            Perk::ShieldBash => "ShieldBash",
             Perk::Conditioning => "Conditioning",
-            Perk::SecondWind => "SecondWind ",
+            Perk::SecondWind => "SecondWind",
             Perk::BonePlating => "BonePlating",
-            Perk::Overgrowth => "Overgrowth ",
+            Perk::Overgrowth => "Overgrowth",
             Perk::Revitalize => "Revitalize",
-            Perk::Unflinching => "Unflinching ",
+            Perk::Unflinching => "Unflinching",
             Perk::Sorcery => "Sorcery",
-            Perk::SummonAery => "SummonAery ",
+            Perk::SummonAery => "SummonAery",
             Perk::ArcaneComet => "ArcaneComet",
-            Perk::PhaseRush => "PhaseRush ",
+            Perk::PhaseRush => "PhaseRush",
             Perk::NullifyingOrb => "NullifyingOrb",
-            Perk::ManaflowBand => "ManaflowBand ",
+            Perk::ManaflowBand => "ManaflowBand",
             Perk::NimbusCloack => "NimbusCloack",
-            Perk::Transcendence => "Transcendence ",
+            Perk::Transcendence => "Transcendence",
             Perk::Celerity => "Celerity",
-            Perk::AbsoluteFocus => "AbsoluteFocus ",
+            Perk::AbsoluteFocus => "AbsoluteFocus",
             Perk::Scorch => "Scorch",
-            Perk::Waterwalking => "Waterwalking ",
+            Perk::Waterwalking => "Waterwalking",
             Perk::GatheringStorm => "GatheringStorm",
         };
         write!(f, "{}", str)
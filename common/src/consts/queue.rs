@@ -1,3 +1,6 @@
+// Generated by asset-generation from Data Dragon and CommunityDragon, do not edit by hand.
+// Run `cargo run --bin asset-generation -- --consts` to update it.
+
 use bitcode::{Decode, Encode};
 
 #[repr(u8)]
@@ -107,24 +110,139 @@ pub enum Queue {
 impl Queue {
     pub const fn to_str(&self) -> &'static str {
         match self {
+            Queue::Custom => "Custom",
+            Queue::SummonersRift5v5BlindPickDeprecated2 => "5v5 Blind Pick",
+            Queue::SummonersRift5v5RankedSoloDeprecated4 => "5v5 Ranked Solo",
+            Queue::SummonersRift5v5RankedPremade => "5v5 Ranked Premade",
+            Queue::SummonersRiftCoOpVsAi => "Co-op vs. AI",
+            Queue::TwistedTreeline3v3Normal => "3v3 Normal",
+            Queue::TwistedTreeline3v3RankedFlexDeprecated9 => "3v3 Ranked Flex",
+            Queue::SummonersRift5v5DraftPickDeprecated14 => "5v5 Draft Pick",
+            Queue::CrystalScar5v5DominionBlindPick => "5v5 Dominion Blind Pick",
+            Queue::CrystalScar5v5DominionDraftPick => "5v5 Dominion Draft Pick",
+            Queue::CrystalScarDominionCoOpVsAi => "Dominion Co-op vs. AI",
+            Queue::SummonersRiftCoOpVsAiIntroBotDeprecated31 => "Co-op vs. AI Intro Bot",
+            Queue::SummonersRiftCoOpVsAiBeginnerBotDeprecated32 => "Co-op vs. AI Beginner Bot",
+            Queue::SummonersRiftCoOpVsAiIntermediateBotDeprecated33 => {
+                "Co-op vs. AI Intermediate Bot"
+            }
+            Queue::TwistedTreeline3v3RankedTeam => "3v3 Ranked Team",
+            Queue::SummonersRift5v5RankedTeam => "5v5 Ranked Team",
+            Queue::TwistedTreelineCoOpVsAi => "Co-op vs. AI",
+            Queue::SummonersRift5v5TeamBuilder => "5v5 Team Builder",
+            Queue::HowlingAbyss5v5AramDeprecated65 => "5v5 ARAM",
+            Queue::HowlingAbyssAramCoOpVsAi => "ARAM Co-op vs. AI",
+            Queue::SummonersRiftOneForAllDeprecated70 => "One for All",
+            Queue::HowlingAbyss1v1SnowdownShowdown => "1v1 Snowdown Showdown",
+            Queue::HowlingAbyss2v2SnowdownShowdown => "2v2 Snowdown Showdown",
+            Queue::SummonersRift6v6Hexakill => "6v6 Hexakill",
+            Queue::SummonersRiftUltraRapidFire => "Ultra Rapid Fire",
+            Queue::HowlingAbyssOneForAllMirrorMode => "One for All Mirror Mode",
+            Queue::SummonersRiftCoOpVsAiUltraRapidFire => "Co-op vs. AI Ultra Rapid Fire",
+            Queue::SummonersRiftDoomBotsRank1 => "Doom Bots Rank 1",
+            Queue::SummonersRiftDoomBotsRank2 => "Doom Bots Rank 2",
+            Queue::SummonersRiftDoomBotsRank5 => "Doom Bots Rank 5",
+            Queue::CrystalScarAscensionDeprecated96 => "Ascension",
+            Queue::TwistedTreeline6v6Hexakill => "6v6 Hexakill",
+            Queue::ButchersBridge5v5Aram => "5v5 ARAM",
+            Queue::HowlingAbyssLegendOfThePoroKingDeprecated300 => "Legend of the Poro King",
+            Queue::SummonersRiftNemesis => "Nemesis",
+            Queue::SummonersRiftBlackMarketBrawlers => "Black Market Brawlers",
+            Queue::SummonersRiftNexusSiegeDeprecated315 => "Nexus Siege",
+            Queue::CrystalScarDefinitelyNotDominion => "Definitely Not Dominion",
+            Queue::SummonersRiftArurfDeprecated318 => "ARURF",
+            Queue::SummonersRiftAllRandom => "All Random",
             Queue::SummonersRift5v5DraftPick => "Normal Draft Pick",
-            Queue::SummonersRift5v5BlindPick => "Normal Blind Pick",
+            Queue::SummonersRift5v5RankedDynamic => "5v5 Ranked Dynamic",
             Queue::SummonersRift5v5RankedSolo => "Ranked Solo/Duo",
+            Queue::SummonersRift5v5BlindPick => "Normal Blind Pick",
             Queue::SummonersRift5v5RankedFlex => "Ranked Flex",
             Queue::HowlingAbyss5v5Aram => "ARAM",
+            Queue::TwistedTreeline3v3BlindPick => "3v3 Blind Pick",
+            Queue::TwistedTreeline3v3RankedFlexDeprecated470 => "3v3 Ranked Flex",
+            Queue::SummonersRiftNormalQuickplay => "Normal Quickplay",
+            Queue::SummonersRiftBloodHuntAssassin => "Blood Hunt Assassin",
+            Queue::CosmicRuinsDarkStarSingularity => "Dark Star Singularity",
+            Queue::SummonersRiftClash => "Clash",
+            Queue::HowlingAbyssAramClash => "ARAM Clash",
+            Queue::TwistedTreelineCoOpVsAiIntermediateBot => "Co-op vs. AI Intermediate Bot",
+            Queue::TwistedTreelineCoOpVsAiIntroBot => "Co-op vs. AI Intro Bot",
+            Queue::TwistedTreelineCoOpVsAiBeginnerBot => "Co-op vs. AI Beginner Bot",
+            Queue::SummonersRiftCoOpVsAiIntroBot => "Co-op vs. AI Intro Bot",
+            Queue::SummonersRiftCoOpVsAiBeginnerBot => "Co-op vs. AI Beginner Bot",
+            Queue::SummonersRiftCoOpVsAiIntermediateBot => "Co-op vs. AI Intermediate Bot",
             Queue::SummonersRiftArurf => "ARURF",
-            Queue::SummonersRiftOneForAll => "One for All",
-            Queue::Arena2v2v2v2Cherry => "2v2v2v2 Cherry",
-            Queue::SummonersRiftPickUrf => "Pick URF",
-            Queue::SummonersRiftUltimateSpellbook => "Ultimate Spellbook",
+            Queue::CrystalScarAscension => "Ascension",
+            Queue::HowlingAbyssLegendOfThePoroKing => "Legend of the Poro King",
             Queue::SummonersRiftNexusSiege => "Nexus Siege",
-            Queue::SummonersRiftClash => "Clash",
-            Queue::SummonersRiftNormalQuickplay => "Normal Quickplay",
+            Queue::SummonersRiftDoomBotsVoting => "Doom Bots Voting",
+            Queue::SummonersRiftDoomBotsStandard => "Doom Bots Standard",
+            Queue::ValoranCityParkStarGuardianInvasionNormal => "Star Guardian Invasion Normal",
+            Queue::ValoranCityParkStarGuardianInvasionOnslaught => {
+                "Star Guardian Invasion Onslaught"
+            }
+            Queue::OverchargeProjectHunters => "Project Hunters",
+            Queue::SummonersRiftSnowArurf => "Snow ARURF",
+            Queue::SummonersRiftOneForAll => "One for All",
+            Queue::CrashSiteOdysseyExtractionIntro => "Odyssey Extraction Intro",
+            Queue::CrashSiteOdysseyExtractionCadet => "Odyssey Extraction Cadet",
+            Queue::CrashSiteOdysseyExtractionCrewmember => "Odyssey Extraction Crewmember",
+            Queue::CrashSiteOdysseyExtractionCaptain => "Odyssey Extraction Captain",
+            Queue::CrashSiteOdysseyExtractionOnslaught => "Odyssey Extraction Onslaught",
+            Queue::ConvergenceTeamfightTactics => "Teamfight Tactics",
+            Queue::ConvergenceTeamfightTactics1v0 => "Teamfight Tactics 1v0",
+            Queue::ConvergenceTeamfightTactics2v0 => "Teamfight Tactics 2v0",
+            Queue::ConvergenceRankedTeamfightTactics => "Ranked Teamfight Tactics",
+            Queue::ConvergenceTeamfightTacticsTutorial => "Teamfight Tactics Tutorial",
+            Queue::ConvergenceTeamfightTacticsSimulation => "Teamfight Tactics Simulation",
+            Queue::ConvergenceRankedTeamfightTacticsHyperRoll => {
+                "Ranked Teamfight Tactics Hyper Roll"
+            }
+            Queue::ConvergenceRankedTeamfightTacticsDoubleUpWorkshopDeprecated1150 => {
+                "Ranked Teamfight Tactics Double Up Workshop"
+            }
+            Queue::ConvergenceRankedTeamfightTacticsDoubleUpWorkshop => {
+                "Ranked Teamfight Tactics Double Up Workshop"
+            }
+            Queue::NexusBlitzDeprecated1200 => "Nexus Blitz",
+            Queue::ConvergenceTeamfightTacticsChonccsTreasure => {
+                "Teamfight Tactics Choncc's Treasure"
+            }
             Queue::NexusBlitz => "Nexus Blitz",
-            _ => "Unknown",
+            Queue::SummonersRiftUltimateSpellbook => "Ultimate Spellbook",
+            Queue::Arena2v2v2v2Cherry => "Arena",
+            Queue::RingsOfWrathArenaCherryGames => "Arena",
+            Queue::SwarmSoloStrawberryGames => "Swarm",
+            Queue::SwarmDuoStrawberryGames => "Swarm",
+            Queue::SwarmTrioStrawberryGames => "Swarm",
+            Queue::SwarmQuadStrawberryGames => "Swarm",
+            Queue::SummonersRiftPickUrf => "Pick URF",
+            Queue::SummonersRiftTutorial1 => "Tutorial 1",
+            Queue::SummonersRiftTutorial2 => "Tutorial 2",
+            Queue::SummonersRiftTutorial3 => "Tutorial 3",
+            Queue::ConvergenceTeamfightTacticsSet35Revival => "Teamfight Tactics Set 3.5 Revival",
         }
     }
 
+    /// Arena (Cherry): 2 players subteams ranked by placement.
+    pub const fn is_arena(&self) -> bool {
+        matches!(
+            self,
+            Queue::Arena2v2v2v2Cherry | Queue::RingsOfWrathArenaCherryGames
+        )
+    }
+
+    /// Swarm (Strawberry): PvE, all players in the same team.
+    pub const fn is_swarm(&self) -> bool {
+        matches!(
+            self,
+            Queue::SwarmSoloStrawberryGames
+                | Queue::SwarmDuoStrawberryGames
+                | Queue::SwarmTrioStrawberryGames
+                | Queue::SwarmQuadStrawberryGames
+        )
+    }
+
     pub fn to_u16(&self) -> u16 {
         match self {
             Queue::Custom => 0,
@@ -1,3 +1,6 @@
+// Generated by asset-generation from Data Dragon and CommunityDragon, do not edit by hand.
+// Run `cargo run --bin asset-generation -- --consts` to update it.
+
 use std::fmt::Formatter;
 
 #[repr(u16)]
@@ -27,10 +30,36 @@ pub enum SummonerSpell {
     Summoner_UltBookSmitePlaceholder = 55,
 }
 
+impl SummonerSpell {
+    /// Name shown in game.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            SummonerSpell::SummonerBarrier => "Barrier",
+            SummonerSpell::SummonerBoost => "Cleanse",
+            SummonerSpell::SummonerCherryFlash => "Flash",
+            SummonerSpell::SummonerCherryHold => "Flee",
+            SummonerSpell::SummonerDot => "Ignite",
+            SummonerSpell::SummonerExhaust => "Exhaust",
+            SummonerSpell::SummonerFlash => "Flash",
+            SummonerSpell::SummonerHaste => "Ghost",
+            SummonerSpell::SummonerHeal => "Heal",
+            SummonerSpell::SummonerMana => "Clarity",
+            SummonerSpell::SummonerPoroRecall => "To the King!",
+            SummonerSpell::SummonerPoroThrow => "Poro Toss",
+            SummonerSpell::SummonerSmite => "Smite",
+            SummonerSpell::SummonerSnowURFSnowball_Mark => "Mark",
+            SummonerSpell::SummonerSnowball => "Mark",
+            SummonerSpell::SummonerTeleport => "Teleport",
+            SummonerSpell::Summoner_UltBookPlaceholder => "Placeholder",
+            SummonerSpell::Summoner_UltBookSmitePlaceholder => "Placeholder and Attack-Smite",
+            SummonerSpell::UNKNOWN => "Unknown",
+        }
+    }
+}
+
 impl From<u16> for SummonerSpell {
     fn from(value: u16) -> Self {
         match value {
-            0 => SummonerSpell::UNKNOWN,
             21 => SummonerSpell::SummonerBarrier,
             1 => SummonerSpell::SummonerBoost,
             2202 => SummonerSpell::SummonerCherryFlash,
@@ -93,9 +122,9 @@ impl std::fmt::Display for SummonerSpell {
             SummonerSpell::SummonerPoroThrow => "SummonerPoroThrow",
             SummonerSpell::SummonerSmite => "SummonerSmite",
             SummonerSpell::SummonerSnowURFSnowball_Mark => "SummonerSnowURFSnowball_Mark",
-            SummonerSpell::SummonerSnowball => "SummonerSnowURFSnowball_Mark",
-            SummonerSpell::SummonerTeleport => "SummonerSnowball",
-            SummonerSpell::Summoner_UltBookPlaceholder => "SummonerTeleport",
+            SummonerSpell::SummonerSnowball => "SummonerSnowball",
+            SummonerSpell::SummonerTeleport => "SummonerTeleport",
+            SummonerSpell::Summoner_UltBookPlaceholder => "Summoner_UltBookPlaceholder",
             SummonerSpell::Summoner_UltBookSmitePlaceholder => "Summoner_UltBookSmitePlaceholder",
         };
         write!(f, "{}", str)
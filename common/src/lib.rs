@@ -1,3 +1,4 @@
+pub mod asset_metadata;
 pub mod consts;
 
 pub enum AssetType{
@@ -5,7 +6,8 @@ pub enum AssetType{
     ProfileIcon,
     SummonerSpell,
     Perk,
-    Champion
+    Champion,
+    Augment
 }
 
 impl AssetType {
@@ -21,7 +23,8 @@ impl AssetType {
             AssetType::ProfileIcon => "profile_icons",
             AssetType::SummonerSpell => "summoner_spells",
             AssetType::Perk => "perks",
-            AssetType::Champion => "champions"
+            AssetType::Champion => "champions",
+            AssetType::Augment => "augments"
         }
     }
 
@@ -31,7 +34,8 @@ impl AssetType {
             AssetType::ProfileIcon => "pi",
             AssetType::SummonerSpell => "ss",
             AssetType::Perk => "pk",
-            AssetType::Champion => "cn"
+            AssetType::Champion => "cn",
+            AssetType::Augment => "ag"
         }
     }
 
@@ -42,7 +46,8 @@ impl AssetType {
             AssetType::ProfileIcon => (64,64),
             AssetType::SummonerSpell => (22,22),
             AssetType::Perk => (28,28),
-            AssetType::Champion => (48,48)
+            AssetType::Champion => (48,48),
+            AssetType::Augment => (22,22)
         }
     }
 }
\ No newline at end of file
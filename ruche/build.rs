@@ -0,0 +1,16 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Written by asset-generation, served from `/assets` with a one year cache.
+const ASSET_METADATA_FILE: &str = "public/assets/metadata.bin";
+
+/// Exposes the content hash of the asset metadata, appended to its url so a regenerated file is
+/// fetched again.
+fn main() {
+    println!("cargo:rerun-if-changed={}", ASSET_METADATA_FILE);
+    let mut hasher = DefaultHasher::new();
+    std::fs::read(ASSET_METADATA_FILE)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    println!("cargo:rustc-env=ASSET_METADATA_HASH={:x}", hasher.finish());
+}
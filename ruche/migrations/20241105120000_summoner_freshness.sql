@@ -0,0 +1,30 @@
+-- Add migration script here
+ALTER TABLE summoners
+    ADD COLUMN profile_updated_at TIMESTAMP DEFAULT NULL,
+    ADD COLUMN matches_updated_at TIMESTAMP DEFAULT NULL,
+    ADD COLUMN ranks_updated_at   TIMESTAMP DEFAULT NULL,
+    ADD COLUMN mastery_updated_at TIMESTAMP DEFAULT NULL;
+
+-- league entries of the summoner, replaced as a whole when the ranks are refreshed.
+CREATE TABLE IF NOT EXISTS summoner_ranks
+(
+    summoner_id   INTEGER     NOT NULL REFERENCES summoners (id) ON DELETE CASCADE,
+    queue_type    VARCHAR(32) NOT NULL,
+    tier          VARCHAR(16),
+    division      VARCHAR(4),
+    league_points INTEGER     NOT NULL,
+    wins          INTEGER     NOT NULL,
+    losses        INTEGER     NOT NULL,
+    PRIMARY KEY (summoner_id, queue_type)
+);
+
+-- champion masteries of the summoner, replaced as a whole when the mastery is refreshed.
+CREATE TABLE IF NOT EXISTS summoner_champion_masteries
+(
+    summoner_id     INTEGER   NOT NULL REFERENCES summoners (id) ON DELETE CASCADE,
+    champion_id     INTEGER   NOT NULL,
+    champion_level  INTEGER   NOT NULL,
+    champion_points INTEGER   NOT NULL,
+    last_play_time  TIMESTAMP NOT NULL,
+    PRIMARY KEY (summoner_id, champion_id)
+);
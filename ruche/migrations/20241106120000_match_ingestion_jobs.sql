@@ -0,0 +1,23 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS match_ingestion_jobs
+(
+    id              SERIAL PRIMARY KEY,
+    lol_match_id    INTEGER   NOT NULL UNIQUE REFERENCES lol_matches (id) ON DELETE CASCADE,
+    priority        SMALLINT  NOT NULL DEFAULT 0,
+    attempts        INTEGER   NOT NULL DEFAULT 0,
+    last_error      TEXT               DEFAULT NULL,
+    next_attempt_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    failed          BOOLEAN   NOT NULL DEFAULT FALSE,
+    created_at      TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_match_ingestion_jobs_due
+    ON match_ingestion_jobs (priority DESC, next_attempt_at)
+    WHERE failed = false;
+
+-- matches waiting for the old polling of lol_matches.updated
+INSERT INTO match_ingestion_jobs (lol_match_id)
+SELECT id
+FROM lol_matches
+WHERE updated = false
+ON CONFLICT (lol_match_id) DO NOTHING;
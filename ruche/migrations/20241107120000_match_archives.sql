@@ -0,0 +1,8 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS lol_match_archives
+(
+    lol_match_id  INTEGER PRIMARY KEY REFERENCES lol_matches (id) ON DELETE CASCADE,
+    match_json    BYTEA,
+    timeline_json BYTEA,
+    archived_at   TIMESTAMP NOT NULL DEFAULT NOW()
+);
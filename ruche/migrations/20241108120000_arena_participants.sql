@@ -0,0 +1,35 @@
+-- Add migration script here
+-- Arena (CHERRY): 2 players subteams ranked by placement, with up to 6 augments.
+ALTER TABLE lol_match_participants
+    ADD COLUMN IF NOT EXISTS player_subteam_id INTEGER,
+    ADD COLUMN IF NOT EXISTS subteam_placement INTEGER,
+    ADD COLUMN IF NOT EXISTS augment1_id       INTEGER,
+    ADD COLUMN IF NOT EXISTS augment2_id       INTEGER,
+    ADD COLUMN IF NOT EXISTS augment3_id       INTEGER,
+    ADD COLUMN IF NOT EXISTS augment4_id       INTEGER,
+    ADD COLUMN IF NOT EXISTS augment5_id       INTEGER,
+    ADD COLUMN IF NOT EXISTS augment6_id       INTEGER;
+
+-- Swarm (STRAWBERRY) matches used to be trashed without keeping their queue or mode, requeue in
+-- background the trashed rows with no queue, the ingestion trashes the invalid ones, such as riot 404s, again.
+UPDATE lol_matches
+SET updated = false,
+    trashed = false
+WHERE trashed = true
+  AND queue_id IS NULL;
+
+INSERT INTO match_ingestion_jobs (lol_match_id, priority)
+SELECT id, 0
+FROM lol_matches
+WHERE updated = false
+ON CONFLICT (lol_match_id) DO NOTHING;
+
+-- Arena (CHERRY) matches already stored have 5v5 teams and no subteams, refetch them in background,
+-- the ingestion replaces the participants of a match it already stored.
+INSERT INTO match_ingestion_jobs (lol_match_id, priority)
+SELECT id, 0
+FROM lol_matches
+WHERE updated = true
+  AND trashed = false
+  AND (queue_id IN (1700, 1710) OR game_mode = 'CHERRY')
+ON CONFLICT (lol_match_id) DO NOTHING;
@@ -0,0 +1,41 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS lol_match_bans
+(
+    lol_match_id INTEGER NOT NULL REFERENCES lol_matches (id) ON DELETE CASCADE,
+    team_id      INTEGER NOT NULL,
+    pick_turn    INTEGER NOT NULL,
+    champion_id  INTEGER NOT NULL,
+    PRIMARY KEY (lol_match_id, team_id, pick_turn)
+);
+
+-- site-wide aggregates rebuilt by UpdateChampionStatsTask, one row per (platform, queue, patch, champion).
+-- sums are stored instead of averages so several platforms or patches can be merged at read time.
+CREATE TABLE IF NOT EXISTS champion_stats
+(
+    platform      platform_type NOT NULL,
+    queue_id      INTEGER       NOT NULL,
+    version       VARCHAR(5)    NOT NULL,
+    champion_id   INTEGER       NOT NULL,
+    total_picks   INTEGER       NOT NULL,
+    total_wins    INTEGER       NOT NULL,
+    total_bans    INTEGER       NOT NULL,
+    total_kills   BIGINT        NOT NULL,
+    total_deaths  BIGINT        NOT NULL,
+    total_assists BIGINT        NOT NULL,
+    PRIMARY KEY (platform, queue_id, version, champion_id)
+);
+
+-- number of matches per (platform, queue, patch), denominator of pick rates.
+-- ban rates only count the matches with stored bans, ones ingested before lol_match_bans have none.
+CREATE TABLE IF NOT EXISTS champion_stats_matches
+(
+    platform          platform_type NOT NULL,
+    queue_id          INTEGER       NOT NULL,
+    version           VARCHAR(5)    NOT NULL,
+    total_matches     INTEGER       NOT NULL,
+    total_ban_matches INTEGER       NOT NULL DEFAULT 0,
+    updated_at        TIMESTAMP     NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (platform, queue_id, version)
+);
+
+CREATE INDEX IF NOT EXISTS idx_lol_matches_version ON lol_matches (version);
@@ -0,0 +1,25 @@
+-- Add migration script here
+-- ranked season and split boundaries used by the season filter, as the first and last patch of each split.
+-- the current split has no end patch, a new split closes the previous one.
+CREATE TABLE IF NOT EXISTS seasons
+(
+    id            SERIAL PRIMARY KEY,
+    name          VARCHAR(32) NOT NULL,
+    start_version VARCHAR(5)  NOT NULL,
+    end_version   VARCHAR(5) DEFAULT NULL
+);
+
+INSERT INTO seasons (name, start_version, end_version)
+VALUES ('2023 Split 1', '13.1', '13.13'),
+       ('2023 Split 2', '13.14', '13.24'),
+       ('2024 Split 1', '14.1', '14.9'),
+       ('2024 Split 2', '14.10', '14.18'),
+       ('2024 Split 3', '14.19', '14.24'),
+       ('2025 Split 1', '15.1', '15.8'),
+       ('2025 Split 2', '15.9', '15.16'),
+       ('2025 Split 3', '15.17', '15.24'),
+       ('2026 Split 1', '16.1', NULL);
+
+-- patches are compared as INT[] ("14.9" < "14.10"), index the same expression as the filters
+CREATE INDEX IF NOT EXISTS idx_lol_matches_version_parts
+    ON lol_matches ((string_to_array(version, '.')::INT[]));
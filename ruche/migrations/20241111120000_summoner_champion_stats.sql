@@ -0,0 +1,67 @@
+-- Add migration script here
+-- per summoner aggregates of the champions and live pages, one row per (summoner, champion, queue, patch).
+-- incremented by bulk_insert_lol_match_participants, SummonerChampionStatsRepairTask rebuilds drifted summoners.
+CREATE TABLE IF NOT EXISTS summoner_champion_stats
+(
+    summoner_id                     INTEGER    NOT NULL REFERENCES summoners (id) ON DELETE CASCADE,
+    champion_id                     INTEGER    NOT NULL,
+    queue_id                        INTEGER    NOT NULL,
+    version                         VARCHAR(5) NOT NULL,
+    total_matches                   INTEGER    NOT NULL,
+    total_wins                      INTEGER    NOT NULL,
+    total_kills                     BIGINT     NOT NULL,
+    total_deaths                    BIGINT     NOT NULL,
+    total_assists                   BIGINT     NOT NULL,
+    total_kda                       FLOAT8     NOT NULL,
+    total_kill_participation        FLOAT8     NOT NULL,
+    total_gold_earned               BIGINT     NOT NULL,
+    total_cs                        BIGINT     NOT NULL,
+    total_damage_dealt_to_champions BIGINT     NOT NULL,
+    total_damage_taken              BIGINT     NOT NULL,
+    total_double_kills              INTEGER    NOT NULL,
+    total_triple_kills              INTEGER    NOT NULL,
+    total_quadra_kills              INTEGER    NOT NULL,
+    total_penta_kills               INTEGER    NOT NULL,
+    -- arena games count by placement, index 1 is the 1st place
+    arena_placements                INTEGER[]  NOT NULL DEFAULT '{0,0,0,0,0,0,0,0}',
+    PRIMARY KEY (summoner_id, champion_id, queue_id, version)
+);
+
+INSERT INTO summoner_champion_stats (summoner_id, champion_id, queue_id, version, total_matches, total_wins,
+                                     total_kills, total_deaths, total_assists, total_kda,
+                                     total_kill_participation, total_gold_earned, total_cs,
+                                     total_damage_dealt_to_champions, total_damage_taken, total_double_kills,
+                                     total_triple_kills, total_quadra_kills, total_penta_kills, arena_placements)
+SELECT lmp.summoner_id,
+       lmp.champion_id,
+       lm.queue_id,
+       lm.version,
+       count(*),
+       count(*) FILTER (WHERE lmp.won),
+       sum(lmp.kills),
+       sum(lmp.deaths),
+       sum(lmp.assists),
+       sum(lmp.kda),
+       sum(lmp.kill_participation),
+       sum(lmp.gold_earned),
+       sum(lmp.cs),
+       sum(lmp.damage_dealt_to_champions),
+       sum(lmp.damage_taken),
+       sum(lmp.double_kills),
+       sum(lmp.triple_kills),
+       sum(lmp.quadra_kills),
+       sum(lmp.penta_kills),
+       ARRAY [count(*) FILTER (WHERE lmp.subteam_placement = 1),
+           count(*) FILTER (WHERE lmp.subteam_placement = 2),
+           count(*) FILTER (WHERE lmp.subteam_placement = 3),
+           count(*) FILTER (WHERE lmp.subteam_placement = 4),
+           count(*) FILTER (WHERE lmp.subteam_placement = 5),
+           count(*) FILTER (WHERE lmp.subteam_placement = 6),
+           count(*) FILTER (WHERE lmp.subteam_placement = 7),
+           count(*) FILTER (WHERE lmp.subteam_placement >= 8)]::INTEGER[]
+FROM lol_match_participants as lmp
+         INNER JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+WHERE lm.queue_id IS NOT NULL
+  AND lm.version IS NOT NULL
+GROUP BY lmp.summoner_id, lmp.champion_id, lm.queue_id, lm.version
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,5 @@
+-- Add migration script here
+-- games queued when a live game ends: riot publishes them some minutes later, so a missing match is
+-- retried with the backoff instead of being trashed.
+ALTER TABLE match_ingestion_jobs
+    ADD COLUMN IF NOT EXISTS wait_for_publication BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,5 @@
+-- Add migration script here
+-- position played in the game (TOP, JUNGLE, MIDDLE, BOTTOM, UTILITY), NULL for modes without roles and
+-- for the matches ingested before this column.
+ALTER TABLE lol_match_participants
+    ADD COLUMN IF NOT EXISTS team_position VARCHAR(7);
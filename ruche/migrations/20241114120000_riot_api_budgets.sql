@@ -0,0 +1,13 @@
+-- Add migration script here
+-- riot api requests spent by the instances in the current period, so a budget holds cluster-wide.
+CREATE TABLE IF NOT EXISTS riot_api_budgets
+(
+    name         VARCHAR(32) PRIMARY KEY,
+    period_start TIMESTAMP   NOT NULL DEFAULT NOW(),
+    spent        INTEGER     NOT NULL DEFAULT 0
+);
+
+-- summoner refresh and live scouts, spent from `REFRESH_SUMMONERS_API_BUDGET`
+INSERT INTO riot_api_budgets (name)
+VALUES ('refresh_summoners')
+ON CONFLICT DO NOTHING;
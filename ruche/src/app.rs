@@ -1,3 +1,6 @@
+use crate::views::champion_tier_list_page::ChampionTierListPage;
+use crate::views::components::asset_tooltip::{AssetTooltip, AssetTooltipContext};
+use crate::views::multisearch_page::MultiSearchPage;
 use crate::views::platform_type_page::PlatformTypePage;
 use crate::views::summoner_page::SummonerPage;
 use bitcode::{Decode, Encode};
@@ -44,6 +47,7 @@ pub fn shell(options: LeptosOptions) -> impl IntoView {
 pub fn App() -> impl IntoView {
     let meta_store = reactive_stores::Store::new(MetaStore::default());
     provide_context(meta_store);
+    provide_context(AssetTooltipContext::default());
 
     // Provides context that manages stylesheets, titles, meta tags, etc.
     provide_meta_context();
@@ -82,6 +86,8 @@ pub fn App() -> impl IntoView {
                         view=PlatformTypePage
                     >
                         <Route path=StaticSegment("") view=move || view! {} />
+                        <Route path=StaticSegment("multisearch") view=MultiSearchPage />
+                        <Route path=StaticSegment("champions") view=ChampionTierListPage />
                         <Route
                             path=(StaticSegment("summoners"), ParamSegment("summoner_slug"))
                             view=SummonerPage
@@ -90,5 +96,6 @@ pub fn App() -> impl IntoView {
                 </Routes>
             </main>
         </Router>
+        <AssetTooltip />
     }
 }
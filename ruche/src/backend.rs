@@ -5,9 +5,19 @@ pub mod server_fns;
 #[cfg(feature = "ssr")]
 pub mod tasks;
 
+#[cfg(feature = "ssr")]
+pub mod cli;
+#[cfg(feature = "ssr")]
+pub mod health;
 #[cfg(feature = "ssr")]
 pub mod live_game_cache;
 #[cfg(feature = "ssr")]
+pub mod matches_stats_cache;
+#[cfg(feature = "ssr")]
+pub mod server_fn_cache;
+#[cfg(feature = "ssr")]
+pub mod sse_events;
+#[cfg(feature = "ssr")]
 pub mod task_director;
 
 pub type ServerResult<T> = Result<T, ServerFnError>;
@@ -0,0 +1,88 @@
+use crate::backend::ssr::AppResult;
+use crate::backend::tasks::update_matches::match_archive::{
+    reprocess_archived_matches, requeue_unarchived_matches,
+};
+use crate::backend::tasks::update_matches::match_ingestion_queue::{
+    find_failed_match_jobs, requeue_failed_match_jobs,
+};
+use clap::{Parser, Subcommand};
+use sqlx::PgPool;
+
+/// Without a command the web server is started.
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// inspect or requeue the match ingestion jobs
+    MatchJobs {
+        #[command(subcommand)]
+        command: MatchJobsCommand,
+    },
+    /// rebuild the match participants from the archived raw matches, without riot api calls
+    ReprocessMatches {
+        #[arg(long, default_value_t = 200)]
+        batch_size: i64,
+        /// only the Summoner's Rift matches with participants stored before `team_position`
+        #[arg(long)]
+        missing_team_position: bool,
+        /// also queue the matches without archive for a refetch from the riot api
+        #[arg(long)]
+        refetch_unarchived: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum MatchJobsCommand {
+    /// list the jobs that reached the max attempts
+    Failed {
+        #[arg(long, default_value_t = 50)]
+        limit: i64,
+    },
+    /// requeue failed jobs by riot match id, every failed job if none given
+    Requeue { match_ids: Vec<String> },
+}
+
+pub async fn run_command(db: &PgPool, command: Command) -> AppResult<()> {
+    match command {
+        Command::MatchJobs { command } => match command {
+            MatchJobsCommand::Failed { limit } => {
+                let jobs = find_failed_match_jobs(db, limit).await?;
+                println!("{} failed match jobs", jobs.len());
+                for job in jobs {
+                    println!(
+                        "{} priority={} attempts={} created_at={} next_attempt_at={} error={}",
+                        job.match_id,
+                        job.priority,
+                        job.attempts,
+                        job.created_at,
+                        job.next_attempt_at,
+                        job.last_error.unwrap_or_default()
+                    );
+                }
+            }
+            MatchJobsCommand::Requeue { match_ids } => {
+                let requeued = requeue_failed_match_jobs(db, &match_ids).await?;
+                println!("Requeued {} match jobs", requeued);
+            }
+        },
+        Command::ReprocessMatches {
+            batch_size,
+            missing_team_position,
+            refetch_unarchived,
+        } => {
+            let reprocessed =
+                reprocess_archived_matches(db, batch_size, missing_team_position).await?;
+            println!("Reprocessed {} matches", reprocessed);
+            if refetch_unarchived {
+                let requeued = requeue_unarchived_matches(db, missing_team_position).await?;
+                println!("Queued {} matches without archive for a refetch", requeued);
+            }
+        }
+    }
+    Ok(())
+}
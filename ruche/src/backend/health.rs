@@ -0,0 +1,235 @@
+use crate::ssr::AppState;
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::Json;
+use dashmap::DashMap;
+use http::StatusCode;
+use itertools::Itertools;
+use serde_json::{json, Map, Value};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Extra time given to a task after its expected window before the instance is not ready.
+const TASK_GRACE: Duration = Duration::from_secs(60);
+const DATABASE_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct TaskHealth {
+    pub registered_at: Instant,
+    pub last_started: Option<Instant>,
+    pub last_finished: Option<Instant>,
+    /// Time between two scheduled runs.
+    pub expected_interval: Duration,
+    /// Cluster singleton task run by another instance.
+    pub standby: bool,
+}
+
+impl TaskHealth {
+    fn is_running(&self) -> bool {
+        self.last_started
+            .is_some_and(|started| self.last_finished.is_none_or(|finished| started > finished))
+    }
+
+    /// The task must have started within two intervals plus `TASK_GRACE`, a long run is not late.
+    fn is_late(&self) -> bool {
+        let reference = self.last_started.unwrap_or(self.registered_at);
+        !self.standby
+            && !self.is_running()
+            && reference.elapsed() > self.expected_interval * 2 + TASK_GRACE
+    }
+}
+
+/// Liveness data of the instance read by `/readyz`: task runs and riot api calls.
+pub struct HealthState {
+    pub started_at: Instant,
+    /// Riot api calls older than this are ignored.
+    pub riot_api_window: Duration,
+    pub tasks: DashMap<&'static str, TaskHealth>,
+    riot_api_last_success: Mutex<Option<Instant>>,
+    riot_api_last_failure: Mutex<Option<Instant>>,
+}
+
+impl HealthState {
+    pub fn new(riot_api_window: Duration) -> Self {
+        HealthState {
+            started_at: Instant::now(),
+            riot_api_window,
+            tasks: DashMap::new(),
+            riot_api_last_success: Mutex::new(None),
+            riot_api_last_failure: Mutex::new(None),
+        }
+    }
+
+    pub fn register_task(&self, name: &'static str, expected_interval: Duration) {
+        self.tasks.insert(
+            name,
+            TaskHealth {
+                registered_at: Instant::now(),
+                last_started: None,
+                last_finished: None,
+                expected_interval,
+                standby: false,
+            },
+        );
+    }
+
+    pub fn task_scheduled(&self, name: &'static str, expected_interval: Duration, standby: bool) {
+        if let Some(mut task) = self.tasks.get_mut(name) {
+            task.expected_interval = expected_interval;
+            task.standby = standby;
+        }
+    }
+
+    pub fn task_started(&self, name: &'static str) {
+        if let Some(mut task) = self.tasks.get_mut(name) {
+            task.last_started = Some(Instant::now());
+        }
+    }
+
+    pub fn task_finished(&self, name: &'static str) {
+        if let Some(mut task) = self.tasks.get_mut(name) {
+            task.last_finished = Some(Instant::now());
+        }
+    }
+
+    pub fn record_riot_api_call(&self, success: bool) {
+        let last_call = if success {
+            &self.riot_api_last_success
+        } else {
+            &self.riot_api_last_failure
+        };
+        *last_call.lock().unwrap() = Some(Instant::now());
+    }
+
+    /// Not ready only when every riot api call of the window failed, an idle instance is ready.
+    fn riot_api_check(&self) -> (bool, Value) {
+        let in_window = |instant: Option<Instant>| {
+            instant.filter(|instant| instant.elapsed() <= self.riot_api_window)
+        };
+        let last_success = *self.riot_api_last_success.lock().unwrap();
+        let last_failure = *self.riot_api_last_failure.lock().unwrap();
+        let (ok, status) = match (in_window(last_success), in_window(last_failure)) {
+            (Some(_), _) => (true, "ok"),
+            (None, Some(_)) => (false, "failing"),
+            (None, None) => (true, "idle"),
+        };
+        (
+            ok,
+            json!({
+                "ok": ok,
+                "status": status,
+                "last_success_secs_ago": last_success.map(|instant| instant.elapsed().as_secs()),
+                "last_failure_secs_ago": last_failure.map(|instant| instant.elapsed().as_secs()),
+            }),
+        )
+    }
+
+    fn tasks_check(&self) -> (bool, Value) {
+        let mut ok = true;
+        let mut tasks = Map::new();
+        for entry in self.tasks.iter().sorted_by_key(|entry| *entry.key()) {
+            let task = entry.value();
+            let task_ok = !task.is_late();
+            ok &= task_ok;
+            tasks.insert(
+                entry.key().to_string(),
+                json!({
+                    "ok": task_ok,
+                    "standby": task.standby,
+                    "running": task.is_running(),
+                    "expected_interval_secs": task.expected_interval.as_secs(),
+                    "last_started_secs_ago": task.last_started.map(|instant| instant.elapsed().as_secs()),
+                    "last_finished_secs_ago": task.last_finished.map(|instant| instant.elapsed().as_secs()),
+                }),
+            );
+        }
+        (ok, Value::Object(tasks))
+    }
+}
+
+/// Liveness, the process answers.
+pub async fn healthz(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        Json(json!({
+            "status": "ok",
+            "uptime_secs": state.health.started_at.elapsed().as_secs(),
+        })),
+    )
+}
+
+/// Readiness: database, applied migrations, riot api and background tasks.
+pub async fn readyz(State(state): State<AppState>) -> impl IntoResponse {
+    let (database_ok, database) = database_check(&state).await;
+    let (migrations_ok, migrations) = if database_ok {
+        migrations_check(&state).await
+    } else {
+        (
+            false,
+            json!({ "ok": false, "error": "database unavailable" }),
+        )
+    };
+    let (riot_api_ok, riot_api) = state.health.riot_api_check();
+    let (tasks_ok, tasks) = state.health.tasks_check();
+
+    let ready = database_ok && migrations_ok && riot_api_ok && tasks_ok;
+    (
+        if ready {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        },
+        Json(json!({
+            "status": if ready { "ok" } else { "unavailable" },
+            "checks": {
+                "database": database,
+                "migrations": migrations,
+                "riot_api": riot_api,
+                "tasks": tasks,
+            },
+        })),
+    )
+}
+
+async fn database_check(state: &AppState) -> (bool, Value) {
+    let start = Instant::now();
+    match tokio::time::timeout(
+        DATABASE_CHECK_TIMEOUT,
+        sqlx::query("SELECT 1").execute(&state.db),
+    )
+    .await
+    {
+        Ok(Ok(_)) => (
+            true,
+            json!({
+                "ok": true,
+                "latency_ms": start.elapsed().as_millis() as u64,
+                "pool_size": state.db.size(),
+                "idle_connections": state.db.num_idle(),
+            }),
+        ),
+        Ok(Err(e)) => (false, json!({ "ok": false, "error": e.to_string() })),
+        Err(_) => (false, json!({ "ok": false, "error": "timeout" })),
+    }
+}
+
+/// Every migration embedded in the binary must be applied.
+async fn migrations_check(state: &AppState) -> (bool, Value) {
+    let applied =
+        match sqlx::query_scalar::<_, i64>("SELECT version FROM _sqlx_migrations WHERE success")
+            .fetch_all(&state.db)
+            .await
+        {
+            Ok(applied) => applied,
+            Err(e) => return (false, json!({ "ok": false, "error": e.to_string() })),
+        };
+    let pending = sqlx::migrate!()
+        .iter()
+        .filter(|migration| !applied.contains(&migration.version))
+        .map(|migration| migration.version)
+        .collect_vec();
+    (
+        pending.is_empty(),
+        json!({ "ok": pending.is_empty(), "pending": pending }),
+    )
+}
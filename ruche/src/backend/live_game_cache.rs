@@ -1,72 +1,371 @@
-use crate::utils::RiotMatchId;
+use crate::utils::{LiveScoutProgress, RiotMatchId};
 use crate::views::summoner_page::summoner_live_page::LiveGame;
 use dashmap::DashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use tokio::time::Instant;
 
+pub struct LiveGameCacheEntry {
+    pub game: LiveGame,
+    pub summoner_ids: Vec<i32>,
+    /// When the game was fetched, `game_length` is counted from it.
+    pub fetched_at: Instant,
+    /// When the game was last confirmed by the riot api, used for the expiration.
+    pub refreshed_at: Instant,
+    /// Delay until the next planned spectator lookup of the game, added to the expiration.
+    pub poll_delay: Duration,
+    /// Used for the LRU eviction.
+    pub last_access: Instant,
+    /// Scout version the participant stats were last recomputed for.
+    pub stats_version: u16,
+    /// Only set on the instance the scout was requested from.
+    pub scout: Option<LiveScout>,
+}
+
+/// History fetch of every participant requested from the live page. The `ScoutLiveGamesTask`
+/// queues the `waiting` ones, they stay `pending` until every queued match job is over.
+pub struct LiveScout {
+    pub version: u16,
+    pub total: u16,
+    pub waiting: HashSet<i32>,
+    /// Riot match ids queued for each pending participant.
+    pub pending: HashMap<i32, Vec<String>>,
+}
+
+impl LiveScout {
+    fn progress(&self) -> LiveScoutProgress {
+        LiveScoutProgress {
+            version: self.version,
+            done: self.total - (self.waiting.len() + self.pending.len()) as u16,
+            total: self.total,
+        }
+    }
+}
+
+/// Result of `LiveGameCache::claim_stats_refresh`.
+pub enum StatsRefreshClaim {
+    NotCached,
+    AlreadyClaimed,
+    Claimed {
+        game_id: RiotMatchId,
+        summoner_ids: Vec<i32>,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct LiveGameCacheStats {
+    pub games: usize,
+    pub summoners: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub expired: u64,
+    pub evicted: u64,
+}
+
+/// Live games by game id, expired `expiration_duration` after their next planned lookup. Past
+/// `max_games` the least recently read game is evicted.
 pub struct LiveGameCache {
-    pub game_cache: DashMap<RiotMatchId, (LiveGame, Instant)>,
+    pub game_cache: DashMap<RiotMatchId, LiveGameCacheEntry>,
     pub summoner_id_to_game: DashMap<i32, RiotMatchId>,
     pub expiration_duration: Duration,
+    pub max_games: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    expired: AtomicU64,
+    evicted: AtomicU64,
 }
 
 impl LiveGameCache {
-    pub fn new(expiration_duration: Duration) -> Self {
+    pub fn new(expiration_duration: Duration, max_games: usize) -> Self {
         LiveGameCache {
             game_cache: DashMap::new(),
             summoner_id_to_game: DashMap::new(),
             expiration_duration,
+            max_games,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            expired: AtomicU64::new(0),
+            evicted: AtomicU64::new(0),
         }
     }
 
     pub fn get_game_data(&self, summoner_id: i32) -> Option<LiveGame> {
-        let remove_summoner_id = if let Some(game_id_entry) = self.summoner_id_to_game.get(&summoner_id) {
-            let game_id = *game_id_entry.value();
-            if let Some(game_entry) = self.game_cache.get(&game_id) {
-                let (game_data, timestamp) = game_entry.value();
-                let diff = Instant::now() - *timestamp;
-                let mut data = game_data.clone();
-                data.game_length += diff.as_secs() as u16;
-                return Some(data);
-            } else {
-                true
+        let game_id = self
+            .summoner_id_to_game
+            .get(&summoner_id)
+            .map(|entry| *entry.value());
+        let Some(game_id) = game_id else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        let game = match self.game_cache.get_mut(&game_id) {
+            Some(mut entry) if !self.is_expired(&entry) => {
+                entry.last_access = Instant::now();
+                let mut data = entry.game.clone();
+                data.game_length += entry.fetched_at.elapsed().as_secs() as u16;
+                Some(data)
             }
-        }else{
-            false
+            Some(_) => {
+                self.expired.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            None => None,
         };
-        if remove_summoner_id {
-            self.summoner_id_to_game.remove(&summoner_id);
+        match game {
+            Some(game) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(game)
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                self.remove_game(game_id);
+                self.summoner_id_to_game
+                    .remove_if(&summoner_id, |_, id| *id == game_id);
+                None
+            }
         }
-        None
     }
 
     pub fn clear_game_data(&self, summoner_id: i32) -> Vec<i32> {
-        let mut summoner_ids = Vec::new();
-        let mut game_id = None;
-        if let Some(game_id_entry) = self.summoner_id_to_game.get(&summoner_id) {
-            let inner_game_id = *game_id_entry.value();
-            game_id = Some(inner_game_id);
-            if let Some(game_cache) = self.game_cache.get(&inner_game_id){
-                let (cache_info, _)  = game_cache.value();
-                for participant in cache_info.participants.iter() {
-                    summoner_ids.push(participant.summoner_id);
-                }
+        let game_id = self
+            .summoner_id_to_game
+            .get(&summoner_id)
+            .map(|entry| *entry.value());
+        let summoner_ids = game_id
+            .map(|game_id| self.remove_game(game_id))
+            .unwrap_or_default();
+        self.summoner_id_to_game.remove(&summoner_id);
+        summoner_ids
+    }
+
+    /// Replaces the game, its planned lookup and a scout running on it are kept.
+    pub fn set_game_data(&self, game_id: RiotMatchId, summoner_ids: Vec<i32>, game_data: LiveGame) {
+        if !self.game_cache.contains_key(&game_id) && self.game_cache.len() >= self.max_games {
+            self.remove_expired();
+            if self.game_cache.len() >= self.max_games {
+                self.evict_least_recently_used();
+            }
+        }
+        for summoner_id in summoner_ids.iter() {
+            self.summoner_id_to_game.insert(*summoner_id, game_id);
+        }
+        let (poll_delay, stats_version, scout) = self
+            .game_cache
+            .remove(&game_id)
+            .map(|(_, entry)| (entry.poll_delay, entry.stats_version, entry.scout))
+            .unwrap_or_default();
+        let now = Instant::now();
+        self.game_cache.insert(
+            game_id,
+            LiveGameCacheEntry {
+                game: game_data,
+                summoner_ids,
+                fetched_at: now,
+                refreshed_at: now,
+                poll_delay,
+                last_access: now,
+                stats_version,
+                scout,
+            },
+        );
+    }
+
+    /// Replaces the game after its participant stats were recomputed for the scout `version`,
+    /// without touching its times. Dropped when a newer version was claimed in the meantime.
+    pub fn set_game_stats(&self, game_id: RiotMatchId, version: u16, game_data: LiveGame) {
+        if let Some(mut entry) = self.game_cache.get_mut(&game_id) {
+            if entry.stats_version == version {
+                entry.game = game_data;
+            }
+        }
+    }
+
+    /// The game as stored, not counted as an access.
+    pub fn peek_game(&self, game_id: RiotMatchId) -> Option<LiveGame> {
+        self.game_cache
+            .get(&game_id)
+            .map(|entry| entry.game.clone())
+    }
+
+    /// Starts a scout of the game of the summoner, or returns the progress of the running one.
+    pub fn start_scout(&self, summoner_id: i32) -> Option<LiveScoutProgress> {
+        let game_id = *self.summoner_id_to_game.get(&summoner_id)?;
+        let mut entry = self.game_cache.get_mut(&game_id)?;
+        let summoner_ids = entry.summoner_ids.clone();
+        let scout = entry.scout.get_or_insert_with(|| LiveScout {
+            version: 1,
+            total: summoner_ids.len() as u16,
+            waiting: summoner_ids.into_iter().collect(),
+            pending: HashMap::new(),
+        });
+        Some(scout.progress())
+    }
+
+    /// Participants of the running scouts whose matches are not queued yet.
+    pub fn scout_waiting(&self) -> Vec<i32> {
+        self.game_cache
+            .iter()
+            .filter_map(|entry| {
+                entry
+                    .scout
+                    .as_ref()
+                    .map(|scout| scout.waiting.iter().copied().collect::<Vec<_>>())
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// The matches of the participant were queued, `queued_match_ids` are not stored yet.
+    pub fn scout_queued(
+        &self,
+        summoner_id: i32,
+        queued_match_ids: Vec<String>,
+    ) -> Option<(LiveScoutProgress, Vec<i32>)> {
+        self.update_scout(summoner_id, |scout| {
+            if !scout.waiting.remove(&summoner_id) {
+                return false;
             }
+            if !queued_match_ids.is_empty() {
+                scout.pending.insert(summoner_id, queued_match_ids);
+            }
+            true
+        })
+    }
+
+    /// Participants of the running scouts still waiting for queued matches, with their match ids.
+    pub fn scout_pending(&self) -> Vec<(i32, Vec<String>)> {
+        self.game_cache
+            .iter()
+            .filter_map(|entry| {
+                entry.scout.as_ref().map(|scout| {
+                    scout
+                        .pending
+                        .iter()
+                        .map(|(summoner_id, match_ids)| (*summoner_id, match_ids.clone()))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Match ids queued for the participant when it is pending in a scout of this instance.
+    pub fn scout_pending_match_ids(&self, summoner_id: i32) -> Option<Vec<String>> {
+        let game_id = *self.summoner_id_to_game.get(&summoner_id)?;
+        let entry = self.game_cache.get(&game_id)?;
+        entry.scout.as_ref()?.pending.get(&summoner_id).cloned()
+    }
 
+    /// All the jobs of the participant are over (ingested, trashed or failed), their stats are
+    /// refreshed at each batch.
+    pub fn scout_ingested(&self, summoner_id: i32) -> Option<(LiveScoutProgress, Vec<i32>)> {
+        self.update_scout(summoner_id, |scout| {
+            scout.pending.remove(&summoner_id).is_some()
+        })
+    }
+
+    /// Only the first event of a scout version recomputes the stats of the game on this instance.
+    pub fn claim_stats_refresh(&self, summoner_id: i32, version: u16) -> StatsRefreshClaim {
+        let Some(game_id) = self
+            .summoner_id_to_game
+            .get(&summoner_id)
+            .map(|entry| *entry.value())
+        else {
+            return StatsRefreshClaim::NotCached;
+        };
+        let Some(mut entry) = self.game_cache.get_mut(&game_id) else {
+            return StatsRefreshClaim::NotCached;
+        };
+        if entry.stats_version >= version {
+            return StatsRefreshClaim::AlreadyClaimed;
         }
-        if let Some(game_id) = game_id {
-            self.game_cache.remove(&game_id);
+        entry.stats_version = version;
+        StatsRefreshClaim::Claimed {
+            game_id,
+            summoner_ids: entry.summoner_ids.clone(),
         }
-        for summoner_id in summoner_ids.iter() {
-            self.summoner_id_to_game.remove(&summoner_id);
+    }
+
+    /// The game is still running and is looked up again in `poll_delay`, postpones its expiration.
+    pub fn refresh_game(&self, game_id: RiotMatchId, poll_delay: Duration) {
+        if let Some(mut entry) = self.game_cache.get_mut(&game_id) {
+            entry.refreshed_at = Instant::now();
+            entry.poll_delay = poll_delay;
         }
-        summoner_ids
     }
 
-    pub fn set_game_data(&self, game_id: RiotMatchId, summoner_ids: Vec<i32>, game_data: LiveGame) {
-        self.game_cache.insert(game_id, (game_data, Instant::now()));
-        for summoner_id in summoner_ids {
-            self.summoner_id_to_game.insert(summoner_id, game_id);
+    pub fn remove_expired(&self) {
+        let expired_game_ids = self
+            .game_cache
+            .iter()
+            .filter(|entry| self.is_expired(entry))
+            .map(|entry| *entry.key())
+            .collect::<Vec<_>>();
+        self.expired
+            .fetch_add(expired_game_ids.len() as u64, Ordering::Relaxed);
+        for game_id in expired_game_ids {
+            self.remove_game(game_id);
+        }
+        // mappings left by a game replaced for some of its summoners only
+        self.summoner_id_to_game
+            .retain(|_, game_id| self.game_cache.contains_key(game_id));
+    }
+
+    pub fn stats(&self) -> LiveGameCacheStats {
+        LiveGameCacheStats {
+            games: self.game_cache.len(),
+            summoners: self.summoner_id_to_game.len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            expired: self.expired.load(Ordering::Relaxed),
+            evicted: self.evicted.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Applies `update` to the scout of the game of the summoner, a new version is returned when it
+    /// changed something.
+    fn update_scout(
+        &self,
+        summoner_id: i32,
+        update: impl FnOnce(&mut LiveScout) -> bool,
+    ) -> Option<(LiveScoutProgress, Vec<i32>)> {
+        let game_id = *self.summoner_id_to_game.get(&summoner_id)?;
+        let mut entry = self.game_cache.get_mut(&game_id)?;
+        let summoner_ids = entry.summoner_ids.clone();
+        let scout = entry.scout.as_mut()?;
+        if !update(scout) {
+            return None;
+        }
+        scout.version += 1;
+        Some((scout.progress(), summoner_ids))
+    }
+
+    fn is_expired(&self, entry: &LiveGameCacheEntry) -> bool {
+        entry.refreshed_at.elapsed() >= self.expiration_duration + entry.poll_delay
+    }
+
+    fn evict_least_recently_used(&self) {
+        let oldest = self
+            .game_cache
+            .iter()
+            .min_by_key(|entry| entry.last_access)
+            .map(|entry| *entry.key());
+        if let Some(game_id) = oldest {
+            self.remove_game(game_id);
+            self.evicted.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Removes the game and the mappings of its summoners still pointing to it.
+    fn remove_game(&self, game_id: RiotMatchId) -> Vec<i32> {
+        let Some((_, entry)) = self.game_cache.remove(&game_id) else {
+            return Vec::new();
+        };
+        for summoner_id in entry.summoner_ids.iter() {
+            self.summoner_id_to_game
+                .remove_if(summoner_id, |_, id| *id == game_id);
         }
+        entry.summoner_ids
     }
 }
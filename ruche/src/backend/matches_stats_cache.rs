@@ -0,0 +1,65 @@
+use crate::views::summoner_page::summoner_matches_page::MatchesResultInfo;
+use crate::views::BackEndMatchFiltersSearch;
+use dashmap::DashMap;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Filter sets kept per summoner, the oldest one is dropped first.
+const MAX_FILTERS_PER_SUMMONER: usize = 16;
+
+/// Aggregate statistics of the matches list by summoner and filter set, so that changing page
+/// does not recompute them. Entries of a summoner are invalidated when new matches are stored.
+pub struct MatchesStatsCache {
+    pub stats: DashMap<i32, Vec<(BackEndMatchFiltersSearch, MatchesResultInfo, Instant)>>,
+    pub expiration_duration: Duration,
+}
+
+impl MatchesStatsCache {
+    pub fn new(expiration_duration: Duration) -> Self {
+        MatchesStatsCache {
+            stats: DashMap::new(),
+            expiration_duration,
+        }
+    }
+
+    pub fn get(
+        &self,
+        summoner_id: i32,
+        filters: &BackEndMatchFiltersSearch,
+    ) -> Option<MatchesResultInfo> {
+        let entries = self.stats.get(&summoner_id)?;
+        entries
+            .iter()
+            .find(|(cached_filters, _, timestamp)| {
+                cached_filters == filters && timestamp.elapsed() < self.expiration_duration
+            })
+            .map(|(_, info, _)| info.clone())
+    }
+
+    pub fn set(
+        &self,
+        summoner_id: i32,
+        filters: BackEndMatchFiltersSearch,
+        info: MatchesResultInfo,
+    ) {
+        let mut entries = self.stats.entry(summoner_id).or_default();
+        entries.retain(|(cached_filters, _, timestamp)| {
+            *cached_filters != filters && timestamp.elapsed() < self.expiration_duration
+        });
+        if entries.len() >= MAX_FILTERS_PER_SUMMONER {
+            entries.remove(0);
+        }
+        entries.push((filters, info, Instant::now()));
+    }
+
+    pub fn invalidate(&self, summoner_id: i32) {
+        self.stats.remove(&summoner_id);
+    }
+
+    pub fn remove_expired(&self) {
+        self.stats.retain(|_, entries| {
+            entries.retain(|(_, _, timestamp)| timestamp.elapsed() < self.expiration_duration);
+            !entries.is_empty()
+        });
+    }
+}
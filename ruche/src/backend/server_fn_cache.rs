@@ -0,0 +1,277 @@
+use crate::backend::ssr::AppResult;
+use bitcode::Encode;
+use dashmap::DashMap;
+use std::any::Any;
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Share of the cache dropped at once when it is full, `max_entries / EVICTION_BATCH_DIVISOR`.
+const EVICTION_BATCH_DIVISOR: usize = 10;
+
+/// Server fn name and bitcode encoded arguments.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct ServerFnCacheKey {
+    pub server_fn: &'static str,
+    pub args: Vec<u8>,
+}
+
+impl ServerFnCacheKey {
+    pub fn new<A: Encode + ?Sized>(server_fn: &'static str, args: &A) -> Self {
+        ServerFnCacheKey {
+            server_fn,
+            args: bitcode::encode(args),
+        }
+    }
+}
+
+pub struct CachedResponse {
+    pub summoner_id: i32,
+    pub value: Arc<dyn Any + Send + Sync>,
+    pub inserted_at: Instant,
+}
+
+/// Fetches of a summoner in flight and the invalidations received since the first one started.
+#[derive(Default)]
+struct SummonerFetches {
+    in_flight: usize,
+    generation: u64,
+}
+
+/// Unregisters the fetch once it completes or is dropped.
+struct InFlightFetch<'a> {
+    cache: &'a ServerFnCache,
+    summoner_id: i32,
+    generation: u64,
+}
+
+impl InFlightFetch<'_> {
+    fn is_invalidated(&self) -> bool {
+        self.cache
+            .fetches
+            .get(&self.summoner_id)
+            .is_some_and(|fetches| fetches.generation != self.generation)
+    }
+}
+
+impl Drop for InFlightFetch<'_> {
+    fn drop(&mut self) {
+        self.cache
+            .fetches
+            .remove_if_mut(&self.summoner_id, |_, fetches| {
+                fetches.in_flight -= 1;
+                fetches.in_flight == 0
+            });
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ServerFnCacheStats {
+    pub enabled: bool,
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Responses of the summoner page server fns, so that switching tab does not run the same queries
+/// again. Entries of a summoner are invalidated when new matches are stored for them.
+pub struct ServerFnCache {
+    pub enabled: bool,
+    pub max_entries: usize,
+    pub expiration_duration: Duration,
+    pub entries: DashMap<ServerFnCacheKey, CachedResponse>,
+    pub keys_by_summoner: DashMap<i32, HashSet<ServerFnCacheKey>>,
+    /// Summoners with a fetch in flight, an invalidation bumps their generation so a fetch started
+    /// before is not stored. Entries are dropped with the last fetch, the map stays small.
+    fetches: DashMap<i32, SummonerFetches>,
+    /// Bumped on every invalidation, for the fetches whose summoner is only known afterwards.
+    invalidations: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ServerFnCache {
+    pub fn new(enabled: bool, max_entries: usize, expiration_duration: Duration) -> Self {
+        ServerFnCache {
+            enabled,
+            max_entries,
+            expiration_duration,
+            entries: DashMap::new(),
+            keys_by_summoner: DashMap::new(),
+            fetches: DashMap::new(),
+            invalidations: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn get<T: Clone + 'static>(&self, key: &ServerFnCacheKey) -> Option<T> {
+        if !self.enabled {
+            return None;
+        }
+        let (value, expired) = match self.entries.get(key) {
+            Some(entry) => {
+                let expired = entry.inserted_at.elapsed() >= self.expiration_duration;
+                (entry.value.downcast_ref::<T>().cloned(), expired)
+            }
+            None => (None, false),
+        };
+        if expired {
+            self.entries.remove(key);
+        }
+        match value.filter(|_| !expired) {
+            Some(value) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(value)
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    fn insert<T: Clone + Send + Sync + 'static>(
+        &self,
+        key: ServerFnCacheKey,
+        summoner_id: i32,
+        value: T,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        if self.entries.len() >= self.max_entries {
+            self.evict();
+        }
+        self.keys_by_summoner
+            .entry(summoner_id)
+            .or_default()
+            .insert(key.clone());
+        self.entries.insert(
+            key,
+            CachedResponse {
+                summoner_id,
+                value: Arc::new(value),
+                inserted_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Returns the cached response or stores the one of `fetch`, errors are not cached.
+    pub async fn get_or_fetch<T, F>(
+        &self,
+        key: ServerFnCacheKey,
+        summoner_id: i32,
+        fetch: F,
+    ) -> AppResult<T>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Future<Output = AppResult<T>>,
+    {
+        if let Some(value) = self.get::<T>(&key) {
+            return Ok(value);
+        }
+        let in_flight_fetch = self.start_fetch(summoner_id);
+        let value = fetch.await?;
+        // the summoner was invalidated during the fetch, the value may predate the new matches
+        if !in_flight_fetch.is_invalidated() {
+            self.insert(key, summoner_id, value.clone());
+        }
+        Ok(value)
+    }
+
+    /// Like `get_or_fetch` for the lookups whose summoner is resolved by `fetch`, the response is
+    /// registered under the resolved summoner id. `None` is not cached.
+    pub async fn get_or_fetch_resolved<T, F>(
+        &self,
+        key: ServerFnCacheKey,
+        fetch: F,
+        summoner_id: impl Fn(&T) -> i32,
+    ) -> AppResult<Option<T>>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: Future<Output = AppResult<Option<T>>>,
+    {
+        if let Some(value) = self.get::<T>(&key) {
+            return Ok(Some(value));
+        }
+        let invalidations = self.invalidations.load(Ordering::Acquire);
+        let value = fetch.await?;
+        if let Some(value) = &value {
+            if self.invalidations.load(Ordering::Acquire) == invalidations {
+                self.insert(key, summoner_id(value), value.clone());
+            }
+        }
+        Ok(value)
+    }
+
+    pub fn invalidate(&self, summoner_id: i32) {
+        if let Some(mut fetches) = self.fetches.get_mut(&summoner_id) {
+            fetches.generation += 1;
+        }
+        self.invalidations.fetch_add(1, Ordering::AcqRel);
+        if let Some((_, keys)) = self.keys_by_summoner.remove(&summoner_id) {
+            for key in keys {
+                self.entries.remove(&key);
+            }
+        }
+    }
+
+    fn start_fetch(&self, summoner_id: i32) -> InFlightFetch<'_> {
+        let mut fetches = self.fetches.entry(summoner_id).or_default();
+        fetches.in_flight += 1;
+        InFlightFetch {
+            cache: self,
+            summoner_id,
+            generation: fetches.generation,
+        }
+    }
+
+    pub fn remove_expired(&self) {
+        self.entries
+            .retain(|_, entry| entry.inserted_at.elapsed() < self.expiration_duration);
+        self.keys_by_summoner.retain(|_, keys| {
+            keys.retain(|key| self.entries.contains_key(key));
+            !keys.is_empty()
+        });
+    }
+
+    pub fn stats(&self) -> ServerFnCacheStats {
+        ServerFnCacheStats {
+            enabled: self.enabled,
+            entries: self.entries.len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Drops the expired entries, then the oldest ones until the cache is at most 90% full, so the
+    /// scans run once every `max_entries / 10` inserts at most.
+    fn evict(&self) {
+        self.remove_expired();
+        let target_len = self.max_entries - self.max_entries.div_ceil(EVICTION_BATCH_DIVISOR);
+        if self.entries.len() <= target_len {
+            return;
+        }
+        let mut oldest = self
+            .entries
+            .iter()
+            .map(|entry| (entry.inserted_at, entry.key().clone(), entry.summoner_id))
+            .collect::<Vec<_>>();
+        let evicted_len = oldest.len() - target_len;
+        if evicted_len < oldest.len() {
+            oldest.select_nth_unstable_by_key(evicted_len, |(inserted_at, _, _)| *inserted_at);
+        }
+        for (_, key, summoner_id) in oldest.into_iter().take(evicted_len) {
+            self.entries.remove(&key);
+            self.keys_by_summoner
+                .remove_if_mut(&summoner_id, |_, keys| {
+                    keys.remove(&key);
+                    keys.is_empty()
+                });
+        }
+    }
+}
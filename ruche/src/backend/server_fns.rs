@@ -1,9 +1,14 @@
+pub mod get_champion_tier_list;
 pub mod get_champions;
 pub mod get_encounter;
 pub mod get_encounters;
 pub mod get_live_game;
 pub mod get_match_details;
+pub mod get_match_filter_options;
 pub mod get_matches;
+pub mod get_multisearch;
 pub mod get_summoner;
+pub mod scout_live_game;
 pub mod search_summoner;
+pub mod suggest_summoners;
 pub mod update_summoner;
@@ -0,0 +1,186 @@
+use crate::views::champion_tier_list_page::ChampionTierList;
+use common::consts::platform_route::PlatformRoute;
+use leptos::prelude::*;
+use leptos::server;
+use leptos::server_fn::codec::Bitcode;
+
+#[server(input=Bitcode,output=Bitcode)]
+pub async fn get_champion_tier_list(
+    platform_route: Option<PlatformRoute>,
+    queue_id: Option<u8>,
+    version: Option<String>,
+) -> Result<ChampionTierList, ServerFnError> {
+    let state = expect_context::<crate::ssr::AppState>();
+    let db = state.db.clone();
+
+    ssr::inner_get_champion_tier_list(&db, platform_route, queue_id, version)
+        .await
+        .map_err(|e| e.to_server_fn_error())
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use crate::backend::ssr::{AppResult, PlatformRouteDb};
+    use crate::views::champion_tier_list_page::{
+        ChampionTierList, GlobalChampionStats, DEFAULT_TIER_LIST_QUEUE,
+    };
+    use common::consts::platform_route::PlatformRoute;
+    use common::consts::queue::Queue;
+    use itertools::Itertools;
+    use sqlx::{FromRow, PgPool};
+
+    /// Champions picked in less than this percentage of the matches are not ranked (tier 5).
+    const MIN_PICK_RATE: f32 = 0.5;
+    /// Upper bound of each tier as a share of the ranked champions, best first.
+    const TIER_THRESHOLDS: [f32; 3] = [0.1, 0.3, 0.7];
+
+    pub async fn inner_get_champion_tier_list(
+        db: &PgPool,
+        platform_route: Option<PlatformRoute>,
+        queue_id: Option<u8>,
+        version: Option<String>,
+    ) -> AppResult<ChampionTierList> {
+        let queue_id =
+            Queue::from(queue_id.unwrap_or(DEFAULT_TIER_LIST_QUEUE as u8)).to_u16() as i32;
+        let platform = platform_route.map(PlatformRouteDb::from);
+
+        let versions = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT version
+            FROM champion_stats_matches
+            WHERE queue_id = $1
+            GROUP BY version
+            ORDER BY string_to_array(version, '.')::INT[] DESC
+            "#,
+        )
+        .bind(queue_id)
+        .fetch_all(db)
+        .await?;
+        let Some(version) = version
+            .filter(|version| versions.contains(version))
+            .or_else(|| versions.first().cloned())
+        else {
+            return Ok(ChampionTierList::default());
+        };
+
+        let (total_matches, total_ban_matches) = sqlx::query_as::<_, (i64, i64)>(
+            r#"
+            SELECT coalesce(sum(total_matches), 0)::BIGINT,
+                   coalesce(sum(total_ban_matches), 0)::BIGINT
+            FROM champion_stats_matches
+            WHERE queue_id = $1
+              AND version = $2
+              AND ($3::platform_type IS NULL OR platform = $3)
+            "#,
+        )
+        .bind(queue_id)
+        .bind(&version)
+        .bind(platform)
+        .fetch_one(db)
+        .await?;
+
+        let champions = sqlx::query_as::<_, GlobalChampionStatsModel>(
+            r#"
+            SELECT champion_id,
+                   sum(total_picks)::BIGINT   AS total_picks,
+                   sum(total_wins)::BIGINT    AS total_wins,
+                   sum(total_bans)::BIGINT    AS total_bans,
+                   sum(total_kills)::BIGINT   AS total_kills,
+                   sum(total_deaths)::BIGINT  AS total_deaths,
+                   sum(total_assists)::BIGINT AS total_assists
+            FROM champion_stats
+            WHERE queue_id = $1
+              AND version = $2
+              AND ($3::platform_type IS NULL OR platform = $3)
+            GROUP BY champion_id
+            "#,
+        )
+        .bind(queue_id)
+        .bind(&version)
+        .bind(platform)
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|model| {
+            let rate = |value: i64, total: i64| {
+                if total == 0 {
+                    0.0
+                } else {
+                    value as f32 / total as f32 * 100.0
+                }
+            };
+            let per_pick = |value: i64| {
+                if model.total_picks == 0 {
+                    0.0
+                } else {
+                    value as f32 / model.total_picks as f32
+                }
+            };
+            GlobalChampionStats {
+                champion_id: model.champion_id as u16,
+                tier: 0,
+                total_picks: model.total_picks as u32,
+                win_rate: rate(model.total_wins, model.total_picks),
+                pick_rate: rate(model.total_picks, total_matches),
+                ban_rate: rate(model.total_bans, total_ban_matches),
+                avg_kills: per_pick(model.total_kills),
+                avg_deaths: per_pick(model.total_deaths),
+                avg_assists: per_pick(model.total_assists),
+            }
+        })
+        .collect_vec();
+
+        Ok(ChampionTierList {
+            versions,
+            version: Some(version),
+            total_matches: total_matches as u32,
+            champions: rank_champions(champions),
+        })
+    }
+
+    /// Tiers come from the rank of a score mixing the win rate with the popularity of the champion,
+    /// rarely picked champions are put in the last tier whatever their win rate.
+    fn rank_champions(champions: Vec<GlobalChampionStats>) -> Vec<GlobalChampionStats> {
+        let score = |stats: &GlobalChampionStats| {
+            stats.win_rate + stats.pick_rate * 0.25 + stats.ban_rate * 0.1
+        };
+        let (ranked, unranked): (Vec<_>, Vec<_>) = champions
+            .into_iter()
+            .partition(|stats| stats.pick_rate >= MIN_PICK_RATE);
+        let ranked_len = ranked.len() as f32;
+        ranked
+            .into_iter()
+            .sorted_by(|a, b| score(b).total_cmp(&score(a)))
+            .enumerate()
+            .map(|(index, mut stats)| {
+                let position = index as f32 / ranked_len;
+                stats.tier = TIER_THRESHOLDS
+                    .iter()
+                    .position(|threshold| position < *threshold)
+                    .unwrap_or(TIER_THRESHOLDS.len()) as u8
+                    + 1;
+                stats
+            })
+            .chain(
+                unranked
+                    .into_iter()
+                    .sorted_by(|a, b| b.win_rate.total_cmp(&a.win_rate))
+                    .map(|mut stats| {
+                        stats.tier = TIER_THRESHOLDS.len() as u8 + 2;
+                        stats
+                    }),
+            )
+            .collect()
+    }
+
+    #[derive(FromRow)]
+    struct GlobalChampionStatsModel {
+        pub champion_id: i32,
+        pub total_picks: i64,
+        pub total_wins: i64,
+        pub total_bans: i64,
+        pub total_kills: i64,
+        pub total_deaths: i64,
+        pub total_assists: i64,
+    }
+}
@@ -9,10 +9,20 @@ pub async fn get_champions(
     summoner_id: i32,
     filters: Option<BackEndMatchFiltersSearch>,
 ) -> Result<Vec<ChampionStats>, ServerFnError> {
+    use crate::backend::server_fn_cache::ServerFnCacheKey;
+
     let state = expect_context::<crate::ssr::AppState>();
     let db = state.db.clone();
+    let filters = filters.unwrap_or_default();
 
-    ssr::inner_get_champions(&db, summoner_id, filters.unwrap_or_default())
+    let cache_key = ServerFnCacheKey::new("get_champions", &(summoner_id, filters.clone()));
+    state
+        .server_fn_cache
+        .get_or_fetch(
+            cache_key,
+            summoner_id,
+            ssr::inner_get_champions(&db, summoner_id, filters),
+        )
         .await
         .map_err(|e| e.to_server_fn_error())
 }
@@ -25,64 +35,19 @@ pub mod ssr {
     use bigdecimal::{BigDecimal, ToPrimitive};
     use common::consts::queue::Queue;
     use itertools::Itertools;
-    use sqlx::{FromRow, PgPool, QueryBuilder};
+    use sqlx::{FromRow, PgPool, Postgres, QueryBuilder};
 
+    /// Reads the `summoner_champion_stats` aggregates unless a filter needs the match rows.
     pub async fn inner_get_champions(
         db: &PgPool,
         summoner_id: i32,
         filters: BackEndMatchFiltersSearch,
     ) -> AppResult<Vec<ChampionStats>> {
-        let start_date = filters.start_date_to_naive();
-        let end_date = filters.end_date_to_naive();
-
-        let mut query = QueryBuilder::new(
-            r#"
-             SELECT lmp.champion_id,
-               count(lmp.lol_match_id)                  as total_matches,
-               sum(CASE WHEN lmp.won THEN 1 ELSE 0 END) AS total_wins,
-               avg(lmp.kill_participation)              as avg_kill_participation,
-               avg(lmp.kills)                           as avg_kills,
-               avg(lmp.deaths)                          as avg_deaths,
-               avg(lmp.assists)                         as avg_assists,
-               avg(lmp.gold_earned)                     as avg_gold_earned,
-               avg(lmp.cs)                              as avg_cs,
-               avg(lmp.kda)                              as avg_kda,
-               avg(lmp.damage_dealt_to_champions)       as avg_damage_dealt_to_champions,
-               avg(lmp.damage_taken)                    as avg_damage_taken,
-               sum(lmp.double_kills)                    AS total_double_kills,
-               sum(lmp.triple_kills)                    AS total_triple_kills,
-               sum(lmp.quadra_kills)                    AS total_quadra_kills,
-               sum(lmp.penta_kills)                     AS total_penta_kills
-            FROM lol_match_participants as lmp
-                     left JOIN lol_matches lm ON lm.id = lmp.lol_match_id
-            WHERE lmp.summoner_id =
-        "#,
-        );
-
-        query.push_bind(summoner_id);
-        if let Some(champion_id) = filters.champion_id {
-            let sql_filter = " AND lmp.champion_id = ";
-            query.push(sql_filter);
-            query.push_bind(champion_id as i32);
-        }
-        if let Some(queue_id) = filters.queue_id {
-            let sql_filter = " AND lm.queue_id = ";
-            query.push(sql_filter);
-            query.push_bind(Queue::from(queue_id).to_u16() as i32);
-        }
-
-        if let Some(start_date) = start_date {
-            let sql_filter = " AND lm.match_end >= ";
-            query.push(sql_filter);
-            query.push_bind(start_date);
-        }
-        if let Some(end_date) = end_date {
-            let sql_filter = " AND lm.match_end <= ";
-            query.push(sql_filter);
-            query.push_bind(end_date);
-        }
-        query.push(" GROUP BY lmp.champion_id ORDER BY total_matches DESC");
-
+        let query = if filters_use_aggregates(&filters) {
+            aggregated_champions_query(summoner_id, &filters)
+        } else {
+            raw_champions_query(summoner_id, &filters)
+        };
         Ok(query
             .build_query_as::<ChampionStatsModel>()
             .fetch_all(db)
@@ -92,6 +57,15 @@ pub mod ssr {
                 let win_rate = (champion_stats.total_wins as f32
                     / champion_stats.total_matches as f32)
                     * 100.0;
+                let arena_placements = if champion_stats.arena_placements.iter().all(|x| *x == 0) {
+                    Vec::new()
+                } else {
+                    champion_stats
+                        .arena_placements
+                        .iter()
+                        .map(|count| *count as u16)
+                        .collect_vec()
+                };
                 ChampionStats {
                     champion_id: champion_stats.champion_id as u16,
                     total_matches: champion_stats.total_matches as u16,
@@ -120,11 +94,129 @@ pub mod ssr {
                     total_triple_kills: champion_stats.total_triple_kills as u16,
                     total_quadra_kills: champion_stats.total_quadra_kills as u16,
                     total_penta_kills: champion_stats.total_penta_kills as u16,
+                    arena_placements,
                 }
             })
             .collect_vec())
     }
 
+    /// The aggregates are keyed by champion, queue and patch only, seasons are patch ranges.
+    fn filters_use_aggregates(filters: &BackEndMatchFiltersSearch) -> bool {
+        filters.start_date.is_none()
+            && filters.end_date.is_none()
+            && filters.result.is_none()
+            && filters.role.is_none()
+            && filters.min_duration.is_none()
+            && filters.max_duration.is_none()
+            && !filters.exclude_remakes
+            && filters.played_with.is_none()
+    }
+
+    fn aggregated_champions_query(
+        summoner_id: i32,
+        filters: &BackEndMatchFiltersSearch,
+    ) -> QueryBuilder<'static, Postgres> {
+        let mut query = QueryBuilder::new(
+            r#"
+            SELECT scs.champion_id,
+               sum(scs.total_matches)::BIGINT                                            AS total_matches,
+               sum(scs.total_wins)::BIGINT                                               AS total_wins,
+               (sum(scs.total_kill_participation) / sum(scs.total_matches))::NUMERIC     AS avg_kill_participation,
+               sum(scs.total_kills)::NUMERIC / sum(scs.total_matches)                    AS avg_kills,
+               sum(scs.total_deaths)::NUMERIC / sum(scs.total_matches)                   AS avg_deaths,
+               sum(scs.total_assists)::NUMERIC / sum(scs.total_matches)                  AS avg_assists,
+               sum(scs.total_gold_earned)::NUMERIC / sum(scs.total_matches)              AS avg_gold_earned,
+               sum(scs.total_cs)::NUMERIC / sum(scs.total_matches)                       AS avg_cs,
+               (sum(scs.total_kda) / sum(scs.total_matches))::NUMERIC                    AS avg_kda,
+               sum(scs.total_damage_dealt_to_champions)::NUMERIC / sum(scs.total_matches) AS avg_damage_dealt_to_champions,
+               sum(scs.total_damage_taken)::NUMERIC / sum(scs.total_matches)             AS avg_damage_taken,
+               sum(scs.total_double_kills)::BIGINT                                       AS total_double_kills,
+               sum(scs.total_triple_kills)::BIGINT                                       AS total_triple_kills,
+               sum(scs.total_quadra_kills)::BIGINT                                       AS total_quadra_kills,
+               sum(scs.total_penta_kills)::BIGINT                                        AS total_penta_kills,
+               ARRAY [sum(scs.arena_placements[1]), sum(scs.arena_placements[2]),
+                   sum(scs.arena_placements[3]), sum(scs.arena_placements[4]),
+                   sum(scs.arena_placements[5]), sum(scs.arena_placements[6]),
+                   sum(scs.arena_placements[7]), sum(scs.arena_placements[8])]::BIGINT[] AS arena_placements
+            FROM summoner_champion_stats as scs
+            WHERE scs.summoner_id =
+        "#,
+        );
+        query.push_bind(summoner_id);
+        if !filters.champion_ids.is_empty() {
+            query.push(" AND scs.champion_id = ANY(");
+            query.push_bind(
+                filters
+                    .champion_ids
+                    .iter()
+                    .map(|champion_id| *champion_id as i32)
+                    .collect_vec(),
+            );
+            query.push(")");
+        }
+        if !filters.queue_ids.is_empty() {
+            query.push(" AND scs.queue_id = ANY(");
+            query.push_bind(
+                filters
+                    .queue_ids
+                    .iter()
+                    .map(|queue_id| Queue::from(*queue_id).to_u16() as i32)
+                    .collect_vec(),
+            );
+            query.push(")");
+        }
+        filters.push_sql_version_filters(&mut query, "scs.version");
+        query.push(" GROUP BY scs.champion_id ORDER BY total_matches DESC");
+        query
+    }
+
+    /// Skips the matches without queue or version like the aggregates, so both paths count the
+    /// same games.
+    fn raw_champions_query(
+        summoner_id: i32,
+        filters: &BackEndMatchFiltersSearch,
+    ) -> QueryBuilder<'static, Postgres> {
+        let mut query = QueryBuilder::new(
+            r#"
+             SELECT lmp.champion_id,
+               count(lmp.lol_match_id)                  as total_matches,
+               sum(CASE WHEN lmp.won THEN 1 ELSE 0 END) AS total_wins,
+               avg(lmp.kill_participation)              as avg_kill_participation,
+               avg(lmp.kills)                           as avg_kills,
+               avg(lmp.deaths)                          as avg_deaths,
+               avg(lmp.assists)                         as avg_assists,
+               avg(lmp.gold_earned)                     as avg_gold_earned,
+               avg(lmp.cs)                              as avg_cs,
+               avg(lmp.kda)                              as avg_kda,
+               avg(lmp.damage_dealt_to_champions)       as avg_damage_dealt_to_champions,
+               avg(lmp.damage_taken)                    as avg_damage_taken,
+               sum(lmp.double_kills)                    AS total_double_kills,
+               sum(lmp.triple_kills)                    AS total_triple_kills,
+               sum(lmp.quadra_kills)                    AS total_quadra_kills,
+               sum(lmp.penta_kills)                     AS total_penta_kills,
+               ARRAY [count(*) FILTER (WHERE lmp.subteam_placement = 1),
+                   count(*) FILTER (WHERE lmp.subteam_placement = 2),
+                   count(*) FILTER (WHERE lmp.subteam_placement = 3),
+                   count(*) FILTER (WHERE lmp.subteam_placement = 4),
+                   count(*) FILTER (WHERE lmp.subteam_placement = 5),
+                   count(*) FILTER (WHERE lmp.subteam_placement = 6),
+                   count(*) FILTER (WHERE lmp.subteam_placement = 7),
+                   count(*) FILTER (WHERE lmp.subteam_placement >= 8)]
+                                                        AS arena_placements
+            FROM lol_match_participants as lmp
+                     INNER JOIN lol_matches lm ON lm.id = lmp.lol_match_id
+            WHERE lm.queue_id IS NOT NULL
+              AND lm.version IS NOT NULL
+              AND lmp.summoner_id =
+        "#,
+        );
+
+        query.push_bind(summoner_id);
+        filters.push_sql_filters(&mut query, "lmp");
+        query.push(" GROUP BY lmp.champion_id ORDER BY total_matches DESC");
+        query
+    }
+
     #[derive(FromRow)]
     struct ChampionStatsModel {
         pub champion_id: i32,
@@ -143,5 +235,7 @@ pub mod ssr {
         pub total_triple_kills: i64,
         pub total_quadra_kills: i64,
         pub total_penta_kills: i64,
+        /// Games count by placement, index 0 is the 1st place.
+        pub arena_placements: Vec<i64>,
     }
 }
@@ -74,8 +74,6 @@ pub mod ssr {
         let per_page = 20;
         let offset = (page_number.max(1) - 1) * per_page;
 
-        let start_date = filters.start_date_to_naive();
-        let end_date = filters.end_date_to_naive();
         let mut stats_query = QueryBuilder::new(
             r#"
             select
@@ -161,37 +159,8 @@ pub mod ssr {
         query.push_bind(summoner_id);
         stats_query.push_bind(summoner_id);
 
-        if let Some(champion_id) = filters.champion_id {
-            let sql_filter = " AND lmp1.champion_id = ";
-            query.push(sql_filter);
-            query.push_bind(champion_id as i32);
-            stats_query.push(sql_filter);
-            stats_query.push_bind(champion_id as i32);
-        }
-
-        if let Some(queue_id) = filters.queue_id {
-            let sql_filter = " AND lm.queue_id = ";
-            query.push(sql_filter);
-            query.push_bind(Queue::from(queue_id).to_u16() as i32);
-            stats_query.push(sql_filter);
-            stats_query.push_bind(Queue::from(queue_id).to_u16() as i32);
-        }
-
-        if let Some(start_date) = start_date {
-            let sql_filter = " AND lm.match_end >= ";
-            query.push(sql_filter);
-            query.push_bind(start_date);
-            stats_query.push(sql_filter);
-            stats_query.push_bind(start_date);
-        }
-
-        if let Some(end_date) = end_date {
-            let sql_filter = " AND lm.match_end <= ";
-            query.push(sql_filter);
-            query.push_bind(end_date);
-            stats_query.push(sql_filter);
-            stats_query.push_bind(end_date);
-        }
+        filters.push_sql_filters(&mut query, "lmp1");
+        filters.push_sql_filters(&mut stats_query, "lmp1");
 
         query.push(" order by lm.match_end desc limit 20 offset ");
         query.push_bind(offset);
@@ -364,7 +333,8 @@ pub mod ssr {
                    ss.profile_icon_id as profile_icon_id,
                    ss.summoner_level  as summoner_level,
                    ss.puuid           as puuid,
-                   ss.pro_player_slug            as pro_slug
+                   ss.pro_player_slug            as pro_slug,
+                   ss.matches_updated_at as matches_updated_at
             FROM summoners as ss
             WHERE
                 ss.id = $1"#,
@@ -403,6 +373,7 @@ pub mod ssr {
                 pro_slug: summoner_db
                     .pro_slug
                     .map(|slug| ProPlayerSlug::new(slug.as_str())),
+                updated_since: summoner_db.matches_updated_at.map(format_duration_since),
             })
     }
 
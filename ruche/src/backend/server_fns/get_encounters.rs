@@ -10,18 +10,36 @@ pub async fn get_encounters(
     search_summoner: Option<String>,
     filters: Option<BackEndMatchFiltersSearch>,
 ) -> Result<SummonerEncountersResult, ServerFnError> {
+    use crate::backend::server_fn_cache::ServerFnCacheKey;
+
     let state = expect_context::<crate::ssr::AppState>();
     let db = state.db.clone();
+    let filters = filters.unwrap_or_default();
 
-    ssr::inner_get_encounters(
-        &db,
-        summoner_id,
-        page_number as i32,
-        filters.unwrap_or_default(),
-        search_summoner.map(|r| r.to_string()),
-    )
-    .await
-    .map_err(|e| e.to_server_fn_error())
+    let cache_key = ServerFnCacheKey::new(
+        "get_encounters",
+        &(
+            summoner_id,
+            page_number,
+            search_summoner.clone(),
+            filters.clone(),
+        ),
+    );
+    state
+        .server_fn_cache
+        .get_or_fetch(
+            cache_key,
+            summoner_id,
+            ssr::inner_get_encounters(
+                &db,
+                summoner_id,
+                page_number as i32,
+                filters,
+                search_summoner,
+            ),
+        )
+        .await
+        .map_err(|e| e.to_server_fn_error())
 }
 
 #[cfg(feature = "ssr")]
@@ -31,7 +49,6 @@ pub mod ssr {
         SummonerEncountersResult, SummonerEncountersSummoner,
     };
     use crate::views::BackEndMatchFiltersSearch;
-    use common::consts::queue::Queue;
     use sqlx::{FromRow, PgPool, QueryBuilder};
     use std::collections::HashMap;
 
@@ -45,8 +62,6 @@ pub mod ssr {
         let per_page = 40;
         let offset = (page.max(1) - 1) * per_page;
 
-        let start_date = filters.start_date_to_naive();
-        let end_date = filters.end_date_to_naive();
         let search_summoner = search_summoner.filter(|s| !s.is_empty());
 
         let mut query = QueryBuilder::new(
@@ -70,16 +85,7 @@ pub mod ssr {
         query.push("JOIN lol_match_participants tm ON lmp.lol_match_id = tm.lol_match_id AND tm.summoner_id = ");
 
         query.push_bind(summoner_id);
-        if let Some(champion_id) = filters.champion_id {
-            let sql_filter = " AND tm.champion_id = ";
-            query.push(sql_filter);
-            query.push_bind(champion_id as i32);
-        }
-        if filters.queue_id.is_some()
-            || start_date.is_some()
-            || end_date.is_some()
-            || search_summoner.is_some()
-        {
+        if filters.needs_lol_match() || search_summoner.is_some() {
             query.push(" JOIN lol_matches lm ON lm.id = lmp.lol_match_id ");
         }
         query.push(" WHERE lmp.summoner_id != ");
@@ -90,22 +96,7 @@ pub mod ssr {
             query.push_bind(format!("%{}%", search_summoner));
         }
 
-        if let Some(queue_id) = filters.queue_id {
-            let sql_filter = " AND lm.queue_id = ";
-            query.push(sql_filter);
-            query.push_bind(Queue::from(queue_id).to_u16() as i32);
-        }
-
-        if let Some(start_date) = start_date {
-            let sql_filter = " AND lm.match_end >= ";
-            query.push(sql_filter);
-            query.push_bind(start_date);
-        }
-        if let Some(end_date) = end_date {
-            let sql_filter = " AND lm.match_end <= ";
-            query.push(sql_filter);
-            query.push_bind(end_date);
-        }
+        filters.push_sql_filters(&mut query, "tm");
         query.push(" GROUP BY lmp.summoner_id ORDER BY match_count DESC LIMIT 40 OFFSET ");
         query.push_bind(offset);
         let results = query
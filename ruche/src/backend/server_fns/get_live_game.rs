@@ -20,7 +20,8 @@ pub async fn get_live_game(
     if force_refresh || live_cache.get_game_data(summoner_id).is_none() {
         let riot_api = state.riot_api.clone();
         let puuid = Puuid::new(find_summoner_puuid_by_id(&db, summoner_id).await?.as_str());
-        let live_game = ssr::get_live_game_data(&db, &riot_api, puuid, platform_route).await?;
+        let live_game =
+            ssr::get_live_game_data(&db, &riot_api, &state.health, puuid, platform_route).await?;
         Ok(match live_game {
             Some((summoner_ids, live_data)) => {
                 live_cache.set_game_data(live_data.game_id, summoner_ids, live_data.clone());
@@ -42,6 +43,7 @@ pub async fn get_live_game(
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
+    use crate::backend::health::HealthState;
     use crate::backend::ssr::{AppResult, PlatformRouteDb};
     use crate::backend::tasks::update_matches::bulk_summoners::bulk_insert_summoners;
     use crate::backend::tasks::update_matches::{SummonerFull, TempSummoner};
@@ -50,7 +52,7 @@ pub mod ssr {
     use crate::ssr::RiotApiState;
     use crate::utils::{ProPlayerSlug, Puuid, RiotMatchId};
     use crate::views::summoner_page::summoner_live_page::{
-        LiveGame, LiveGameParticipant, LiveGameParticipantChampionStats,
+        LiveGame, LiveGameBan, LiveGameParticipant, LiveGameParticipantChampionStats,
         LiveGameParticipantRankedStats,
     };
     use bigdecimal::{BigDecimal, ToPrimitive};
@@ -64,6 +66,12 @@ pub mod ssr {
     use std::collections::{HashMap, HashSet};
     use std::sync::Arc;
 
+    /// Games played together on the same team before two participants are considered premade.
+    const PREMADE_MIN_GAMES: i64 = 2;
+    const PREMADE_RECENT_DAYS: i32 = 30;
+    /// Matches read for the recent form and the main role of a participant.
+    const RECENT_FORM_MATCHES: i64 = 10;
+
     pub async fn add_encounters(
         db: &PgPool,
         mut game_data: LiveGame,
@@ -86,23 +94,26 @@ pub mod ssr {
     pub async fn get_live_game_data(
         db: &PgPool,
         riot_api: &RiotApiState,
+        health: &HealthState,
         puuid: Puuid,
         platform: PlatformRoute,
     ) -> AppResult<Option<(Vec<i32>, LiveGame)>> {
         let live_game = riot_api
             .spectator_v5()
             .get_current_game_info_by_puuid(platform.to_riven(), puuid.as_ref())
-            .await
-            .ok()
-            .flatten();
-        if let Some(live_game) = live_game {
-            let (all_participants, live_game_stats) =
-                get_all_participants_live_game_stats(db, riot_api, vec![&live_game]).await?;
+            .await;
+        health.record_riot_api_call(live_game.is_ok());
+        if let Some(live_game) = live_game.ok().flatten() {
+            let (all_participants, live_game_stats, premade_pairs, recent_forms) =
+                get_all_participants_live_game_stats(db, riot_api, health, vec![&live_game])
+                    .await?;
             let (summoner_ids, live_game) = game_info_to_live_game(
                 RiotMatchId::get_live_version(live_game.platform_id.as_str(), live_game.game_id),
                 live_game,
                 &all_participants,
                 &live_game_stats,
+                &premade_pairs,
+                &recent_forms,
             );
             Ok(Some((summoner_ids, live_game)))
         } else {
@@ -113,6 +124,7 @@ pub mod ssr {
     pub async fn find_and_insert_new_summoners(
         db: &PgPool,
         riot_api: &RiotApiState,
+        health: &HealthState,
         puuids_details: &Vec<(String, PlatformRoute, i64)>,
     ) -> AppResult<HashMap<String, SummonerFull>> {
         let futures = puuids_details
@@ -129,6 +141,9 @@ pub mod ssr {
                 }
             });
         let summoners_accounts = futures::future::join_all(futures).await;
+        for (account, ..) in summoners_accounts.iter() {
+            health.record_riot_api_call(account.is_ok());
+        }
         let new_summoners = summoners_accounts
             .into_iter()
             .filter_map(|(account, _, platform_route, profile_icon_id)| {
@@ -155,9 +170,10 @@ pub mod ssr {
         game_info: CurrentGameInfo,
         all_participants: &HashMap<String, SummonerFull>,
         live_game_stats: &HashMap<i32, HashMap<i32, ParticipantLiveStats>>,
+        premade_pairs: &HashSet<(i32, i32)>,
+        recent_forms: &HashMap<i32, ParticipantRecentForm>,
     ) -> (Vec<i32>, LiveGame) {
         let mut participants = vec![];
-        let default_hashmap = HashMap::new();
         let mut summoner_ids = vec![];
         for participant in &game_info.participants {
             let participant_puuid = participant.puuid.clone();
@@ -171,58 +187,56 @@ pub mod ssr {
             let summoner_detail = all_participants
                 .get(participant_puuid.as_str())
                 .expect("summoner not found");
-            let stats = live_game_stats
-                .get(&summoner_detail.id)
-                .unwrap_or(&default_hashmap);
-            let champion_stats =
-                stats
-                    .get(&(participant.champion_id.0 as i32))
-                    .map(|champion_stats| LiveGameParticipantChampionStats {
-                        total_champion_played: champion_stats.total_match as u16,
-                        total_champion_wins: champion_stats.total_win as u16,
-                        avg_kills: champion_stats.avg_kills.to_f32().unwrap_or_default(),
-                        avg_deaths: champion_stats.avg_deaths.to_f32().unwrap_or_default(),
-                        avg_assists: champion_stats.avg_assists.to_f32().unwrap_or_default(),
-                    });
-            let (total_wins, total_ranked) = stats.iter().fold((0, 0), |acc, (_, v)| {
-                (acc.0 + v.total_win, acc.1 + v.total_match)
-            });
-
-            let ranked_stats = if total_ranked == 0 {
-                None
-            } else {
-                Some(LiveGameParticipantRankedStats {
-                    total_ranked: total_ranked as u16,
-                    total_ranked_wins: total_wins as u16,
-                })
-            };
-            let (perk_primary_selection_id, perk_sub_style_id) = match participant.perks.clone() {
-                None => (0, 0),
-                Some(perks) => {
-                    let primary = perks.perk_ids.first().cloned().unwrap_or_default();
-                    let sub_style = perks.perk_sub_style;
-                    (primary as u16, sub_style as u16)
-                }
+            let (perk_ids, perk_primary_style_id, perk_sub_style_id) = match &participant.perks {
+                None => (vec![], 0, 0),
+                Some(perks) => (
+                    perks
+                        .perk_ids
+                        .iter()
+                        .map(|perk_id| *perk_id as u16)
+                        .collect_vec(),
+                    perks.perk_style as u16,
+                    perks.perk_sub_style as u16,
+                ),
             };
             summoner_ids.push(summoner_detail.id);
-            participants.push(LiveGameParticipant {
+            let mut live_participant = LiveGameParticipant {
                 summoner_id: summoner_detail.id,
                 champion_id: participant.champion_id.0 as u16,
                 summoner_spell1_id: participant.spell1_id as u16,
                 summoner_spell2_id: participant.spell2_id as u16,
-                perk_primary_selection_id,
+                perk_primary_selection_id: perk_ids.first().copied().unwrap_or_default(),
+                perk_primary_style_id,
                 perk_sub_style_id,
+                perk_ids,
+                recent_results: vec![],
+                main_role: None,
                 game_name: summoner_detail.game_name.clone(),
                 tag_line: summoner_detail.tag_line.clone(),
                 platform: summoner_detail.platform.into(),
                 summoner_level: summoner_detail.summoner_level as u16,
                 team_id: participant.team_id as u16,
-                ranked_stats,
-                champion_stats,
+                ranked_stats: None,
+                champion_stats: None,
                 encounter_count: 0,
+                premade_group: None,
                 pro_player_slug: summoner_detail.pro_player_slug.clone(),
-            })
+            };
+            set_participant_stats(&mut live_participant, live_game_stats, recent_forms);
+            participants.push(live_participant);
         }
+        set_premade_groups(&mut participants, premade_pairs);
+        let bans = game_info
+            .banned_champions
+            .iter()
+            .filter(|ban| ban.champion_id.0 > 0)
+            .sorted_by_key(|ban| ban.pick_turn)
+            .map(|ban| LiveGameBan {
+                champion_id: ban.champion_id.0 as u16,
+                team_id: ban.team_id as u16,
+                pick_turn: ban.pick_turn as u8,
+            })
+            .collect_vec();
         (
             summoner_ids,
             LiveGame {
@@ -234,17 +248,201 @@ pub mod ssr {
                     .map(|x| Queue::from_u16(x.0))
                     .unwrap(),
                 participants,
+                bans,
             },
         )
     }
 
+    /// Recomputes the stats of the participants from the stored matches, used once a scout stored
+    /// more of their history.
+    pub async fn refresh_live_game_stats(db: &PgPool, live_game: &mut LiveGame) -> AppResult<()> {
+        let summoner_ids = live_game
+            .participants
+            .iter()
+            .map(|participant| participant.summoner_id)
+            .collect_vec();
+        let (live_game_stats, premade_pairs, recent_forms) = tokio::try_join!(
+            get_summoners_live_stats(db, &summoner_ids),
+            find_premade_pairs(db, &summoner_ids),
+            find_participants_recent_form(db, &summoner_ids),
+        )?;
+        for participant in live_game.participants.iter_mut() {
+            set_participant_stats(participant, &live_game_stats, &recent_forms);
+            participant.premade_group = None;
+        }
+        set_premade_groups(&mut live_game.participants, &premade_pairs);
+        Ok(())
+    }
+
+    fn set_participant_stats(
+        participant: &mut LiveGameParticipant,
+        live_game_stats: &HashMap<i32, HashMap<i32, ParticipantLiveStats>>,
+        recent_forms: &HashMap<i32, ParticipantRecentForm>,
+    ) {
+        let default_hashmap = HashMap::new();
+        let stats = live_game_stats
+            .get(&participant.summoner_id)
+            .unwrap_or(&default_hashmap);
+        participant.champion_stats =
+            stats
+                .get(&(participant.champion_id as i32))
+                .map(|champion_stats| LiveGameParticipantChampionStats {
+                    total_champion_played: champion_stats.total_match as u16,
+                    total_champion_wins: champion_stats.total_win as u16,
+                    avg_kills: champion_stats.avg_kills.to_f32().unwrap_or_default(),
+                    avg_deaths: champion_stats.avg_deaths.to_f32().unwrap_or_default(),
+                    avg_assists: champion_stats.avg_assists.to_f32().unwrap_or_default(),
+                });
+        let (total_wins, total_ranked) = stats.iter().fold((0, 0), |acc, (_, v)| {
+            (acc.0 + v.total_win, acc.1 + v.total_match)
+        });
+        participant.ranked_stats = if total_ranked == 0 {
+            None
+        } else {
+            Some(LiveGameParticipantRankedStats {
+                total_ranked: total_ranked as u16,
+                total_ranked_wins: total_wins as u16,
+            })
+        };
+        (participant.recent_results, participant.main_role) = recent_forms
+            .get(&participant.summoner_id)
+            .map(|form| (form.results.clone(), form.main_role.clone()))
+            .unwrap_or_default();
+    }
+
+    /// Clusters the participants of a team linked by a premade pair, groups are numbered from 1
+    /// across the whole game so that both teams get distinct colours.
+    fn set_premade_groups(
+        participants: &mut [LiveGameParticipant],
+        premade_pairs: &HashSet<(i32, i32)>,
+    ) {
+        fn find(parents: &mut [usize], index: usize) -> usize {
+            let mut root = index;
+            while parents[root] != root {
+                root = parents[root];
+            }
+            parents[index] = root;
+            root
+        }
+
+        // union find over the participant indexes
+        let mut parents = (0..participants.len()).collect_vec();
+        for (i, j) in (0..participants.len()).tuple_combinations() {
+            let (a, b) = (&participants[i], &participants[j]);
+            if a.team_id != b.team_id {
+                continue;
+            }
+            let pair = (
+                a.summoner_id.min(b.summoner_id),
+                a.summoner_id.max(b.summoner_id),
+            );
+            if premade_pairs.contains(&pair) {
+                let (root_i, root_j) = (find(&mut parents, i), find(&mut parents, j));
+                parents[root_i] = root_j;
+            }
+        }
+        let roots = (0..participants.len())
+            .map(|index| find(&mut parents, index))
+            .collect_vec();
+        let mut groups = HashMap::new();
+        for (index, root) in roots.iter().enumerate() {
+            if roots.iter().filter(|other| *other == root).count() < 2 {
+                continue;
+            }
+            let next_group = groups.len() as u8 + 1;
+            let group = *groups.entry(*root).or_insert(next_group);
+            participants[index].premade_group = Some(group);
+        }
+    }
+
+    /// Pairs of summoners, smallest id first, who played at least `PREMADE_MIN_GAMES` games on the
+    /// same team during the last `PREMADE_RECENT_DAYS` days.
+    pub async fn find_premade_pairs(
+        db: &PgPool,
+        summoner_ids: &[i32],
+    ) -> AppResult<HashSet<(i32, i32)>> {
+        Ok(sqlx::query_as::<_, (i32, i32)>(
+            r#"
+            SELECT lmp1.summoner_id, lmp2.summoner_id
+            FROM lol_match_participants AS lmp1
+                     INNER JOIN lol_match_participants AS lmp2
+                                ON lmp2.lol_match_id = lmp1.lol_match_id
+                                    AND lmp2.team_id = lmp1.team_id
+                                    AND lmp2.summoner_id > lmp1.summoner_id
+                     INNER JOIN lol_matches AS lm ON lm.id = lmp1.lol_match_id
+            WHERE lmp1.summoner_id = ANY($1)
+              AND lmp2.summoner_id = ANY($1)
+              AND lm.match_end >= NOW() - make_interval(days => $2)
+            GROUP BY lmp1.summoner_id, lmp2.summoner_id
+            HAVING count(*) >= $3
+            "#,
+        )
+        .bind(summoner_ids)
+        .bind(PREMADE_RECENT_DAYS)
+        .bind(PREMADE_MIN_GAMES)
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .collect())
+    }
+
+    /// Results of the last `RECENT_FORM_MATCHES` matches of each summoner, newest first, and the
+    /// position they played the most among them.
+    pub async fn find_participants_recent_form(
+        db: &PgPool,
+        summoner_ids: &[i32],
+    ) -> AppResult<HashMap<i32, ParticipantRecentForm>> {
+        let rows = sqlx::query_as::<_, (i32, bool, Option<String>)>(
+            r#"
+            SELECT summoner.id, recent.won, recent.team_position
+            FROM UNNEST($1::INT[]) AS summoner(id)
+                     CROSS JOIN LATERAL (
+                SELECT lmp.won, lmp.team_position, lm.match_end
+                FROM lol_match_participants AS lmp
+                         INNER JOIN lol_matches AS lm ON lm.id = lmp.lol_match_id
+                WHERE lmp.summoner_id = summoner.id
+                  AND lm.match_end IS NOT NULL
+                ORDER BY lm.match_end DESC
+                LIMIT $2
+                ) AS recent
+            ORDER BY summoner.id, recent.match_end DESC
+            "#,
+        )
+        .bind(summoner_ids)
+        .bind(RECENT_FORM_MATCHES)
+        .fetch_all(db)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .chunk_by(|(summoner_id, _, _)| *summoner_id)
+            .into_iter()
+            .map(|(summoner_id, matches)| {
+                let (results, positions): (Vec<_>, Vec<_>) = matches
+                    .map(|(_, won, team_position)| (won, team_position))
+                    .unzip();
+                let position_counts = positions.iter().flatten().counts();
+                // ties go to the most recent position
+                let main_role = positions
+                    .iter()
+                    .flatten()
+                    .rev()
+                    .max_by_key(|position| position_counts[position])
+                    .cloned();
+                (summoner_id, ParticipantRecentForm { results, main_role })
+            })
+            .collect())
+    }
+
     pub async fn get_all_participants_live_game_stats(
         db: &PgPool,
         riot_api: &Arc<RiotApi>,
+        health: &HealthState,
         live_games: Vec<&CurrentGameInfo>,
     ) -> AppResult<(
         HashMap<String, SummonerFull>,
         HashMap<i32, HashMap<i32, ParticipantLiveStats>>,
+        HashSet<(i32, i32)>,
+        HashMap<i32, ParticipantRecentForm>,
     )> {
         let participant_puuids_info = live_games
             .iter()
@@ -294,7 +492,7 @@ pub mod ssr {
             })
             .collect::<Vec<(String, PlatformRoute, i64)>>();
         let inserted_summoners =
-            find_and_insert_new_summoners(db, riot_api, &puuids_platform_not_found).await?;
+            find_and_insert_new_summoners(db, riot_api, health, &puuids_platform_not_found).await?;
         let all_participants = inserted_summoners
             .into_iter()
             .chain(summoner_details.into_iter())
@@ -306,6 +504,8 @@ pub mod ssr {
         Ok((
             all_participants,
             get_summoners_live_stats(db, &all_summoner_ids).await?,
+            find_premade_pairs(db, &all_summoner_ids).await?,
+            find_participants_recent_form(db, &all_summoner_ids).await?,
         ))
     }
 
@@ -313,23 +513,40 @@ pub mod ssr {
         db: &PgPool,
         summoner_ids: &[i32],
     ) -> AppResult<HashMap<i32, HashMap<i32, ParticipantLiveStats>>> {
-        let query_results = sqlx::query_as::<_, ParticipantLiveStats>(r#"
-            select
-                summoner_id,
-                champion_id,
-                count(lmp.lol_match_id) as total_match,
-                sum(CASE WHEN won THEN 1 ELSE 0 END) as total_win,
-                avg(lmp.kills) as avg_kills,
-                avg(lmp.deaths) as avg_deaths,
-                avg(lmp.assists) as avg_assists
-            from lol_match_participants as lmp
-                join lol_matches as lm on lmp.lol_match_id = lm.id
-            where lmp.summoner_id = ANY($1) and lm.queue_id = 420 and lm.match_end >= '2024-09-25 12:00:00'
-            group by lmp.summoner_id, lmp.champion_id;
-        "#)  // 420 is the queue id for ranked solo/duo and 2024-09-25 is the split 3 s14 start date
-            .bind(summoner_ids)
-            .fetch_all(db)
-            .await?;
+        // ranked solo/duo (420) aggregates of the current season, the last one of `seasons` started
+        // at the latest stored patch, with the same patch range as the season filter
+        let query_results = sqlx::query_as::<_, ParticipantLiveStats>(
+            r#"
+            WITH current_season AS (SELECT string_to_array(s.start_version, '.')::INT[] AS start_version,
+                                           coalesce(string_to_array(s.end_version, '.')::INT[], '{32767}') AS end_version
+                                    FROM seasons AS s
+                                    WHERE string_to_array(s.start_version, '.')::INT[] <=
+                                          (SELECT string_to_array(version, '.')::INT[]
+                                           FROM lol_matches
+                                           WHERE version IS NOT NULL
+                                           ORDER BY string_to_array(version, '.')::INT[] DESC
+                                           LIMIT 1)
+                                    ORDER BY string_to_array(s.start_version, '.')::INT[] DESC
+                                    LIMIT 1)
+            SELECT scs.summoner_id,
+                   scs.champion_id,
+                   sum(scs.total_matches)::BIGINT                         AS total_match,
+                   sum(scs.total_wins)::BIGINT                            AS total_win,
+                   sum(scs.total_kills)::NUMERIC / sum(scs.total_matches)   AS avg_kills,
+                   sum(scs.total_deaths)::NUMERIC / sum(scs.total_matches)  AS avg_deaths,
+                   sum(scs.total_assists)::NUMERIC / sum(scs.total_matches) AS avg_assists
+            FROM summoner_champion_stats AS scs
+                     CROSS JOIN current_season AS cs
+            WHERE scs.summoner_id = ANY($1)
+              AND scs.queue_id = 420
+              AND string_to_array(scs.version, '.')::INT[] >= cs.start_version
+              AND string_to_array(scs.version, '.')::INT[] <= cs.end_version
+            GROUP BY scs.summoner_id, scs.champion_id
+            "#,
+        )
+        .bind(summoner_ids)
+        .fetch_all(db)
+        .await?;
         let mut nested_map = HashMap::new();
 
         for participant in query_results {
@@ -382,6 +599,12 @@ pub mod ssr {
         .collect::<HashMap<String, SummonerFull>>())
     }
 
+    pub struct ParticipantRecentForm {
+        /// Newest first.
+        pub results: Vec<bool>,
+        pub main_role: Option<String>,
+    }
+
     #[derive(sqlx::FromRow)]
     pub struct ParticipantLiveStats {
         pub summoner_id: i32,
@@ -35,6 +35,7 @@ pub async fn get_match_details(
             match_id,
             riot_match_id.as_ref(),
             platform,
+            state.match_archive_enabled,
         )
         .await?;
         match_timelines = ssr::get_match_timeline(&db, match_id).await?;
@@ -115,7 +116,15 @@ pub mod ssr {
                lmp.item3_id,
                lmp.item4_id,
                lmp.item5_id,
-               lmp.item6_id
+               lmp.item6_id,
+               lmp.player_subteam_id,
+               lmp.subteam_placement,
+               lmp.augment1_id,
+               lmp.augment2_id,
+               lmp.augment3_id,
+               lmp.augment4_id,
+               lmp.augment5_id,
+               lmp.augment6_id
             FROM lol_match_participants as lmp
                 left JOIN summoners as ss ON ss.id = lmp.summoner_id
             WHERE lmp.lol_match_id = $1;
@@ -159,6 +168,14 @@ pub mod ssr {
                 deaths: lmp.deaths as u16,
                 assists: lmp.assists as u16,
                 champ_level: lmp.champ_level as u16,
+                player_subteam_id: lmp.player_subteam_id.unwrap_or_default() as u16,
+                subteam_placement: lmp.subteam_placement.unwrap_or_default() as u16,
+                augment1_id: lmp.augment1_id.unwrap_or_default() as u16,
+                augment2_id: lmp.augment2_id.unwrap_or_default() as u16,
+                augment3_id: lmp.augment3_id.unwrap_or_default() as u16,
+                augment4_id: lmp.augment4_id.unwrap_or_default() as u16,
+                augment5_id: lmp.augment5_id.unwrap_or_default() as u16,
+                augment6_id: lmp.augment6_id.unwrap_or_default() as u16,
                 kill_participation: (lmp
                     .kill_participation
                     .map_or(0.0, |bd| bd.to_f32().unwrap_or(0.0))
@@ -269,6 +286,14 @@ pub mod ssr {
         pub item4_id: Option<i64>,
         pub item5_id: Option<i64>,
         pub item6_id: Option<i64>,
+        pub player_subteam_id: Option<i32>,
+        pub subteam_placement: Option<i32>,
+        pub augment1_id: Option<i32>,
+        pub augment2_id: Option<i32>,
+        pub augment3_id: Option<i32>,
+        pub augment4_id: Option<i32>,
+        pub augment5_id: Option<i32>,
+        pub augment6_id: Option<i32>,
     }
 
     #[derive(FromRow)]
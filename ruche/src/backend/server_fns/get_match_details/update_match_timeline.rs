@@ -1,4 +1,5 @@
 use crate::backend::ssr::{AppError, AppResult};
+use crate::backend::tasks::update_matches::match_archive;
 use crate::ssr::RiotApiState;
 use crate::views::summoner_page::match_details::{ItemEvent, ItemEventType, Skill};
 use chrono::NaiveDateTime;
@@ -14,6 +15,7 @@ pub async fn update_match_timeline(
     match_id: i32,
     riot_match_id: &str,
     platform_route: PlatformRoute,
+    archive_timeline: bool,
 ) -> AppResult<()> {
     // Fetch the match timeline
     let riven_pr = platform_route.to_riven();
@@ -22,6 +24,9 @@ pub async fn update_match_timeline(
         .get_timeline(riven_pr.to_regional(), riot_match_id)
         .await?
         .ok_or_else(|| AppError::CustomError("Timeline not found".into()))?;
+    if archive_timeline {
+        match_archive::archive_timeline(db, match_id, &timeline).await?;
+    }
 
     let puuids_summoner_ids =
         find_summoner_ids_by_puuids(db, &timeline.metadata.participants).await?;
@@ -0,0 +1,51 @@
+use crate::views::components::match_filters::MatchFilterOptions;
+use leptos::prelude::*;
+use leptos::server;
+use leptos::server_fn::codec::Bitcode;
+
+#[server(input=Bitcode,output=Bitcode)]
+pub async fn get_match_filter_options() -> Result<MatchFilterOptions, ServerFnError> {
+    let state = expect_context::<crate::ssr::AppState>();
+    let db = state.db.clone();
+
+    ssr::inner_get_match_filter_options(&db)
+        .await
+        .map_err(|e| e.to_server_fn_error())
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use crate::backend::ssr::AppResult;
+    use crate::views::components::match_filters::{MatchFilterOptions, SeasonOption};
+    use crate::views::CompactPatch;
+    use sqlx::PgPool;
+
+    pub async fn inner_get_match_filter_options(db: &PgPool) -> AppResult<MatchFilterOptions> {
+        let patches = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT version
+            FROM lol_matches
+            WHERE version IS NOT NULL
+            GROUP BY version
+            ORDER BY string_to_array(version, '.')::INT[] DESC
+            "#,
+        )
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .filter_map(|version| CompactPatch::parse(version.as_str()))
+        .collect();
+        let seasons = sqlx::query_as::<_, (i32, String)>(
+            "SELECT id, name FROM seasons ORDER BY string_to_array(start_version, '.')::INT[] DESC",
+        )
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|(id, name)| SeasonOption {
+            id: id as u16,
+            name,
+        })
+        .collect();
+        Ok(MatchFilterOptions { patches, seasons })
+    }
+}
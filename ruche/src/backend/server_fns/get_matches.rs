@@ -1,4 +1,4 @@
-use crate::views::summoner_page::summoner_matches_page::GetSummonerMatchesResult;
+use crate::views::summoner_page::summoner_matches_page::{GetSummonerMatchesResult, MatchesCursor};
 use crate::views::BackEndMatchFiltersSearch;
 use leptos::prelude::*;
 use leptos::server_fn::codec::Bitcode;
@@ -7,26 +7,44 @@ use leptos::server_fn::codec::Bitcode;
 pub async fn get_matches(
     summoner_id: i32,
     page_number: u16,
+    cursor: Option<MatchesCursor>,
     filters: Option<BackEndMatchFiltersSearch>,
 ) -> Result<GetSummonerMatchesResult, ServerFnError> {
+    use crate::backend::server_fn_cache::ServerFnCacheKey;
+
     let state = expect_context::<crate::ssr::AppState>();
     let db = state.db.clone();
+    let filters = filters.unwrap_or_default();
 
-    ssr::fetch_matches(
-        &db,
-        summoner_id,
-        page_number as i32,
-        filters.unwrap_or_default(),
-    )
-    .await
-    .map_err(|e| e.to_server_fn_error())
+    let cache_key = ServerFnCacheKey::new(
+        "get_matches",
+        &(summoner_id, page_number, cursor, filters.clone()),
+    );
+    state
+        .server_fn_cache
+        .get_or_fetch(
+            cache_key,
+            summoner_id,
+            ssr::fetch_matches(
+                &db,
+                &state.matches_stats_cache,
+                summoner_id,
+                page_number as i32,
+                cursor,
+                filters,
+            ),
+        )
+        .await
+        .map_err(|e| e.to_server_fn_error())
 }
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
+    use crate::backend::matches_stats_cache::MatchesStatsCache;
     use crate::backend::ssr::{format_duration_since, AppResult, PlatformRouteDb};
     use crate::views::summoner_page::summoner_matches_page::{
-        GetSummonerMatchesResult, MatchesResultInfo, SummonerMatch, SummonerMatchParticipant,
+        GetSummonerMatchesResult, MatchesCursor, MatchesResultInfo, SummonerMatch,
+        SummonerMatchParticipant,
     };
     use crate::views::BackEndMatchFiltersSearch;
     use bigdecimal::{BigDecimal, ToPrimitive};
@@ -39,17 +57,20 @@ pub mod ssr {
     use sqlx::{FromRow, PgPool, QueryBuilder};
     use std::collections::HashMap;
 
+    pub const MATCHES_PER_PAGE: i32 = 20;
+
+    /// Pages are read after `cursor` when given, skipping the pages between `cursor.page` and
+    /// `page`, so only the page links jumping past the known cursors pay for an offset.
     pub async fn fetch_matches(
         db: &PgPool,
+        stats_cache: &MatchesStatsCache,
         summoner_id: i32,
         page: i32,
+        cursor: Option<MatchesCursor>,
         filters: BackEndMatchFiltersSearch,
     ) -> AppResult<GetSummonerMatchesResult> {
-        let per_page = 20;
-        let offset = (page.max(1) - 1) * per_page;
-
-        let start_date = filters.start_date_to_naive();
-        let end_date = filters.end_date_to_naive();
+        let per_page = MATCHES_PER_PAGE;
+        let page = page.max(1);
 
         let mut statistics_query = QueryBuilder::new(
             r#"
@@ -91,6 +112,14 @@ pub mod ssr {
                    lmp.item4_id,
                    lmp.item5_id,
                    lmp.item6_id,
+                   lmp.player_subteam_id,
+                   lmp.subteam_placement,
+                   lmp.augment1_id,
+                   lmp.augment2_id,
+                   lmp.augment3_id,
+                   lmp.augment4_id,
+                   lmp.augment5_id,
+                   lmp.augment6_id,
                    lm.match_id       AS riot_match_id,
                    lm.platform       AS platform,
                    lm.queue_id       AS lol_match_queue_id,
@@ -102,83 +131,61 @@ pub mod ssr {
             WHERE lmp.summoner_id =
         "#,
         );
-        statistics_query.push_bind(summoner_id);
         participants_query.push_bind(summoner_id);
-
-        if let Some(champion_id) = filters.champion_id {
-            let sql_filter = " AND lmp.champion_id = ";
-            statistics_query.push(sql_filter);
-            statistics_query.push_bind(champion_id as i32);
-            participants_query.push(sql_filter);
-            participants_query.push_bind(champion_id as i32);
-        }
-        if let Some(queue_id) = filters.queue_id {
-            let sql_filter = " AND lm.queue_id = ";
-            let queue = Queue::from(queue_id).to_u16() as i32;
-            statistics_query.push(sql_filter);
-            statistics_query.push_bind(queue);
-            participants_query.push(sql_filter);
-            participants_query.push_bind(queue);
-        }
-
-        if let Some(start_date) = start_date {
-            let sql_filter = " AND lm.match_end >= ";
-            statistics_query.push(sql_filter);
-            statistics_query.push_bind(start_date);
-            participants_query.push(sql_filter);
-            participants_query.push_bind(start_date);
-        }
-        if let Some(end_date) = end_date {
-            let sql_filter = " AND lm.match_end <= ";
-            statistics_query.push(sql_filter);
-            statistics_query.push_bind(end_date);
-            participants_query.push(sql_filter);
-            participants_query.push_bind(end_date);
+        filters.push_sql_filters(&mut participants_query, "lmp");
+        let offset = match cursor.filter(|cursor| cursor.page as i32 <= page) {
+            Some(cursor) => {
+                participants_query.push(" AND (lm.match_end, lm.id) < (");
+                participants_query.push_bind(cursor.match_end_to_naive());
+                participants_query.push(", ");
+                participants_query.push_bind(cursor.lol_match_id);
+                participants_query.push(")");
+                (page - cursor.page as i32) * per_page
+            }
+            None => (page - 1) * per_page,
+        };
+        participants_query.push(" ORDER BY lm.match_end DESC, lm.id DESC LIMIT ");
+        participants_query.push_bind(per_page);
+        if offset > 0 {
+            participants_query.push(" OFFSET ");
+            participants_query.push_bind(offset);
         }
 
-        participants_query.push(" ORDER BY lm.match_end DESC LIMIT 20 OFFSET ");
-        participants_query.push_bind(offset);
-        let (matches_statistics, matches_participants) = tokio::join!(
-            statistics_query
-                .build_query_as::<MatchesResultInfoModel>()
-                .fetch_one(db),
-            participants_query
-                .build_query_as::<SummonerMatchModel>()
-                .fetch_all(db),
-        );
-        let matches_statistics = matches_statistics?;
-        let matches_participants = matches_participants?;
-        let total_matches = matches_statistics.total_matches.unwrap_or_default() as u16;
-        let total_pages = (total_matches as f32 / per_page as f32).ceil() as u16;
-
-        let matches_result_info = {
-            MatchesResultInfo {
-                total_matches,
-                total_wins: matches_statistics.total_wins.unwrap_or_default() as u16,
-                avg_kills: matches_statistics
-                    .avg_kills
-                    .unwrap_or_default()
-                    .to_f32()
-                    .unwrap_or_default(),
-                avg_deaths: matches_statistics
-                    .avg_deaths
-                    .unwrap_or_default()
-                    .to_f32()
-                    .unwrap_or_default(),
-                avg_assists: matches_statistics
-                    .avg_assists
-                    .unwrap_or_default()
-                    .to_f32()
-                    .unwrap_or_default(),
-                avg_kill_participation: (matches_statistics
-                    .avg_kill_participation
-                    .unwrap_or_default()
-                    .to_f32()
-                    .unwrap_or_default()
-                    * 100.0)
-                    .round() as u16,
+        let cached_result_info = stats_cache.get(summoner_id, &filters);
+        let (matches_result_info, matches_participants) = match cached_result_info {
+            Some(matches_result_info) => (
+                matches_result_info,
+                participants_query
+                    .build_query_as::<SummonerMatchModel>()
+                    .fetch_all(db)
+                    .await?,
+            ),
+            None => {
+                statistics_query.push_bind(summoner_id);
+                filters.push_sql_filters(&mut statistics_query, "lmp");
+                let (matches_statistics, matches_participants) = tokio::join!(
+                    statistics_query
+                        .build_query_as::<MatchesResultInfoModel>()
+                        .fetch_one(db),
+                    participants_query
+                        .build_query_as::<SummonerMatchModel>()
+                        .fetch_all(db),
+                );
+                let matches_result_info = matches_result_info_from_model(matches_statistics?);
+                stats_cache.set(summoner_id, filters, matches_result_info.clone());
+                (matches_result_info, matches_participants?)
             }
         };
+        let total_pages =
+            (matches_result_info.total_matches as f32 / per_page as f32).ceil() as u16;
+        let next_cursor = (matches_participants.len() == per_page as usize)
+            .then(|| matches_participants.last())
+            .flatten()
+            .and_then(|row| {
+                row.lol_match_match_end.map(|match_end| {
+                    MatchesCursor::new(match_end, row.lol_match_id, page as u16 + 1)
+                })
+            });
 
         let matches_ids: Vec<_> = matches_participants
             .iter()
@@ -220,6 +227,14 @@ pub mod ssr {
                     item4_id: row.item4_id.unwrap_or_default() as u32,
                     item5_id: row.item5_id.unwrap_or_default() as u32,
                     item6_id: row.item6_id.unwrap_or_default() as u32,
+                    player_subteam_id: row.player_subteam_id.unwrap_or_default() as u16,
+                    subteam_placement: row.subteam_placement.unwrap_or_default() as u16,
+                    augment1_id: row.augment1_id.unwrap_or_default() as u16,
+                    augment2_id: row.augment2_id.unwrap_or_default() as u16,
+                    augment3_id: row.augment3_id.unwrap_or_default() as u16,
+                    augment4_id: row.augment4_id.unwrap_or_default() as u16,
+                    augment5_id: row.augment5_id.unwrap_or_default() as u16,
+                    augment6_id: row.augment6_id.unwrap_or_default() as u16,
                     participants: vec![],
                 }
             })
@@ -233,10 +248,12 @@ pub mod ssr {
                         lmp.lol_match_id,
                         lmp.summoner_id,
                         lmp.champion_id,
-                        lmp.team_id
+                        lmp.team_id,
+                        lmp.player_subteam_id,
+                        lmp.subteam_placement
                 FROM lol_match_participants as lmp
                 WHERE lmp.lol_match_id = ANY($1)
-                ORDER BY lmp.team_id",
+                ORDER BY lmp.subteam_placement, lmp.player_subteam_id, lmp.team_id",
             )
             .bind(&matches_ids)
             .fetch_all(db)
@@ -258,6 +275,8 @@ pub mod ssr {
                     let encounter_count = *encounter_counts.get(&row.summoner_id).unwrap_or(&0);
                     SummonerMatchParticipant {
                         team_id: row.team_id as u16,
+                        player_subteam_id: row.player_subteam_id.unwrap_or_default() as u16,
+                        subteam_placement: row.subteam_placement.unwrap_or_default() as u16,
                         lol_match_id: row.lol_match_id,
                         summoner_id: row.summoner_id,
                         champion_id: row.champion_id as u16,
@@ -290,9 +309,41 @@ pub mod ssr {
             matches,
             total_pages,
             matches_result_info,
+            next_cursor,
         })
     }
 
+    fn matches_result_info_from_model(
+        matches_statistics: MatchesResultInfoModel,
+    ) -> MatchesResultInfo {
+        MatchesResultInfo {
+            total_matches: matches_statistics.total_matches.unwrap_or_default() as u16,
+            total_wins: matches_statistics.total_wins.unwrap_or_default() as u16,
+            avg_kills: matches_statistics
+                .avg_kills
+                .unwrap_or_default()
+                .to_f32()
+                .unwrap_or_default(),
+            avg_deaths: matches_statistics
+                .avg_deaths
+                .unwrap_or_default()
+                .to_f32()
+                .unwrap_or_default(),
+            avg_assists: matches_statistics
+                .avg_assists
+                .unwrap_or_default()
+                .to_f32()
+                .unwrap_or_default(),
+            avg_kill_participation: (matches_statistics
+                .avg_kill_participation
+                .unwrap_or_default()
+                .to_f32()
+                .unwrap_or_default()
+                * 100.0)
+                .round() as u16,
+        }
+    }
+
     pub async fn get_summoner_encounters(
         db: &PgPool,
         summoner_id: i32,
@@ -386,6 +437,14 @@ pub mod ssr {
         pub item4_id: Option<i64>,
         pub item5_id: Option<i64>,
         pub item6_id: Option<i64>,
+        pub player_subteam_id: Option<i32>,
+        pub subteam_placement: Option<i32>,
+        pub augment1_id: Option<i32>,
+        pub augment2_id: Option<i32>,
+        pub augment3_id: Option<i32>,
+        pub augment4_id: Option<i32>,
+        pub augment5_id: Option<i32>,
+        pub augment6_id: Option<i32>,
         pub lol_match_queue_id: Option<i32>,
         pub lol_match_match_end: Option<NaiveDateTime>,
         pub lol_match_match_duration: Option<i32>,
@@ -394,6 +453,8 @@ pub mod ssr {
     #[derive(FromRow)]
     pub struct SummonerMatchParticipantModel {
         pub team_id: i32,
+        pub player_subteam_id: Option<i32>,
+        pub subteam_placement: Option<i32>,
         pub lol_match_id: i32,
         pub summoner_id: i32,
         pub champion_id: i32,
@@ -0,0 +1,194 @@
+use crate::views::multisearch_page::MultiSearchPlayer;
+use common::consts::platform_route::PlatformRoute;
+use leptos::prelude::*;
+use leptos::server;
+use leptos::server_fn::codec::Bitcode;
+
+#[server(input=Bitcode,output=Bitcode)]
+pub async fn get_multisearch(
+    platform_route: PlatformRoute,
+    riot_ids: Vec<(String, String)>,
+    current_riot_id: Option<(String, String)>,
+) -> Result<Vec<MultiSearchPlayer>, ServerFnError> {
+    let state = expect_context::<crate::ssr::AppState>();
+    let db = state.db.clone();
+
+    ssr::inner_get_multisearch(
+        &db,
+        &state.riot_api,
+        platform_route,
+        riot_ids,
+        current_riot_id,
+    )
+    .await
+    .map_err(|e| e.to_server_fn_error())
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use crate::backend::server_fns::get_champions::ssr::inner_get_champions;
+    use crate::backend::server_fns::search_summoner::ssr::resolve_summoner;
+    use crate::backend::ssr::AppResult;
+    use crate::utils::{ProPlayerSlug, MULTISEARCH_MAX_PLAYERS};
+    use crate::views::multisearch_page::{
+        MultiSearchEncounter, MultiSearchPlayer, MultiSearchSummoner,
+    };
+    use crate::views::BackEndMatchFiltersSearch;
+    use common::consts::platform_route::PlatformRoute;
+    use futures::future::join_all;
+    use riven::RiotApi;
+    use sqlx::{FromRow, PgPool};
+
+    pub const MULTISEARCH_RECENT_MATCHES: i64 = 20;
+    pub const MULTISEARCH_TOP_CHAMPIONS: usize = 3;
+
+    pub async fn inner_get_multisearch(
+        db: &PgPool,
+        riot_api: &RiotApi,
+        platform_route: PlatformRoute,
+        riot_ids: Vec<(String, String)>,
+        current_riot_id: Option<(String, String)>,
+    ) -> AppResult<Vec<MultiSearchPlayer>> {
+        let current_summoner_id = match current_riot_id {
+            Some((game_name, tag_line)) => {
+                resolve_summoner(db, riot_api, platform_route, &game_name, &tag_line)
+                    .await?
+                    .map(|summoner| summoner.id)
+            }
+            None => None,
+        };
+        join_all(
+            riot_ids
+                .into_iter()
+                .take(MULTISEARCH_MAX_PLAYERS)
+                .map(|(game_name, tag_line)| {
+                    get_multisearch_player(
+                        db,
+                        riot_api,
+                        platform_route,
+                        game_name,
+                        tag_line,
+                        current_summoner_id,
+                    )
+                }),
+        )
+        .await
+        .into_iter()
+        .collect()
+    }
+
+    async fn get_multisearch_player(
+        db: &PgPool,
+        riot_api: &RiotApi,
+        platform_route: PlatformRoute,
+        game_name: String,
+        tag_line: String,
+        current_summoner_id: Option<i32>,
+    ) -> AppResult<MultiSearchPlayer> {
+        let Some(summoner) =
+            resolve_summoner(db, riot_api, platform_route, &game_name, &tag_line).await?
+        else {
+            return Ok(MultiSearchPlayer {
+                game_name,
+                tag_line,
+                summoner: None,
+            });
+        };
+        let profile = sqlx::query_as::<_, MultiSearchProfileModel>(
+            r#"
+            SELECT profile_icon_id, summoner_level, pro_player_slug
+            FROM summoners
+            WHERE id = $1
+            "#,
+        )
+        .bind(summoner.id)
+        .fetch_one(db)
+        .await?;
+        let (recent_matches, recent_wins) = sqlx::query_as::<_, (i64, i64)>(
+            r#"
+            SELECT count(*), coalesce(sum(CASE WHEN recent.won THEN 1 ELSE 0 END), 0)
+            FROM (SELECT lmp.won
+                  FROM lol_match_participants as lmp
+                           INNER JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+                  WHERE lmp.summoner_id = $1
+                  ORDER BY lm.match_end DESC
+                  LIMIT $2) as recent
+            "#,
+        )
+        .bind(summoner.id)
+        .bind(MULTISEARCH_RECENT_MATCHES)
+        .fetch_one(db)
+        .await?;
+        let mut top_champions =
+            inner_get_champions(db, summoner.id, BackEndMatchFiltersSearch::default()).await?;
+        top_champions.truncate(MULTISEARCH_TOP_CHAMPIONS);
+        let encounter = match current_summoner_id.filter(|id| *id != summoner.id) {
+            Some(current_summoner_id) => {
+                get_encounter_counts(db, summoner.id, current_summoner_id).await?
+            }
+            None => None,
+        };
+        Ok(MultiSearchPlayer {
+            game_name: summoner.game_name,
+            tag_line: summoner.tag_line,
+            summoner: Some(MultiSearchSummoner {
+                id: summoner.id,
+                platform: PlatformRoute::from(summoner.platform),
+                profile_icon_id: profile.profile_icon_id as u16,
+                summoner_level: profile.summoner_level as u16,
+                pro_slug: profile
+                    .pro_player_slug
+                    .map(|pps| ProPlayerSlug::new(pps.as_str())),
+                recent_matches: recent_matches as u16,
+                recent_wins: recent_wins as u16,
+                top_champions,
+                encounter,
+            }),
+        })
+    }
+
+    /// Same counts as the encounters tab, seen from `current_summoner_id`.
+    async fn get_encounter_counts(
+        db: &PgPool,
+        summoner_id: i32,
+        current_summoner_id: i32,
+    ) -> AppResult<Option<MultiSearchEncounter>> {
+        let encounter = sqlx::query_as::<_, MultiSearchEncounterModel>(
+            r#"
+            SELECT COUNT(*)                                                                 AS match_count,
+                   COUNT(*) FILTER (WHERE lmp.team_id = tm.team_id)                         AS with_match_count,
+                   SUM(CASE WHEN lmp.team_id = tm.team_id AND tm.won THEN 1 ELSE 0 END)    AS with_win_count,
+                   SUM(CASE WHEN lmp.team_id != tm.team_id AND tm.won THEN 1 ELSE 0 END)   AS vs_win_count
+            FROM lol_match_participants lmp
+                     JOIN lol_match_participants tm
+                          ON lmp.lol_match_id = tm.lol_match_id AND tm.summoner_id = $2
+            WHERE lmp.summoner_id = $1
+            "#,
+        )
+        .bind(summoner_id)
+        .bind(current_summoner_id)
+        .fetch_one(db)
+        .await?;
+        Ok((encounter.match_count > 0).then(|| MultiSearchEncounter {
+            match_count: encounter.match_count as u16,
+            with_match_count: encounter.with_match_count as u16,
+            with_win_count: encounter.with_win_count.unwrap_or_default() as u16,
+            vs_win_count: encounter.vs_win_count.unwrap_or_default() as u16,
+        }))
+    }
+
+    #[derive(FromRow)]
+    struct MultiSearchProfileModel {
+        pub profile_icon_id: i32,
+        pub summoner_level: i32,
+        pub pro_player_slug: Option<String>,
+    }
+
+    #[derive(FromRow)]
+    struct MultiSearchEncounterModel {
+        pub match_count: i64,
+        pub with_match_count: i64,
+        pub with_win_count: Option<i64>,
+        pub vs_win_count: Option<i64>,
+    }
+}
@@ -12,19 +12,31 @@ pub async fn get_summoner(
     summoner_slug: String,
 ) -> Result<Summoner, ServerFnError> {
     //log!("Server::Fetching summoner: {}", summoner_slug);
+    use crate::backend::server_fn_cache::ServerFnCacheKey;
+
     let state = expect_context::<crate::ssr::AppState>();
     let db = state.db.clone();
 
     let (game_name, tag_line) = parse_summoner_slug(summoner_slug.as_ref());
-    match ssr::find_summoner_by_exact_game_name_tag_line(
-        &db,
-        platform_route,
-        game_name.clone(),
-        tag_line.clone(),
-    )
-    .await
-    {
-        Ok(Some(summoner)) => Ok(summoner),
+    let cache_key = ServerFnCacheKey::new("get_summoner", &(platform_route, summoner_slug));
+    let summoner = state
+        .server_fn_cache
+        .get_or_fetch_resolved(
+            cache_key,
+            ssr::find_summoner_by_exact_game_name_tag_line(
+                &db,
+                platform_route,
+                game_name.clone(),
+                tag_line.clone(),
+            ),
+            |summoner: &Summoner| summoner.id,
+        )
+        .await;
+    match summoner {
+        Ok(Some(summoner)) => {
+            *state.summoner_page_views.entry(summoner.id).or_default() += 1;
+            Ok(summoner)
+        }
         _ => {
             leptos_axum::redirect(
                 summoner_not_found_url(
@@ -41,9 +53,10 @@ pub async fn get_summoner(
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
-    use crate::backend::ssr::{AppResult, PlatformRouteDb};
+    use crate::backend::ssr::{format_duration_since, AppResult, PlatformRouteDb};
     use crate::utils::ProPlayerSlug;
     use crate::views::summoner_page::Summoner;
+    use chrono::NaiveDateTime;
     use common::consts::platform_route::PlatformRoute;
 
     pub async fn find_summoner_by_exact_game_name_tag_line(
@@ -61,7 +74,8 @@ pub mod ssr {
                ss.platform        as platform,
                ss.profile_icon_id as profile_icon_id,
                ss.summoner_level  as summoner_level,
-               ss.pro_player_slug as pro_slug
+               ss.pro_player_slug as pro_slug,
+               ss.matches_updated_at as matches_updated_at
             FROM summoners as ss
             WHERE ss.game_name = $1
               AND lower(ss.tag_line) = lower($2)
@@ -82,6 +96,7 @@ pub mod ssr {
                 summoner_level: summoner_db.summoner_level as u16,
                 profile_icon_id: summoner_db.profile_icon_id as u16,
                 pro_slug: summoner_db.pro_slug.map(|s| ProPlayerSlug::new(s.as_str())),
+                updated_since: summoner_db.matches_updated_at.map(format_duration_since),
             })
         })
         .map_err(|e| e.into())
@@ -96,5 +111,6 @@ pub mod ssr {
         pub summoner_level: i32,
         pub profile_icon_id: i32,
         pub pro_slug: Option<String>,
+        pub matches_updated_at: Option<NaiveDateTime>,
     }
 }
@@ -0,0 +1,37 @@
+#[cfg(feature = "ssr")]
+use crate::backend::server_fns::get_encounter::ssr::find_summoner_puuid_by_id;
+#[cfg(feature = "ssr")]
+use crate::backend::server_fns::get_live_game::ssr::get_live_game_data;
+use crate::utils::LiveScoutProgress;
+#[cfg(feature = "ssr")]
+use crate::utils::Puuid;
+use common::consts::platform_route::PlatformRoute;
+use leptos::prelude::*;
+use leptos::server;
+use leptos::server_fn::codec::Bitcode;
+
+/// Starts fetching the history of every participant of the summoner live game, the participants are
+/// queued by the refresh summoners task and the progress is streamed as live scout sse events.
+/// Returns the progress of the running scout when the game is already scouted.
+#[server(input=Bitcode,output=Bitcode)]
+pub async fn scout_live_game(
+    summoner_id: i32,
+    platform_route: PlatformRoute,
+) -> Result<LiveScoutProgress, ServerFnError> {
+    let state = expect_context::<crate::ssr::AppState>();
+    let live_cache = state.live_game_cache.clone();
+    if let Some(progress) = live_cache.start_scout(summoner_id) {
+        return Ok(progress);
+    }
+    // the game was read through another instance
+    let db = state.db.clone();
+    let puuid = Puuid::new(find_summoner_puuid_by_id(&db, summoner_id).await?.as_str());
+    if let Some((summoner_ids, live_game)) =
+        get_live_game_data(&db, &state.riot_api, &state.health, puuid, platform_route).await?
+    {
+        live_cache.set_game_data(live_game.game_id, summoner_ids, live_game);
+    }
+    live_cache
+        .start_scout(summoner_id)
+        .ok_or_else(|| ServerFnError::new("Not in live game"))
+}
@@ -1,5 +1,6 @@
 #[cfg(feature = "ssr")]
 use crate::utils::{summoner_not_found_url, summoner_url};
+use crate::views::summoner_page::summoner_search_page::SummonerSuggestion;
 use common::consts::platform_route::PlatformRoute;
 use leptos::prelude::*;
 use leptos::server;
@@ -14,16 +15,16 @@ pub async fn search_summoner(
     let state = expect_context::<crate::ssr::AppState>();
     let db = state.db.clone();
 
-    let riven_pr = platform_route.to_riven();
-    match ssr::find_summoner_by_game_name_tag_line(
+    match ssr::resolve_summoner(
         &db,
-        &platform_route,
+        &state.riot_api,
+        platform_route,
         game_name.as_ref(),
         tag_line.as_ref(),
     )
-    .await
+    .await?
     {
-        Ok(summoner) => {
+        Some(summoner) => {
             leptos_axum::redirect(
                 summoner_url(
                     platform_route.as_ref(),
@@ -33,71 +34,120 @@ pub async fn search_summoner(
                 .as_str(),
             );
         }
-        Err(_) => {
-            let not_found_url = summoner_not_found_url(
-                platform_route.as_ref(),
-                game_name.as_ref(),
-                tag_line.as_ref(),
-            );
-            let riot_api = state.riot_api.clone();
-            match riot_api
-                .account_v1()
-                .get_by_riot_id(
-                    riven_pr.to_regional(),
+        None => {
+            leptos_axum::redirect(
+                summoner_not_found_url(
+                    platform_route.as_ref(),
                     game_name.as_ref(),
                     tag_line.as_ref(),
                 )
-                .await
-            {
-                Ok(Some(account)) => {
-                    match riot_api
-                        .summoner_v4()
-                        .get_by_puuid(riven_pr, account.puuid.as_str())
-                        .await
-                    {
-                        Ok(summoner_data) => {
-                            let redirect_url = summoner_url(
-                                platform_route.as_ref(),
-                                account
-                                    .game_name
-                                    .clone()
-                                    .expect("search summoner: account game name not found")
-                                    .as_str(),
-                                account
-                                    .tag_line
-                                    .clone()
-                                    .expect("search summoner: account tag line not found")
-                                    .as_str(),
-                            );
-                            ssr::insert_or_update_account_and_summoner(
-                                &db,
-                                platform_route,
-                                account,
-                                summoner_data,
-                            )
-                            .await?;
-                            // Generate slug for URL
-                            leptos_axum::redirect(redirect_url.as_str());
-                        }
-                        _ => {
-                            leptos_axum::redirect(not_found_url.as_str());
-                        }
-                    }
-                }
-                _ => {
-                    leptos_axum::redirect(not_found_url.as_str());
-                }
-            }
+                .as_str(),
+            );
         }
     }
     Ok(())
 }
 
+/// Searches the riot id on every platform of the `platform_route` region. Redirects when a single
+/// platform matches, otherwise returns the matching summoners so the user can choose.
+#[server(input=Bitcode,output=Bitcode)]
+pub async fn search_summoner_any_platform(
+    platform_route: PlatformRoute,
+    game_name: String,
+    tag_line: String,
+) -> Result<Vec<SummonerSuggestion>, ServerFnError> {
+    let state = expect_context::<crate::ssr::AppState>();
+    let db = state.db.clone();
+
+    let candidates = ssr::resolve_summoner_any_platform(
+        &db,
+        &state.riot_api,
+        platform_route,
+        game_name.as_ref(),
+        tag_line.as_ref(),
+    )
+    .await
+    .map_err(|e| e.to_server_fn_error())?;
+    match candidates.as_slice() {
+        [] => {
+            leptos_axum::redirect(
+                summoner_not_found_url(
+                    platform_route.as_ref(),
+                    game_name.as_ref(),
+                    tag_line.as_ref(),
+                )
+                .as_str(),
+            );
+        }
+        [candidate] => {
+            leptos_axum::redirect(
+                summoner_url(
+                    candidate.platform.as_ref(),
+                    candidate.game_name.as_str(),
+                    candidate.tag_line.as_str(),
+                )
+                .as_str(),
+            );
+        }
+        _ => {}
+    }
+    Ok(candidates)
+}
+
 #[cfg(feature = "ssr")]
 pub mod ssr {
     use crate::backend::ssr::{AppError, AppResult, Id, PlatformRouteDb};
-    use chrono::Utc;
-    use common::consts::platform_route::PlatformRoute;
+    use crate::views::summoner_page::summoner_search_page::SummonerSuggestion;
+    use common::consts::platform_route::{PlatformRoute, PLATFORM_ROUTE_OPTIONS};
+    use futures::future::join_all;
+    use itertools::Itertools;
+    use riven::RiotApi;
+
+    /// Finds the summoner in the database, falling back to the riot api (account_v1 then summoner_v4).
+    /// Summoners fetched from the api are inserted or updated. Returns `None` when the riot id does not exist.
+    pub async fn resolve_summoner(
+        db: &sqlx::PgPool,
+        riot_api: &RiotApi,
+        platform_route: PlatformRoute,
+        game_name: &str,
+        tag_line: &str,
+    ) -> AppResult<Option<SummonerDb>> {
+        if let Ok(summoner) =
+            find_summoner_by_game_name_tag_line(db, &platform_route, game_name, tag_line).await
+        {
+            return Ok(Some(summoner));
+        }
+        let riven_pr = platform_route.to_riven();
+        let Ok(Some(account)) = riot_api
+            .account_v1()
+            .get_by_riot_id(riven_pr.to_regional(), game_name, tag_line)
+            .await
+        else {
+            return Ok(None);
+        };
+        let Ok(summoner_data) = riot_api
+            .summoner_v4()
+            .get_by_puuid(riven_pr, account.puuid.as_str())
+            .await
+        else {
+            return Ok(None);
+        };
+        let (Some(game_name), Some(tag_line)) =
+            (account.game_name.clone(), account.tag_line.clone())
+        else {
+            return Err(AppError::CustomError(
+                "search summoner: account riot id not found".to_string(),
+            ));
+        };
+        let id = insert_or_update_account_and_summoner(db, platform_route, account, summoner_data)
+            .await?;
+        Ok(Some(SummonerDb {
+            id,
+            game_name,
+            tag_line,
+            platform: PlatformRouteDb::from(platform_route),
+        }))
+    }
 
     pub async fn find_summoner_by_game_name_tag_line(
         db: &sqlx::PgPool,
@@ -122,6 +172,60 @@ pub mod ssr {
         .map_err(|e| e.into())
     }
 
+    /// Accounts and puuids are global, so the account is resolved once and `summoner_v4` is
+    /// probed on the platforms of `PLATFORM_ROUTE_OPTIONS` sharing the `platform_route` region,
+    /// which bounds the riot api calls of an anonymous search.
+    /// Every summoner found is inserted or updated, highest level first in the result.
+    pub async fn resolve_summoner_any_platform(
+        db: &sqlx::PgPool,
+        riot_api: &RiotApi,
+        platform_route: PlatformRoute,
+        game_name: &str,
+        tag_line: &str,
+    ) -> AppResult<Vec<SummonerSuggestion>> {
+        let region = platform_route.to_riven().to_regional();
+        let Ok(Some(account)) = riot_api
+            .account_v1()
+            .get_by_riot_id(region, game_name, tag_line)
+            .await
+        else {
+            return Ok(vec![]);
+        };
+        let platforms = PLATFORM_ROUTE_OPTIONS
+            .iter()
+            .filter(|platform| platform.to_riven().to_regional() == region);
+        let summoners = join_all(platforms.map(|platform| {
+            let puuid = account.puuid.as_str();
+            async move {
+                riot_api
+                    .summoner_v4()
+                    .get_by_puuid(platform.to_riven(), puuid)
+                    .await
+                    .ok()
+                    .map(|summoner| (*platform, summoner))
+            }
+        }))
+        .await
+        .into_iter()
+        .flatten()
+        .collect_vec();
+
+        let mut candidates = Vec::with_capacity(summoners.len());
+        for (platform, summoner) in summoners {
+            candidates.push(SummonerSuggestion {
+                game_name: account.game_name.clone().unwrap_or_default(),
+                tag_line: account.tag_line.clone().unwrap_or_default(),
+                platform,
+                profile_icon_id: summoner.profile_icon_id as u16,
+                summoner_level: summoner.summoner_level as u16,
+                pro_slug: None,
+            });
+            insert_or_update_account_and_summoner(db, platform, account.clone(), summoner).await?;
+        }
+        candidates.sort_by(|a, b| b.summoner_level.cmp(&a.summoner_level));
+        Ok(candidates)
+    }
+
     #[derive(sqlx::FromRow)]
     pub struct SummonerDb {
         pub id: i32,
@@ -135,7 +239,7 @@ pub mod ssr {
         platform_route: PlatformRoute,
         account: riven::models::account_v1::Account,
         summoner: riven::models::summoner_v4::Summoner,
-    ) -> AppResult<()> {
+    ) -> AppResult<i32> {
         match find_summoner_id_by_puuid(db, platform_route, &summoner.puuid).await {
             Ok(id) => update_summoner_by_id(db, id, platform_route, account, summoner).await,
             Err(_) => insert_summoner(db, platform_route, account, summoner).await,
@@ -162,9 +266,9 @@ pub mod ssr {
         platform_route: PlatformRoute,
         account: riven::models::account_v1::Account,
         summoner: riven::models::summoner_v4::Summoner,
-    ) -> AppResult<()> {
+    ) -> AppResult<i32> {
         sqlx::query(
-            "UPDATE summoners SET game_name = $1, tag_line = $2, puuid = $3, summoner_level = $4, profile_icon_id = $5, platform = $6, updated_at = NOW() WHERE id = $8"
+            "UPDATE summoners SET game_name = $1, tag_line = $2, puuid = $3, summoner_level = $4, profile_icon_id = $5, platform = $6, updated_at = NOW(), profile_updated_at = NOW() WHERE id = $7"
         )
             .bind(account.game_name.unwrap_or_default().trim())
             .bind(account.tag_line.unwrap_or_default().trim())
@@ -172,11 +276,10 @@ pub mod ssr {
             .bind(summoner.summoner_level as i32)
             .bind(summoner.profile_icon_id)
             .bind(PlatformRouteDb::from(platform_route))
-            .bind(Utc::now().naive_utc())
             .bind(id)
             .execute(db)
             .await?;
-        Ok(())
+        Ok(id)
     }
 
     async fn insert_summoner(
@@ -184,9 +287,9 @@ pub mod ssr {
         platform_route: PlatformRoute,
         account: riven::models::account_v1::Account,
         summoner: riven::models::summoner_v4::Summoner,
-    ) -> AppResult<()> {
-        sqlx::query(
-            "INSERT INTO summoners(game_name, tag_line, puuid, platform, summoner_level, profile_icon_id, updated_at) VALUES ($1, $2, $3, $4, $5, $6, NOW())"
+    ) -> AppResult<i32> {
+        sqlx::query_as::<_, Id>(
+            "INSERT INTO summoners(game_name, tag_line, puuid, platform, summoner_level, profile_icon_id, updated_at, profile_updated_at) VALUES ($1, $2, $3, $4, $5, $6, NOW(), NOW()) RETURNING id"
         )
             .bind(account.game_name.unwrap_or_default().trim())
             .bind(account.tag_line.unwrap_or_default().trim())
@@ -194,9 +297,9 @@ pub mod ssr {
             .bind(PlatformRouteDb::from(platform_route))
             .bind(summoner.summoner_level as i32)
             .bind(summoner.profile_icon_id)
-            .bind(Utc::now().naive_utc())
-            .execute(db)
-            .await?;
-        Ok(())
+            .fetch_one(db)
+            .await
+            .map(|x| x.id)
+            .map_err(AppError::from)
     }
 }
@@ -0,0 +1,107 @@
+use crate::views::summoner_page::summoner_search_page::SummonerSuggestion;
+use common::consts::platform_route::PlatformRoute;
+use leptos::prelude::*;
+use leptos::server;
+use leptos::server_fn::codec::Bitcode;
+
+#[server(input=Bitcode,output=Bitcode)]
+pub async fn suggest_summoners(
+    platform_route: PlatformRoute,
+    query: String,
+) -> Result<Vec<SummonerSuggestion>, ServerFnError> {
+    let state = expect_context::<crate::ssr::AppState>();
+    let db = state.db.clone();
+
+    ssr::find_summoner_suggestions(&db, platform_route, query.as_str())
+        .await
+        .map_err(|e| e.to_server_fn_error())
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use crate::backend::ssr::{AppResult, PlatformRouteDb};
+    use crate::utils::ProPlayerSlug;
+    use crate::views::summoner_page::summoner_search_page::{
+        SummonerSuggestion, SUGGESTIONS_MIN_QUERY_LEN,
+    };
+    use common::consts::platform_route::PlatformRoute;
+    use itertools::Itertools;
+
+    pub const SUGGESTIONS_LIMIT: i64 = 8;
+
+    /// Fuzzy search on `game_name` backed by `idx_summoners_game_name_trgm`.
+    /// A `#` in the query restricts the results to tag lines starting with what follows it.
+    /// Prefix matches rank first, then trigram similarity, summoners of `platform_route` being preferred.
+    pub async fn find_summoner_suggestions(
+        db: &sqlx::PgPool,
+        platform_route: PlatformRoute,
+        query: &str,
+    ) -> AppResult<Vec<SummonerSuggestion>> {
+        let (game_name, tag_line) = match query.split_once('#') {
+            Some((game_name, tag_line)) => (game_name.trim(), Some(tag_line.trim())),
+            None => (query.trim(), None),
+        };
+        if game_name.chars().count() < SUGGESTIONS_MIN_QUERY_LEN {
+            return Ok(vec![]);
+        }
+        let game_name_prefix = format!("{}%", escape_like(game_name));
+        let tag_line_prefix = tag_line
+            .filter(|tag_line| !tag_line.is_empty())
+            .map(|tag_line| format!("{}%", escape_like(tag_line)));
+
+        Ok(sqlx::query_as::<_, SummonerSuggestionModel>(
+            r#"
+            SELECT ss.game_name,
+                   ss.tag_line,
+                   ss.platform,
+                   ss.profile_icon_id,
+                   ss.summoner_level,
+                   ss.pro_player_slug
+            FROM summoners as ss
+            WHERE (ss.game_name % $1 OR ss.game_name ILIKE $2)
+              AND ($3::TEXT IS NULL OR ss.tag_line ILIKE $3)
+            ORDER BY ss.game_name ILIKE $2 DESC,
+                     similarity(ss.game_name, $1) DESC,
+                     ss.platform = $4 DESC,
+                     ss.summoner_level DESC
+            LIMIT $5
+            "#,
+        )
+        .bind(game_name)
+        .bind(game_name_prefix)
+        .bind(tag_line_prefix)
+        .bind(PlatformRouteDb::from(platform_route))
+        .bind(SUGGESTIONS_LIMIT)
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|suggestion| SummonerSuggestion {
+            game_name: suggestion.game_name,
+            tag_line: suggestion.tag_line,
+            platform: PlatformRoute::from(suggestion.platform),
+            profile_icon_id: suggestion.profile_icon_id as u16,
+            summoner_level: suggestion.summoner_level as u16,
+            pro_slug: suggestion
+                .pro_player_slug
+                .map(|pps| ProPlayerSlug::new(pps.as_str())),
+        })
+        .collect_vec())
+    }
+
+    fn escape_like(value: &str) -> String {
+        value
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    }
+
+    #[derive(sqlx::FromRow)]
+    struct SummonerSuggestionModel {
+        pub game_name: String,
+        pub tag_line: String,
+        pub platform: PlatformRouteDb,
+        pub profile_icon_id: i32,
+        pub summoner_level: i32,
+        pub pro_player_slug: Option<String>,
+    }
+}
@@ -1,9 +1,8 @@
 #[cfg(feature = "ssr")]
-use crate::backend::server_fns::get_encounter::ssr::find_summoner_puuid_by_id;
-#[cfg(feature = "ssr")]
 use crate::backend::server_fns::search_summoner::ssr::insert_or_update_account_and_summoner;
 #[cfg(feature = "ssr")]
 use crate::utils::summoner_url;
+use crate::utils::DurationSince;
 use common::consts::platform_route::PlatformRoute;
 #[cfg(feature = "ssr")]
 use leptos::logging::log;
@@ -13,32 +12,65 @@ use leptos::server_fn::codec::Bitcode;
 #[cfg(feature = "ssr")]
 use std::string::ToString;
 
+/// Returns `(summoner_level, profile_icon_id, updated_since)`, or `None` when the
+/// riot id changed and the client is redirected to the new summoner url.
+/// Within the update cooldown the stored values are returned without calling the riot api.
 #[server( input=Bitcode, output=Bitcode)]
 pub async fn update_summoner(
     summoner_id: i32,
     game_name: String,
     tag_line: String,
     platform_route: PlatformRoute,
-) -> Result<Option<(u16, u16)>, ServerFnError> {
+) -> Result<Option<(u16, u16, DurationSince)>, ServerFnError> {
+    use crate::backend::ssr::format_duration_since;
+    use crate::backend::tasks::update_matches::match_ingestion_queue::MatchJobPriority;
+
     let state = expect_context::<crate::ssr::AppState>();
     let riot_api = state.riot_api.clone();
     let max_matches = state.max_matches;
     let db = state.db.clone();
-    let puuid = find_summoner_puuid_by_id(&db, summoner_id).await?;
+    let claim =
+        match ssr::try_claim_summoner_update(&db, summoner_id, state.summoner_update_cooldown)
+            .await
+            .map_err(|e| e.to_server_fn_error())?
+        {
+            Some(claim) => claim,
+            None => {
+                let freshness = ssr::find_summoner_freshness(&db, summoner_id)
+                    .await
+                    .map_err(|e| e.to_server_fn_error())?;
+                return Ok(Some((
+                    freshness.summoner_level as u16,
+                    freshness.profile_icon_id as u16,
+                    freshness
+                        .matches_updated_at
+                        .map_or_else(|| DurationSince::new("just now"), format_duration_since),
+                )));
+            }
+        };
     let (account, summoner) = tokio::join!(
-        riot_api
-            .account_v1()
-            .get_by_puuid(platform_route.to_riven().to_regional(), puuid.as_str()),
+        riot_api.account_v1().get_by_puuid(
+            platform_route.to_riven().to_regional(),
+            claim.puuid.as_str()
+        ),
         riot_api
             .summoner_v4()
-            .get_by_puuid(platform_route.to_riven(), puuid.as_str())
+            .get_by_puuid(platform_route.to_riven(), claim.puuid.as_str())
     );
+    state
+        .health
+        .record_riot_api_call(account.is_ok() && summoner.is_ok());
     if let (Ok(account), Ok(summoner)) = (account, summoner) {
         let inner_db = db.clone();
+        let server_fn_cache = state.server_fn_cache.clone();
+        let health = state.health.clone();
         let puuid = summoner.puuid.clone();
+        let encrypted_summoner_id = summoner.id.clone();
+        let update_cooldown = state.summoner_update_cooldown;
         let lvl_profile_icon_id = (
             summoner.summoner_level as u16,
             summoner.profile_icon_id as u16,
+            DurationSince::new("just now"),
         );
         let acc_game_name = account.game_name.clone().unwrap_or_default();
         let acc_tag_line = account.tag_line.clone().unwrap_or_default();
@@ -46,23 +78,48 @@ pub async fn update_summoner(
             insert_or_update_account_and_summoner(&db, platform_route, account, summoner)
                 .await
                 .unwrap();
+            server_fn_cache.invalidate(summoner_id);
+            if let Err(e) = ssr::update_summoner_ranks_and_masteries(
+                &inner_db,
+                &riot_api,
+                &health,
+                summoner_id,
+                platform_route.to_riven(),
+                &encrypted_summoner_id,
+                &puuid,
+                update_cooldown,
+            )
+            .await
+            {
+                log!("Error updating summoner ranks and masteries: {}", e);
+            }
             match ssr::update_summoner_default_matches(
-                inner_db,
+                inner_db.clone(),
                 riot_api,
+                &health,
                 puuid,
                 platform_route.to_riven(),
                 max_matches,
+                claim.previous_matches_updated_at.is_some(),
+                MatchJobPriority::User,
+                None,
             )
             .await
             {
                 Ok(_) => {}
                 Err(e) => {
                     log!("Error updating summoner matches: {}", e);
+                    let _ = ssr::release_summoner_update(
+                        &inner_db,
+                        summoner_id,
+                        claim.previous_matches_updated_at,
+                    )
+                    .await;
                 }
             };
         });
-        let has_changed = game_name.as_str() != acc_game_name.trim()
-            || tag_line.as_str() != acc_tag_line.trim();
+        let has_changed =
+            game_name.as_str() != acc_game_name.trim() || tag_line.as_str() != acc_tag_line.trim();
         if has_changed {
             leptos_axum::redirect(
                 summoner_url(
@@ -77,70 +134,298 @@ pub async fn update_summoner(
             Ok(Some(lvl_profile_icon_id))
         }
     } else {
+        let _ =
+            ssr::release_summoner_update(&db, summoner_id, claim.previous_matches_updated_at).await;
         Err(ServerFnError::new("Summoner not found"))
     }
 }
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
-    use crate::backend::ssr::{AppResult, Id, PlatformRouteDb};
+    use crate::backend::health::HealthState;
+    use crate::backend::ssr::AppResult;
+    use crate::backend::tasks::update_matches::match_ingestion_queue::{
+        enqueue_match_ids, MatchJobPriority,
+    };
     use crate::ssr::RiotApiState;
+    use chrono::NaiveDateTime;
+    use itertools::Itertools;
     use leptos::logging::log;
     use riven::consts::RegionalRoute;
     use riven::RiotApi;
     use std::collections::HashSet;
+    use std::time::Duration;
+
+    /// Marks the summoner matches as being updated now, unless the last update is more recent
+    /// than `cooldown`. Returns `None` while the summoner is on cooldown.
+    pub async fn try_claim_summoner_update(
+        db: &sqlx::PgPool,
+        summoner_id: i32,
+        cooldown: Duration,
+    ) -> AppResult<Option<SummonerUpdateClaim>> {
+        sqlx::query_as::<_, SummonerUpdateClaim>(
+            r#"
+            UPDATE summoners as ss
+            SET matches_updated_at = NOW()
+            FROM (SELECT id, matches_updated_at FROM summoners WHERE id = $1 FOR UPDATE) as old
+            WHERE ss.id = old.id
+              AND (old.matches_updated_at IS NULL
+                OR old.matches_updated_at < NOW() - make_interval(secs => $2))
+            RETURNING ss.puuid, old.matches_updated_at as previous_matches_updated_at
+            "#,
+        )
+        .bind(summoner_id)
+        .bind(cooldown.as_secs_f64())
+        .fetch_optional(db)
+        .await
+        .map_err(|e| e.into())
+    }
+
+    /// Restores the previous matches freshness after a failed update, so the next update
+    /// is not blocked by the cooldown and does not stop early on partially fetched history.
+    pub async fn release_summoner_update(
+        db: &sqlx::PgPool,
+        summoner_id: i32,
+        previous_matches_updated_at: Option<NaiveDateTime>,
+    ) -> AppResult<()> {
+        sqlx::query("UPDATE summoners SET matches_updated_at = $1 WHERE id = $2")
+            .bind(previous_matches_updated_at)
+            .bind(summoner_id)
+            .execute(db)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn find_summoner_freshness(
+        db: &sqlx::PgPool,
+        summoner_id: i32,
+    ) -> AppResult<SummonerFreshness> {
+        sqlx::query_as::<_, SummonerFreshness>(
+            r#"
+            SELECT
+                ss.summoner_level,
+                ss.profile_icon_id,
+                ss.matches_updated_at
+            FROM summoners as ss
+            WHERE ss.id = $1"#,
+        )
+        .bind(summoner_id)
+        .fetch_one(db)
+        .await
+        .map_err(|e| e.into())
+    }
+
+    /// Replaces the league entries and the champion masteries of the summoner which are older
+    /// than `cooldown`, and marks them as updated now.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_summoner_ranks_and_masteries(
+        db: &sqlx::PgPool,
+        api: &RiotApi,
+        health: &HealthState,
+        summoner_id: i32,
+        platform: riven::consts::PlatformRoute,
+        encrypted_summoner_id: &str,
+        puuid: &str,
+        cooldown: Duration,
+    ) -> AppResult<()> {
+        let (ranks_outdated, mastery_outdated) = sqlx::query_as::<_, (bool, bool)>(
+            r#"
+            SELECT
+                ss.ranks_updated_at IS NULL OR ss.ranks_updated_at < NOW() - make_interval(secs => $2),
+                ss.mastery_updated_at IS NULL OR ss.mastery_updated_at < NOW() - make_interval(secs => $2)
+            FROM summoners as ss
+            WHERE ss.id = $1"#,
+        )
+        .bind(summoner_id)
+        .bind(cooldown.as_secs_f64())
+        .fetch_one(db)
+        .await?;
+
+        if ranks_outdated {
+            let entries = api
+                .league_v4()
+                .get_league_entries_for_summoner(platform, encrypted_summoner_id)
+                .await;
+            health.record_riot_api_call(entries.is_ok());
+            let (queue_types, tiers, divisions, league_points, wins, losses): (
+                Vec<String>,
+                Vec<Option<String>>,
+                Vec<Option<String>>,
+                Vec<i32>,
+                Vec<i32>,
+                Vec<i32>,
+            ) = entries?
+                .into_iter()
+                .map(|entry| {
+                    (
+                        entry.queue_type.to_string(),
+                        entry.tier.map(|tier| tier.to_string()),
+                        entry.rank.map(|division| division.to_string()),
+                        entry.league_points,
+                        entry.wins,
+                        entry.losses,
+                    )
+                })
+                .multiunzip();
+            let mut tx = db.begin().await?;
+            sqlx::query("DELETE FROM summoner_ranks WHERE summoner_id = $1")
+                .bind(summoner_id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query(
+                r#"
+                INSERT INTO summoner_ranks (summoner_id, queue_type, tier, division, league_points, wins, losses)
+                SELECT $1, * FROM UNNEST($2::VARCHAR(32)[], $3::VARCHAR(16)[], $4::VARCHAR(4)[], $5::INT[], $6::INT[], $7::INT[])
+                "#,
+            )
+            .bind(summoner_id)
+            .bind(queue_types)
+            .bind(tiers)
+            .bind(divisions)
+            .bind(league_points)
+            .bind(wins)
+            .bind(losses)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query("UPDATE summoners SET ranks_updated_at = NOW() WHERE id = $1")
+                .bind(summoner_id)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+        }
+
+        if mastery_outdated {
+            let masteries = api
+                .champion_mastery_v4()
+                .get_all_champion_masteries_by_puuid(platform, puuid)
+                .await;
+            health.record_riot_api_call(masteries.is_ok());
+            let (champion_ids, champion_levels, champion_points, last_play_times): (
+                Vec<i32>,
+                Vec<i32>,
+                Vec<i32>,
+                Vec<NaiveDateTime>,
+            ) = masteries?
+                .into_iter()
+                .map(|mastery| {
+                    (
+                        mastery.champion_id.0 as i32,
+                        mastery.champion_level,
+                        mastery.champion_points,
+                        chrono::DateTime::from_timestamp_millis(mastery.last_play_time)
+                            .unwrap_or_default()
+                            .naive_utc(),
+                    )
+                })
+                .multiunzip();
+            let mut tx = db.begin().await?;
+            sqlx::query("DELETE FROM summoner_champion_masteries WHERE summoner_id = $1")
+                .bind(summoner_id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query(
+                r#"
+                INSERT INTO summoner_champion_masteries (summoner_id, champion_id, champion_level, champion_points, last_play_time)
+                SELECT $1, * FROM UNNEST($2::INT[], $3::INT[], $4::INT[], $5::TIMESTAMP[])
+                "#,
+            )
+            .bind(summoner_id)
+            .bind(champion_ids)
+            .bind(champion_levels)
+            .bind(champion_points)
+            .bind(last_play_times)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query("UPDATE summoners SET mastery_updated_at = NOW() WHERE id = $1")
+                .bind(summoner_id)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+        }
+        Ok(())
+    }
 
+    /// Fetches the summoner match ids and queues the not yet ingested ones for the update matches task,
+    /// returns the queued ones. When `incremental` is set, pagination stops at the first match
+    /// id already stored. Each match-id request is taken from `request_budget`, pagination stops
+    /// once it is spent.
+    #[allow(clippy::too_many_arguments)]
     pub async fn update_summoner_default_matches(
         db: sqlx::PgPool,
         api: RiotApiState,
+        health: &HealthState,
         puuid: String,
         platform: riven::consts::PlatformRoute,
         max_matches: usize,
-    ) -> AppResult<()> {
-        let match_ids =
-            fetch_all_match_ids(&api, platform.to_regional(), &puuid, max_matches).await?;
+        incremental: bool,
+        priority: MatchJobPriority,
+        request_budget: Option<&mut usize>,
+    ) -> AppResult<Vec<String>> {
+        let match_ids = fetch_all_match_ids(
+            &db,
+            &api,
+            health,
+            platform.to_regional(),
+            &puuid,
+            max_matches,
+            incremental,
+            request_budget,
+        )
+        .await?;
 
-        // Fetch existing match IDs from the database
-        let existing_match_ids: HashSet<String> =
-            sqlx::query_scalar("SELECT match_id FROM lol_matches WHERE match_id = ANY($1)")
-                .bind(&match_ids)
-                .fetch_all(&db)
-                .await?
-                .into_iter()
-                .collect();
+        // Fetch already ingested match IDs from the database
+        let existing_match_ids: HashSet<String> = sqlx::query_scalar(
+            "SELECT match_id FROM lol_matches WHERE match_id = ANY($1) AND updated = true",
+        )
+        .bind(&match_ids)
+        .fetch_all(&db)
+        .await?
+        .into_iter()
+        .collect();
 
-        // Filter out matches that are already saved
+        // Filter out matches that are already saved, pending ones are requeued with `priority`
         let new_riot_match_ids: Vec<String> = match_ids
             .into_iter()
             .filter(|id| !existing_match_ids.contains(id))
             .collect();
 
         log!(
-            "New {} match ids for puuid {}",
+            "Queued {} match ids for puuid {}",
             new_riot_match_ids.len(),
             puuid
         );
         //let t = std::time::Instant::now();
         if !new_riot_match_ids.is_empty() {
-            bulk_insert_default_match(&db, &new_riot_match_ids).await
-        } else {
-            Ok(())
+            enqueue_match_ids(&db, &new_riot_match_ids, priority).await?;
         }
+        Ok(new_riot_match_ids)
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn fetch_all_match_ids(
+        db: &sqlx::PgPool,
         api: &RiotApi,
+        health: &HealthState,
         region: RegionalRoute,
         puuid: &str,
         max_matches: usize,
+        incremental: bool,
+        mut request_budget: Option<&mut usize>,
     ) -> AppResult<Vec<String>> {
         let max_fetch_limit = max_matches.min(100);
         let mut matches_list = Vec::new();
         let mut begin_index = 0;
 
         loop {
+            if let Some(request_budget) = request_budget.as_deref_mut() {
+                if *request_budget == 0 {
+                    break;
+                }
+                *request_budget -= 1;
+            }
             let fetched_matches = fetch_match_ids(
                 api,
+                health,
                 region,
                 puuid,
                 Some(max_fetch_limit as i32),
@@ -150,57 +435,74 @@ pub mod ssr {
             if fetched_matches.is_empty() {
                 break;
             }
+            // a partial page is the end of the history, no need to request the next one
+            let is_last_page = fetched_matches.len() < max_fetch_limit;
             begin_index += fetched_matches.len() as i32;
+            if incremental {
+                // match ids are sorted from newest to oldest, older ones are already known
+                if let Some(known_index) =
+                    find_first_known_match_index(db, &fetched_matches).await?
+                {
+                    matches_list.extend(fetched_matches.into_iter().take(known_index));
+                    if max_matches != 0 {
+                        matches_list.truncate(max_matches);
+                    }
+                    break;
+                }
+            }
             matches_list.extend(fetched_matches);
             if max_matches != 0 && matches_list.len() >= max_matches {
                 matches_list.truncate(max_matches);
                 break;
             }
+            if is_last_page {
+                break;
+            }
         }
 
         Ok(matches_list)
     }
 
+    async fn find_first_known_match_index(
+        db: &sqlx::PgPool,
+        match_ids: &[String],
+    ) -> AppResult<Option<usize>> {
+        let known_match_ids: HashSet<String> =
+            sqlx::query_scalar("SELECT match_id FROM lol_matches WHERE match_id = ANY($1)")
+                .bind(match_ids)
+                .fetch_all(db)
+                .await?
+                .into_iter()
+                .collect();
+        Ok(match_ids.iter().position(|id| known_match_ids.contains(id)))
+    }
+
     async fn fetch_match_ids(
         api: &RiotApi,
+        health: &HealthState,
         region: RegionalRoute,
         puuid: &str,
         count: Option<i32>,
         start: Option<i32>,
     ) -> AppResult<Vec<String>> {
-        api.match_v5()
+        let match_ids = api
+            .match_v5()
             .get_match_ids_by_puuid(region, puuid, count, None, None, None, start, None)
-            .await
-            .map_err(|e| e.into())
+            .await;
+        health.record_riot_api_call(match_ids.is_ok());
+        match_ids.map_err(|e| e.into())
     }
 
-    pub async fn bulk_insert_default_match(
-        db: &sqlx::PgPool,
-        match_ids: &[String],
-    ) -> AppResult<()> {
-        let match_ids = match_ids.to_vec();
-        let platforms = match_ids
-            .iter()
-            .map(|x| {
-                let match_id_split = x.split("_").collect::<Vec<&str>>();
-                PlatformRouteDb::from_raw_str(match_id_split[0])
-            })
-            .collect::<Vec<_>>();
-        let sql = r"
-        INSERT INTO
-            lol_matches
-            (match_id, platform)
-        SELECT * FROM UNNEST(
-            $1::VARCHAR(17)[],
-            $2::platform_type[]
-        )
-        ON CONFLICT (match_id) DO NOTHING;
-        ";
-        let _ = sqlx::query_as::<_, Id>(sql)
-            .bind(match_ids)
-            .bind(platforms)
-            .fetch_all(db)
-            .await?;
-        Ok(())
+    #[derive(sqlx::FromRow)]
+    pub struct SummonerUpdateClaim {
+        pub puuid: String,
+        pub previous_matches_updated_at: Option<NaiveDateTime>,
+    }
+
+    #[derive(sqlx::FromRow)]
+    pub struct SummonerFreshness {
+        pub summoner_level: i32,
+        pub profile_icon_id: i32,
+        pub matches_updated_at: Option<NaiveDateTime>,
     }
 }
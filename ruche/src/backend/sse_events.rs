@@ -0,0 +1,171 @@
+use crate::backend::live_game_cache::{LiveGameCache, StatsRefreshClaim};
+use crate::backend::matches_stats_cache::MatchesStatsCache;
+use crate::backend::server_fn_cache::ServerFnCache;
+use crate::backend::server_fns::get_live_game::ssr::refresh_live_game_stats;
+use crate::backend::ssr::AppResult;
+use crate::backend::tasks::update_matches::match_ingestion_queue::find_open_match_jobs;
+use crate::ssr::SubscriberMap;
+use crate::utils::{LiveScoutProgress, RiotMatchId, SSEEvent};
+use itertools::Itertools;
+use leptos::logging::log;
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::time::Duration;
+
+/// Postgres channel carrying the sse events of every instance, payload is `summoner_id;event`.
+pub const SSE_EVENTS_CHANNEL: &str = "ruche_sse_events";
+
+const LISTENER_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Publishes the events through `NOTIFY`, every instance forwards them to its own subscribers.
+pub async fn publish_sse_events(db: &PgPool, events: &[(i32, SSEEvent)]) -> AppResult<()> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let payloads = events
+        .iter()
+        .map(|(summoner_id, event)| format!("{};{}", summoner_id, event.to_string()))
+        .collect_vec();
+    sqlx::query("SELECT pg_notify($1, payload) FROM UNNEST($2::TEXT[]) AS payload")
+        .bind(SSE_EVENTS_CHANNEL)
+        .bind(&payloads)
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+/// Listens to `SSE_EVENTS_CHANNEL` for the whole life of the instance and forwards the events to
+/// the local subscribers. Matches events also invalidate the local caches of the summoner, since
+/// the matches may have been stored by another instance, and advance the live scouts started here.
+/// Live scout events recompute the stats of the cached game before reaching its subscribers.
+pub async fn forward_sse_events(
+    db: PgPool,
+    subscribers: Arc<SubscriberMap>,
+    matches_stats_cache: Arc<MatchesStatsCache>,
+    server_fn_cache: Arc<ServerFnCache>,
+    live_game_cache: Arc<LiveGameCache>,
+) {
+    loop {
+        let mut listener = match PgListener::connect_with(&db).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                log!("Error connecting the sse events listener: {:?}", e);
+                tokio::time::sleep(LISTENER_RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+        if let Err(e) = listener.listen(SSE_EVENTS_CHANNEL).await {
+            log!("Error listening to the sse events: {:?}", e);
+            tokio::time::sleep(LISTENER_RECONNECT_DELAY).await;
+            continue;
+        }
+        loop {
+            match listener.recv().await {
+                Ok(notification) => {
+                    let Some((summoner_id, event)) = parse_sse_payload(notification.payload())
+                    else {
+                        log!("Invalid sse event payload: {}", notification.payload());
+                        continue;
+                    };
+                    match event {
+                        SSEEvent::SummonerMatches(_) => {
+                            matches_stats_cache.invalidate(summoner_id);
+                            server_fn_cache.invalidate(summoner_id);
+                            if let Err(e) =
+                                advance_live_scout(&db, &live_game_cache, summoner_id).await
+                            {
+                                log!("Error advancing live scout: {:?}", e);
+                            }
+                        }
+                        SSEEvent::LiveScout(progress) => {
+                            match live_game_cache.claim_stats_refresh(summoner_id, progress.version)
+                            {
+                                StatsRefreshClaim::NotCached => {}
+                                // forwarded by the claiming refresh
+                                StatsRefreshClaim::AlreadyClaimed => continue,
+                                StatsRefreshClaim::Claimed {
+                                    game_id,
+                                    summoner_ids,
+                                } => {
+                                    tokio::spawn(refresh_and_forward_live_scout(
+                                        db.clone(),
+                                        live_game_cache.clone(),
+                                        subscribers.clone(),
+                                        game_id,
+                                        summoner_ids,
+                                        progress,
+                                    ));
+                                    continue;
+                                }
+                            }
+                        }
+                        SSEEvent::LiveGame(_) => {}
+                    }
+                    if let Some(sender) = subscribers.get(&summoner_id) {
+                        let _ = sender.send(event);
+                    }
+                }
+                Err(e) => {
+                    log!("Error receiving sse events: {:?}", e);
+                    break;
+                }
+            }
+        }
+        tokio::time::sleep(LISTENER_RECONNECT_DELAY).await;
+    }
+}
+
+/// Recomputes the stats of the cached game then forwards the progress to its local subscribers, so
+/// that they read the new stats.
+/// A matches event only ends the scout of the participant once none of its queued matches has an
+/// open job, the first stored batch is usually not the last one.
+async fn advance_live_scout(
+    db: &PgPool,
+    live_game_cache: &LiveGameCache,
+    summoner_id: i32,
+) -> AppResult<()> {
+    let Some(match_ids) = live_game_cache.scout_pending_match_ids(summoner_id) else {
+        return Ok(());
+    };
+    if !find_open_match_jobs(db, &match_ids).await?.is_empty() {
+        return Ok(());
+    }
+    if let Some((progress, summoner_ids)) = live_game_cache.scout_ingested(summoner_id) {
+        let events = summoner_ids
+            .into_iter()
+            .map(|id| (id, SSEEvent::LiveScout(progress)))
+            .collect_vec();
+        publish_sse_events(db, &events).await?;
+    }
+    Ok(())
+}
+
+async fn refresh_and_forward_live_scout(
+    db: PgPool,
+    live_game_cache: Arc<LiveGameCache>,
+    subscribers: Arc<SubscriberMap>,
+    game_id: RiotMatchId,
+    summoner_ids: Vec<i32>,
+    progress: LiveScoutProgress,
+) {
+    if let Some(mut live_game) = live_game_cache.peek_game(game_id) {
+        match refresh_live_game_stats(&db, &mut live_game).await {
+            Ok(_) => live_game_cache.set_game_stats(game_id, progress.version, live_game),
+            Err(e) => log!("Error refreshing live game stats: {:?}", e),
+        }
+    }
+    for summoner_id in summoner_ids {
+        if let Some(sender) = subscribers.get(&summoner_id) {
+            let _ = sender.send(SSEEvent::LiveScout(progress));
+        }
+    }
+}
+
+fn parse_sse_payload(payload: &str) -> Option<(i32, SSEEvent)> {
+    let (summoner_id, event) = payload.split_once(';')?;
+    Some((
+        summoner_id.parse().ok()?,
+        SSEEvent::from_string(event).ok()?,
+    ))
+}
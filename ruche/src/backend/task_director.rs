@@ -1,7 +1,15 @@
+use crate::backend::health::HealthState;
 use axum::async_trait;
+use leptos::logging::log;
+use sqlx::{Connection, PgConnection, PgPool};
 use std::cmp::Reverse;
-use std::collections::BinaryHeap;
-use tokio::time::{Duration, Instant};
+use std::collections::{BinaryHeap, HashSet};
+use std::sync::Arc;
+use tokio::time::{timeout, Duration, Instant};
+
+/// Longest wait on the task leases connection, a stalled database must not stall the scheduling of
+/// the instance tasks.
+const LEASE_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[async_trait]
 pub trait Task: Send + Sync {
@@ -28,6 +36,13 @@ pub trait Task: Send + Sync {
 
     /// Indicates whether the task allows concurrent executions.
     fn allow_concurrent(&self) -> bool;
+
+    /// Indicates whether only one instance of the cluster may run the task, see `TaskLeases`.
+    /// Tasks writing shared database state or spending the Riot API budget for the whole cluster
+    /// are singletons; tasks working on the memory or the disk of the instance run everywhere.
+    fn cluster_singleton(&self) -> bool {
+        false
+    }
 }
 
 impl Clone for Box<dyn Task> {
@@ -36,9 +51,10 @@ impl Clone for Box<dyn Task> {
     }
 }
 
-#[derive(Default)]
 pub struct TaskDirector {
     tasks: BinaryHeap<Reverse<ScheduledTask>>,
+    leases: TaskLeases,
+    health: Arc<HealthState>,
 }
 
 struct ScheduledTask {
@@ -70,9 +86,21 @@ impl Ord for ScheduledTask {
 }
 
 impl TaskDirector {
+    pub fn new(db: PgPool, health: Arc<HealthState>) -> Self {
+        Self {
+            tasks: BinaryHeap::new(),
+            leases: TaskLeases::new(db),
+            health,
+        }
+    }
+
     /// Adds a task to the TaskDirector.
     pub fn add_task<T: Task + 'static>(&mut self, task: T) {
         let next_run = task.next_execution();
+        self.health.register_task(
+            task.name(),
+            next_run.saturating_duration_since(Instant::now()),
+        );
         self.tasks.push(Reverse(ScheduledTask {
             next_run,
             task: Box::new(task),
@@ -85,21 +113,34 @@ impl TaskDirector {
             if let Some(Reverse(mut scheduled_task)) = self.tasks.pop() {
                 let now = Instant::now();
                 if scheduled_task.next_run <= now {
-                    if !scheduled_task.task.is_running() || scheduled_task.task.allow_concurrent() {
+                    let is_leader = !scheduled_task.task.cluster_singleton()
+                        || self.leases.try_hold(scheduled_task.task.name()).await;
+                    if is_leader
+                        && (!scheduled_task.task.is_running()
+                            || scheduled_task.task.allow_concurrent())
+                    {
                         scheduled_task.task.set_running(true);
 
                         // Clone the task for the async block
                         let task_clone = scheduled_task.task.clone();
+                        let health = self.health.clone();
                         tokio::spawn(async move {
                             // Use a guard to reset running state in case of panic
                             let _guard = RunningGuard::new(task_clone.clone());
+                            health.task_started(task_clone.name());
                             task_clone.execute().await;
+                            health.task_finished(task_clone.name());
                         });
                     }
 
                     // Update and reschedule
                     scheduled_task.task.update_schedule();
                     scheduled_task.next_run = scheduled_task.task.next_execution();
+                    self.health.task_scheduled(
+                        scheduled_task.task.name(),
+                        scheduled_task.next_run.saturating_duration_since(now),
+                        !is_leader,
+                    );
                     self.tasks.push(Reverse(scheduled_task));
                 } else {
                     // Sleep until the next task is due
@@ -132,3 +173,82 @@ impl Drop for RunningGuard {
         self.task.set_running(false);
     }
 }
+
+/// Postgres session advisory locks of the cluster singleton tasks, one per task name. They are held
+/// on a dedicated connection for the life of the instance, so the instance keeps running the task
+/// until it dies, then Postgres releases the locks and another instance takes them over.
+struct TaskLeases {
+    db: PgPool,
+    connection: Option<PgConnection>,
+    held: HashSet<&'static str>,
+}
+
+impl TaskLeases {
+    fn new(db: PgPool) -> Self {
+        Self {
+            db,
+            connection: None,
+            held: HashSet::new(),
+        }
+    }
+
+    /// Returns whether this instance holds the lock of the task, trying to take it otherwise.
+    /// A database call running past `LEASE_TIMEOUT` counts as not holding it.
+    async fn try_hold(&mut self, task_name: &'static str) -> bool {
+        if let Some(connection) = self.connection.as_mut() {
+            // locks are lost with the connection, a stalled one is dropped so Postgres releases them
+            if !matches!(timeout(LEASE_TIMEOUT, connection.ping()).await, Ok(Ok(()))) {
+                log!("Lost the task leases connection, releasing {:?}", self.held);
+                self.connection = None;
+                self.held.clear();
+                return false;
+            }
+        }
+        if self.connection.is_none() {
+            match timeout(LEASE_TIMEOUT, self.db.acquire()).await {
+                Ok(Ok(connection)) => self.connection = Some(connection.detach()),
+                Ok(Err(e)) => {
+                    log!("Error acquiring the task leases connection: {:?}", e);
+                    return false;
+                }
+                Err(_) => {
+                    log!("Timed out acquiring the task leases connection");
+                    return false;
+                }
+            }
+        }
+        if self.held.contains(task_name) {
+            return true;
+        }
+        let connection = self.connection.as_mut().unwrap();
+        let lock = timeout(
+            LEASE_TIMEOUT,
+            sqlx::query_scalar::<_, bool>(
+                "SELECT pg_try_advisory_lock(hashtext('ruche_task:' || $1))",
+            )
+            .bind(task_name)
+            .fetch_one(connection),
+        )
+        .await;
+        match lock {
+            Ok(Ok(true)) => {
+                log!("Running {} for the cluster", task_name);
+                self.held.insert(task_name);
+                true
+            }
+            Ok(Ok(false)) => false,
+            Ok(Err(e)) => {
+                log!("Error taking the lease of {}: {:?}", task_name, e);
+                self.connection = None;
+                self.held.clear();
+                false
+            }
+            Err(_) => {
+                log!("Timed out taking the lease of {}", task_name);
+                self.connection = None;
+                self.held.clear();
+                false
+            }
+        }
+    }
+}
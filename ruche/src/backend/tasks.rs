@@ -3,7 +3,10 @@ use tokio::time::Instant;
 
 pub mod generate_sitemap;
 pub mod handle_live_game_cache;
+pub mod refresh_summoners;
+pub mod repair_summoner_champion_stats;
 pub mod sse_broadcast_match_updated_cleanup;
+pub mod update_champion_stats;
 pub mod update_matches;
 pub mod update_pro_players;
 
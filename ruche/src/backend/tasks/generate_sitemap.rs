@@ -16,6 +16,7 @@ use std::sync::{
 };
 use tokio::time::Instant;
 
+/// Runs on every instance, each one serves the sitemap from its own disk.
 pub struct GenerateSiteMapTask {
     db: PgPool,
     start_hour: u32,
@@ -1,39 +1,70 @@
+use crate::backend::health::HealthState;
 use crate::backend::live_game_cache::LiveGameCache;
 use crate::backend::server_fns::get_live_game::ssr::{
     game_info_to_live_game, get_all_participants_live_game_stats,
 };
+use crate::backend::sse_events::publish_sse_events;
 use crate::backend::ssr::{AppResult, PlatformRouteDb};
 use crate::backend::task_director::Task;
+use crate::backend::tasks::update_matches::match_ingestion_queue::enqueue_ended_live_games;
 use crate::ssr::SubscriberMap;
 use crate::utils::{Puuid, RiotMatchId, SSEEvent};
 use axum::async_trait;
 use common::consts::platform_route::PlatformRoute;
+use dashmap::DashMap;
 use itertools::Itertools;
 use riven::models::spectator_v5::CurrentGameInfo;
 use riven::RiotApi;
 use sqlx::PgPool;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use leptos::logging::log;
 use tokio::time::{Duration, Instant};
 
+/// Games are rarely over before this length, they are looked up at the shortest interval past it.
+const EXPECTED_GAME_LENGTH: Duration = Duration::from_secs(20 * 60);
+
+/// Next spectator lookups of the subscribed summoners. A summoner out of game backs off, its delay
+/// doubling at each idle lookup, and a game is looked up once for all its subscribers, more often
+/// as it gets near `EXPECTED_GAME_LENGTH`.
+#[derive(Default)]
+pub struct LivePollSchedule {
+    idle: DashMap<i32, IdlePoll>,
+    games: DashMap<RiotMatchId, Instant>,
+}
+
+struct IdlePoll {
+    next_poll: Instant,
+    delay: Duration,
+}
+
+/// Polls the spectator api for the summoners followed through sse, every `update_interval` at most,
+/// spending at most `api_budget` lookups per run.
 pub struct HandleLiveGameCacheTask {
     pub db: PgPool,
     pub riot_api: Arc<RiotApi>,
     pub cache: Arc<LiveGameCache>,
     pub summoner_updated_sender: Arc<SubscriberMap>,
+    pub health: Arc<HealthState>,
+    pub schedule: Arc<LivePollSchedule>,
+    pub max_poll_interval: Duration,
+    pub api_budget: usize,
     pub update_interval: Duration,
     pub next_run: Instant,
     pub running: Arc<AtomicBool>,
 }
 
 impl HandleLiveGameCacheTask {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         db: PgPool,
         riot_api: Arc<RiotApi>,
         cache: Arc<LiveGameCache>,
         summoner_updated_sender: Arc<SubscriberMap>,
+        health: Arc<HealthState>,
+        max_poll_interval: Duration,
+        api_budget: usize,
         update_interval: Duration,
     ) -> Self {
         let next_run = Instant::now() + update_interval;
@@ -42,11 +73,107 @@ impl HandleLiveGameCacheTask {
             riot_api,
             cache,
             summoner_updated_sender,
+            health,
+            schedule: Arc::new(LivePollSchedule::default()),
+            max_poll_interval,
+            api_budget,
             update_interval,
             next_run,
             running: Arc::new(AtomicBool::new(false)),
         }
     }
+
+    /// Delay until the next lookup of a running game: half the time left before
+    /// `EXPECTED_GAME_LENGTH`, within `update_interval` and `max_poll_interval`.
+    fn game_poll_delay(&self, game_length_secs: i64) -> Duration {
+        let remaining = EXPECTED_GAME_LENGTH
+            .saturating_sub(Duration::from_secs(game_length_secs.max(0) as u64));
+        (remaining / 2).clamp(
+            self.update_interval,
+            self.max_poll_interval.max(self.update_interval),
+        )
+    }
+
+    /// Delay until the next lookup of a summoner out of game, doubled since their last idle one.
+    fn idle_poll_delay(&self, summoner_id: i32) -> Duration {
+        self.schedule
+            .idle
+            .get(&summoner_id)
+            .map_or(self.update_interval, |poll| {
+                (poll.delay * 2).min(self.max_poll_interval.max(self.update_interval))
+            })
+    }
+
+    /// Keeps the due lookups within the budget, games first then idle summoners, oldest due first.
+    fn select_due_lookups(
+        &self,
+        ig_ids: HashMap<RiotMatchId, Vec<i32>>,
+        none_ids: Vec<i32>,
+    ) -> (HashMap<RiotMatchId, Vec<i32>>, Vec<i32>) {
+        let now = Instant::now();
+        let due_games = ig_ids
+            .into_iter()
+            .filter_map(|(match_id, ids)| {
+                let next_poll = self
+                    .schedule
+                    .games
+                    .get(&match_id)
+                    .map_or(now, |next_poll| *next_poll);
+                (next_poll <= now).then_some((next_poll, match_id, ids))
+            })
+            .sorted_by_key(|(next_poll, _, _)| *next_poll)
+            .take(self.api_budget)
+            .map(|(_, match_id, ids)| (match_id, ids))
+            .collect::<HashMap<_, _>>();
+        let due_idle = none_ids
+            .into_iter()
+            .filter_map(|summoner_id| {
+                let next_poll = self
+                    .schedule
+                    .idle
+                    .get(&summoner_id)
+                    .map_or(now, |poll| poll.next_poll);
+                (next_poll <= now).then_some((next_poll, summoner_id))
+            })
+            .sorted_by_key(|(next_poll, _)| *next_poll)
+            .take(self.api_budget.saturating_sub(due_games.len()))
+            .map(|(_, summoner_id)| summoner_id)
+            .collect_vec();
+        (due_games, due_idle)
+    }
+
+    /// Plans the next lookups from the results of this run.
+    fn update_schedule_from(
+        &self,
+        summoner_match_id: &HashMap<i32, Option<RiotMatchId>>,
+        game_poll_delays: &HashMap<RiotMatchId, Duration>,
+    ) {
+        let now = Instant::now();
+        for (summoner_id, match_id) in summoner_match_id {
+            if match_id.is_some() {
+                self.schedule.idle.remove(summoner_id);
+                continue;
+            }
+            let delay = self.idle_poll_delay(*summoner_id);
+            self.schedule.idle.insert(
+                *summoner_id,
+                IdlePoll {
+                    next_poll: now + delay,
+                    delay,
+                },
+            );
+        }
+        for (match_id, delay) in game_poll_delays {
+            self.schedule.games.insert(*match_id, now + *delay);
+        }
+        // forget the summoners nobody follows anymore and the ended games
+        self.schedule
+            .idle
+            .retain(|summoner_id, _| self.summoner_updated_sender.contains_key(summoner_id));
+        self.schedule
+            .games
+            .retain(|match_id, _| self.cache.game_cache.contains_key(match_id));
+    }
 }
 
 #[async_trait]
@@ -74,28 +201,50 @@ impl Task for HandleLiveGameCacheTask {
             .into_iter()
             .map(|(riot_match_id, id)| (riot_match_id.unwrap(), id))
             .into_group_map();
+        let (ig_ids, none_ids) = self.select_due_lookups(ig_ids, none_ids);
         let (summoner_match_id, mut match_id_game_info) =
             self.fetch_all_game_info(&ig_ids, &none_ids).await;
+        let game_poll_delays = match_id_game_info
+            .iter()
+            .map(|(match_id, game_info)| {
+                (*match_id, self.game_poll_delay(game_info.game_length))
+            })
+            .collect::<HashMap<_, _>>();
 
-        // determine sse events to send
+        // determine sse events to send, from the games before any of them is cleared
+        let previous_match_ids = summoner_match_id
+            .keys()
+            .filter_map(|summoner_id| {
+                self.cache
+                    .summoner_id_to_game
+                    .get(summoner_id)
+                    .map(|entry| (*summoner_id, *entry.value()))
+            })
+            .collect::<HashMap<_, _>>();
         let mut sse_events = vec![];
-        for (summoner_id, match_id) in summoner_match_id {
-            let previous_match_id = self
-                .cache
-                .summoner_id_to_game
-                .get(&summoner_id)
-                .map(|entry| entry.value().clone());
+        let mut ended_games = HashSet::new();
+        for (&summoner_id, &match_id) in summoner_match_id.iter() {
+            let previous_match_id = previous_match_ids.get(&summoner_id).copied();
             match (previous_match_id, match_id) {
                 (Some(previous_match_id), Some(match_id)) => {
                     if previous_match_id != match_id {
                         self.cache.clear_game_data(summoner_id);
+                        ended_games.insert(previous_match_id);
                         sse_events.push((summoner_id, SSEEvent::LiveGame(Some(1))));
                     } else {
+                        self.cache.refresh_game(
+                            match_id,
+                            game_poll_delays
+                                .get(&match_id)
+                                .copied()
+                                .unwrap_or(self.update_interval),
+                        );
                         match_id_game_info.remove(&match_id);
                     }
                 }
-                (Some(_), None) => {
+                (Some(previous_match_id), None) => {
                     self.cache.clear_game_data(summoner_id);
+                    ended_games.insert(previous_match_id);
                     sse_events.push((summoner_id, SSEEvent::LiveGame(None)));
                 }
                 (None, Some(_)) => {
@@ -105,31 +254,52 @@ impl Task for HandleLiveGameCacheTask {
             }
         }
 
+        // ingest the ended games once riot publishes them, the match lands with a SummonerMatches event
+        let ended_games = ended_games
+            .into_iter()
+            .map(|match_id| match_id.to_string())
+            .collect::<Vec<_>>();
+        if let Err(e) = enqueue_ended_live_games(&self.db, &ended_games).await {
+            log!("Error queuing ended live games: {:?}", e);
+        }
+
         // update cache
-        let (all_participants, live_game_stats) = get_all_participants_live_game_stats(
-            &self.db,
-            &self.riot_api,
-            match_id_game_info.values().collect::<Vec<_>>(),
-        )
-        .await
-        .unwrap();
+        let (all_participants, live_game_stats, premade_pairs, recent_forms) =
+            get_all_participants_live_game_stats(
+                &self.db,
+                &self.riot_api,
+                &self.health,
+                match_id_game_info.values().collect::<Vec<_>>(),
+            )
+            .await
+            .unwrap();
         for (match_id, game_info) in match_id_game_info {
-            let (summoner_ids, live_game) =
-                game_info_to_live_game(match_id, game_info, &all_participants, &live_game_stats);
+            let (summoner_ids, live_game) = game_info_to_live_game(
+                match_id,
+                game_info,
+                &all_participants,
+                &live_game_stats,
+                &premade_pairs,
+                &recent_forms,
+            );
             self.cache.set_game_data(match_id, summoner_ids, live_game);
+            self.cache.refresh_game(
+                match_id,
+                game_poll_delays
+                    .get(&match_id)
+                    .copied()
+                    .unwrap_or(self.update_interval),
+            );
         }
 
-        // send sse events
-        for (summoner_id, event) in sse_events {
-            if let Some(sender) = self.summoner_updated_sender.get(&summoner_id) {
-                match sender.value().send(event) {
-                    Ok(_) => {}
-                    Err(e) => {
-                        log!("Error sending sse event: {:?}", e);
-                    }
-                }
-            }
+        // send sse events to the subscribers of every instance
+        if let Err(e) = publish_sse_events(&self.db, &sse_events).await {
+            log!("Error publishing sse events: {:?}", e);
         }
+
+        // drop the games nobody follows anymore
+        self.cache.remove_expired();
+        self.update_schedule_from(&summoner_match_id, &game_poll_delays);
     }
 
     fn next_execution(&self) -> Instant {
@@ -153,7 +323,11 @@ impl Task for HandleLiveGameCacheTask {
             db: self.db.clone(),
             riot_api: self.riot_api.clone(),
             summoner_updated_sender: self.summoner_updated_sender.clone(),
+            health: self.health.clone(),
             cache: self.cache.clone(),
+            schedule: self.schedule.clone(),
+            max_poll_interval: self.max_poll_interval,
+            api_budget: self.api_budget,
             update_interval: self.update_interval,
             next_run: self.next_run,
             running: self.running.clone(),
@@ -179,6 +353,7 @@ impl HandleLiveGameCacheTask {
         HashMap<RiotMatchId, CurrentGameInfo>,
     ) {
         let mut inner_none_ids = none_ids.clone();
+        let mut ended_followers = HashSet::new();
         let ig_first_ids = ig_ids
             .iter()
             .map(|(match_id, ids)| (*ids.iter().next().unwrap(), match_id.clone()))
@@ -201,21 +376,19 @@ impl HandleLiveGameCacheTask {
                     .map(|id| {
                         let (puuid, platform) = puuids.get(id).unwrap();
                         let riot_api = self.riot_api.clone();
+                        let health = self.health.clone();
                         let puuid_ = puuid.clone();
                         let platform_ = platform.clone();
                         async move {
-                            (
-                                *id,
-                                riot_api
-                                    .spectator_v5()
-                                    .get_current_game_info_by_puuid(
-                                        platform_.to_riven(),
-                                        puuid_.as_ref(),
-                                    )
-                                    .await
-                                    .ok()
-                                    .flatten(),
-                            )
+                            let live_game = riot_api
+                                .spectator_v5()
+                                .get_current_game_info_by_puuid(
+                                    platform_.to_riven(),
+                                    puuid_.as_ref(),
+                                )
+                                .await;
+                            health.record_riot_api_call(live_game.is_ok());
+                            (*id, live_game.ok().flatten())
                         }
                     })
                     .collect::<Vec<_>>(),
@@ -239,14 +412,15 @@ impl HandleLiveGameCacheTask {
                             summoner_match_id.insert(id, Some(new_match_id));
                         }
                     } else {
-                        inner_none_ids.extend(participants_ids);
+                        ended_followers.extend(participants_ids);
                     }
                 } else {
                     summoner_match_id.insert(summoner_id, None);
-                    inner_none_ids.extend(participants_ids);
+                    ended_followers.extend(participants_ids);
                 }
             }
         }
+        inner_none_ids.extend(ended_followers.iter().copied());
 
         let puuids = fetch_summoner_puuids_by_ids(&self.db, &inner_none_ids)
             .await
@@ -268,6 +442,23 @@ impl HandleLiveGameCacheTask {
             }
         }
 
+        // the other followers of an ended game spend the budget left by the due lookups, the rest
+        // leave the game now and are looked up on the next run
+        let mut spare_budget = self
+            .api_budget
+            .saturating_sub(ig_ids.len() + none_ids.len());
+        inner_none_ids.retain(|id| {
+            if !ended_followers.contains(id) {
+                return true;
+            }
+            if spare_budget > 0 {
+                spare_budget -= 1;
+                return true;
+            }
+            summoner_match_id.insert(*id, None);
+            false
+        });
+
         while !inner_none_ids.is_empty() {
             let five_first = inner_none_ids
                 .drain(..std::cmp::min(5, inner_none_ids.len()))
@@ -278,21 +469,19 @@ impl HandleLiveGameCacheTask {
                     .map(|id| {
                         let (puuid, platform) = puuids.get(id).unwrap();
                         let riot_api = self.riot_api.clone();
+                        let health = self.health.clone();
                         let puuid_ = puuid.clone();
                         let platform_ = platform.clone();
                         async move {
-                            (
-                                *id,
-                                riot_api
-                                    .spectator_v5()
-                                    .get_current_game_info_by_puuid(
-                                        platform_.to_riven(),
-                                        puuid_.as_ref(),
-                                    )
-                                    .await
-                                    .ok()
-                                    .flatten(),
-                            )
+                            let live_game = riot_api
+                                .spectator_v5()
+                                .get_current_game_info_by_puuid(
+                                    platform_.to_riven(),
+                                    puuid_.as_ref(),
+                                )
+                                .await;
+                            health.record_riot_api_call(live_game.is_ok());
+                            (*id, live_game.ok().flatten())
                         }
                     })
                     .collect::<Vec<_>>(),
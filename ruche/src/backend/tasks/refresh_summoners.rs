@@ -0,0 +1,603 @@
+use crate::backend::health::HealthState;
+use crate::backend::live_game_cache::LiveGameCache;
+use crate::backend::server_fns::update_summoner::ssr::{
+    release_summoner_update, try_claim_summoner_update, update_summoner_default_matches,
+};
+use crate::backend::sse_events::publish_sse_events;
+use crate::backend::ssr::{AppResult, PlatformRouteDb};
+use crate::backend::task_director::Task;
+use crate::backend::tasks::update_matches::match_ingestion_queue::{
+    find_open_match_jobs, MatchJobPriority,
+};
+use crate::ssr::{RiotApiState, SubscriberMap, SummonerPageViews};
+use crate::utils::SSEEvent;
+use axum::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use common::consts::platform_route::PlatformRoute;
+use itertools::Itertools;
+use leptos::logging::log;
+use sqlx::{FromRow, PgPool};
+use std::collections::{BinaryHeap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::time::{Duration, Instant};
+
+const PAGE_VIEW_WEIGHT: u64 = 10;
+const LIVE_SUBSCRIPTION_WEIGHT: u64 = 60;
+const PRO_PLAYER_WEIGHT: u64 = 30;
+/// Staleness is counted in minutes and capped to one day, so it only breaks ties
+/// between summoners nobody is looking at.
+const MAX_STALENESS_MINUTES: u64 = 24 * 60;
+/// Row of `riot_api_budgets` shared by the summoner refresh and the live scouts of every instance.
+const API_BUDGET_NAME: &str = "refresh_summoners";
+
+/// Proactively refreshes the matches of popular summoners (page views, live sse subscriptions,
+/// pro players), most wanted and stalest first. The `api_budget` match-id requests per run are
+/// shared with the live scouts of every instance.
+/// Runs on a single instance, so only the page views and subscriptions of that instance weigh.
+pub struct RefreshSummonersTask {
+    db: PgPool,
+    api: RiotApiState,
+    page_views: Arc<SummonerPageViews>,
+    subscribers: Arc<SubscriberMap>,
+    health: Arc<HealthState>,
+    max_matches: usize,
+    api_budget: usize,
+    min_staleness: Duration,
+    update_interval: Duration,
+    next_run: Instant,
+    running: Arc<AtomicBool>,
+}
+
+impl RefreshSummonersTask {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        db: PgPool,
+        api: RiotApiState,
+        page_views: Arc<SummonerPageViews>,
+        subscribers: Arc<SubscriberMap>,
+        health: Arc<HealthState>,
+        max_matches: usize,
+        api_budget: usize,
+        min_staleness: Duration,
+        update_interval: Duration,
+    ) -> Self {
+        let next_run = Instant::now() + update_interval;
+        Self {
+            db,
+            api,
+            page_views,
+            subscribers,
+            health,
+            max_matches,
+            api_budget,
+            min_staleness,
+            update_interval,
+            next_run,
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+#[async_trait]
+impl Task for RefreshSummonersTask {
+    async fn execute(&self) {
+        let start = Instant::now();
+        match refresh_summoners(
+            &self.db,
+            &self.api,
+            &self.page_views,
+            &self.subscribers,
+            &self.health,
+            self.max_matches,
+            self.api_budget,
+            self.min_staleness,
+            self.update_interval,
+        )
+        .await
+        {
+            Ok(0) => {}
+            Ok(refreshed) => {
+                log!("Refreshed {} summoners in {:?}", refreshed, start.elapsed());
+            }
+            Err(e) => {
+                log!("Error refreshing summoners: {:?}", e);
+            }
+        }
+    }
+
+    fn next_execution(&self) -> Instant {
+        self.next_run
+    }
+
+    fn update_schedule(&mut self) {
+        self.next_run = Instant::now() + self.update_interval;
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    fn set_running(&self, running: bool) {
+        self.running.store(running, Ordering::SeqCst);
+    }
+
+    fn clone_box(&self) -> Box<dyn Task> {
+        Box::new(Self {
+            db: self.db.clone(),
+            api: self.api.clone(),
+            page_views: self.page_views.clone(),
+            subscribers: self.subscribers.clone(),
+            health: self.health.clone(),
+            max_matches: self.max_matches,
+            api_budget: self.api_budget,
+            min_staleness: self.min_staleness,
+            update_interval: self.update_interval,
+            next_run: self.next_run,
+            running: self.running.clone(),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "RefreshSummonersTask"
+    }
+
+    fn allow_concurrent(&self) -> bool {
+        false // Do not allow concurrent executions
+    }
+
+    fn cluster_singleton(&self) -> bool {
+        true
+    }
+}
+
+/// Queues the matches of the participants of the live games scouted on this instance, with at most
+/// `scout_max_matches` matches each, spending the match-id requests from the `api_budget` shared with
+/// the summoner refresh of the cluster. Also decays the page views of the instance, which only feed
+/// the `RefreshSummonersTask` of the leader.
+pub struct ScoutLiveGamesTask {
+    db: PgPool,
+    api: RiotApiState,
+    page_views: Arc<SummonerPageViews>,
+    live_game_cache: Arc<LiveGameCache>,
+    health: Arc<HealthState>,
+    scout_max_matches: usize,
+    api_budget: usize,
+    min_staleness: Duration,
+    update_interval: Duration,
+    next_run: Instant,
+    running: Arc<AtomicBool>,
+}
+
+impl ScoutLiveGamesTask {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        db: PgPool,
+        api: RiotApiState,
+        page_views: Arc<SummonerPageViews>,
+        live_game_cache: Arc<LiveGameCache>,
+        health: Arc<HealthState>,
+        scout_max_matches: usize,
+        api_budget: usize,
+        min_staleness: Duration,
+        update_interval: Duration,
+    ) -> Self {
+        let next_run = Instant::now() + update_interval;
+        Self {
+            db,
+            api,
+            page_views,
+            live_game_cache,
+            health,
+            scout_max_matches,
+            api_budget,
+            min_staleness,
+            update_interval,
+            next_run,
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+#[async_trait]
+impl Task for ScoutLiveGamesTask {
+    async fn execute(&self) {
+        if let Err(e) = scout_live_games(
+            &self.db,
+            &self.api,
+            &self.live_game_cache,
+            &self.health,
+            self.scout_max_matches,
+            self.api_budget,
+            self.min_staleness,
+            self.update_interval,
+        )
+        .await
+        {
+            log!("Error scouting live games: {:?}", e);
+        }
+        decay_page_views(&self.page_views);
+    }
+
+    fn next_execution(&self) -> Instant {
+        self.next_run
+    }
+
+    fn update_schedule(&mut self) {
+        self.next_run = Instant::now() + self.update_interval;
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    fn set_running(&self, running: bool) {
+        self.running.store(running, Ordering::SeqCst);
+    }
+
+    fn clone_box(&self) -> Box<dyn Task> {
+        Box::new(Self {
+            db: self.db.clone(),
+            api: self.api.clone(),
+            page_views: self.page_views.clone(),
+            live_game_cache: self.live_game_cache.clone(),
+            health: self.health.clone(),
+            scout_max_matches: self.scout_max_matches,
+            api_budget: self.api_budget,
+            min_staleness: self.min_staleness,
+            update_interval: self.update_interval,
+            next_run: self.next_run,
+            running: self.running.clone(),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "ScoutLiveGamesTask"
+    }
+
+    fn allow_concurrent(&self) -> bool {
+        false // Do not allow concurrent executions
+    }
+}
+
+/// Queues the matches of the participants waiting in a live game scout and streams the progress,
+/// returns the match-id requests spent. Participants refreshed less than `min_staleness` ago only
+/// count as done.
+#[allow(clippy::too_many_arguments)]
+pub async fn scout_live_games(
+    db: &PgPool,
+    api: &RiotApiState,
+    live_game_cache: &LiveGameCache,
+    health: &HealthState,
+    scout_max_matches: usize,
+    api_budget: usize,
+    min_staleness: Duration,
+    budget_period: Duration,
+) -> AppResult<usize> {
+    let mut progresses = vec![];
+    // a participant whose jobs all failed or were trashed never gets a matches event
+    let pending = live_game_cache.scout_pending();
+    if !pending.is_empty() {
+        let pending_match_ids = pending
+            .iter()
+            .flat_map(|(_, match_ids)| match_ids.iter().cloned())
+            .unique()
+            .collect::<Vec<_>>();
+        let open_match_ids = find_open_match_jobs(db, &pending_match_ids).await?;
+        for (summoner_id, match_ids) in pending {
+            if !match_ids.iter().any(|id| open_match_ids.contains(id)) {
+                progresses.extend(live_game_cache.scout_ingested(summoner_id));
+            }
+        }
+    }
+
+    let waiting_ids = live_game_cache.scout_waiting();
+    let candidates = if waiting_ids.is_empty() {
+        vec![]
+    } else {
+        find_scout_candidates(db, &waiting_ids).await?
+    };
+    let cost = scout_max_matches.div_ceil(100).max(1);
+    let granted_budget = if candidates.is_empty() {
+        0
+    } else {
+        claim_api_budget(db, budget_period, api_budget, candidates.len() * cost).await?
+    };
+    let mut remaining_budget = granted_budget;
+    // unknown summoners have nothing to fetch
+    for summoner_id in waiting_ids
+        .iter()
+        .filter(|id| !candidates.iter().any(|candidate| candidate.id == **id))
+    {
+        progresses.extend(live_game_cache.scout_queued(*summoner_id, vec![]));
+    }
+    for candidate in candidates.iter() {
+        if cost > remaining_budget {
+            break;
+        }
+        let Some(claim) = try_claim_summoner_update(db, candidate.id, min_staleness).await? else {
+            progresses.extend(live_game_cache.scout_queued(candidate.id, vec![]));
+            continue;
+        };
+        match update_summoner_default_matches(
+            db.clone(),
+            api.clone(),
+            health,
+            claim.puuid,
+            PlatformRoute::from(candidate.platform).to_riven(),
+            scout_max_matches,
+            claim.previous_matches_updated_at.is_some(),
+            MatchJobPriority::LiveScout,
+            Some(&mut remaining_budget),
+        )
+        .await
+        {
+            Ok(queued_match_ids) => {
+                progresses.extend(live_game_cache.scout_queued(candidate.id, queued_match_ids));
+            }
+            Err(e) => {
+                // stays waiting, retried on the next run
+                log!("Error scouting summoner {}: {:?}", candidate.id, e);
+                if let Err(e) =
+                    release_summoner_update(db, candidate.id, claim.previous_matches_updated_at)
+                        .await
+                {
+                    log!("Error releasing summoner {}: {:?}", candidate.id, e);
+                }
+            }
+        }
+    }
+    let sse_events = progresses
+        .into_iter()
+        .flat_map(|(progress, summoner_ids)| {
+            summoner_ids
+                .into_iter()
+                .map(move |summoner_id| (summoner_id, SSEEvent::LiveScout(progress)))
+        })
+        .collect::<Vec<_>>();
+    refund_api_budget(db, remaining_budget).await?;
+    publish_sse_events(db, &sse_events).await?;
+    Ok(granted_budget - remaining_budget)
+}
+
+/// Returns the number of summoners whose matches were refreshed.
+#[allow(clippy::too_many_arguments)]
+pub async fn refresh_summoners(
+    db: &PgPool,
+    api: &RiotApiState,
+    page_views: &SummonerPageViews,
+    subscribers: &SubscriberMap,
+    health: &HealthState,
+    max_matches: usize,
+    api_budget: usize,
+    min_staleness: Duration,
+    budget_period: Duration,
+) -> AppResult<usize> {
+    let subscribed_ids = subscribers
+        .iter()
+        .filter(|entry| entry.value().receiver_count() > 0)
+        .map(|entry| *entry.key())
+        .collect::<HashSet<_>>();
+    let watched_ids = page_views
+        .iter()
+        .map(|entry| *entry.key())
+        .chain(subscribed_ids.iter().copied())
+        .unique()
+        .collect::<Vec<_>>();
+
+    let candidates = find_refresh_candidates(db, &watched_ids, min_staleness).await?;
+    if candidates.is_empty() {
+        return Ok(0);
+    }
+    let now = Utc::now().naive_utc();
+    let mut queue = candidates
+        .iter()
+        .enumerate()
+        .map(|(index, candidate)| {
+            let views = page_views
+                .get(&candidate.id)
+                .map(|views| *views.value())
+                .unwrap_or_default();
+            let priority = refresh_priority(
+                views,
+                subscribed_ids.contains(&candidate.id),
+                candidate.is_pro,
+                candidate.matches_updated_at,
+                now,
+            );
+            (priority, index)
+        })
+        .collect::<BinaryHeap<_>>();
+
+    // a full fetch pages through the whole history, an incremental one usually stops on the first page.
+    // the estimate only skips the candidates not fitting the budget, the requests made are charged.
+    let full_fetch_cost = max_matches.div_ceil(100).max(1);
+    let mut remaining_budget = claim_api_budget(db, budget_period, api_budget, api_budget).await?;
+    let mut refreshed = 0;
+    while let Some((_, index)) = queue.pop() {
+        let candidate = &candidates[index];
+        let estimated_cost = if candidate.matches_updated_at.is_some() {
+            1
+        } else {
+            full_fetch_cost
+        };
+        if estimated_cost > remaining_budget {
+            continue;
+        }
+        let Some(claim) = try_claim_summoner_update(db, candidate.id, min_staleness).await? else {
+            // refreshed by a user in the meantime
+            continue;
+        };
+        match update_summoner_default_matches(
+            db.clone(),
+            api.clone(),
+            health,
+            claim.puuid,
+            PlatformRoute::from(candidate.platform).to_riven(),
+            max_matches,
+            claim.previous_matches_updated_at.is_some(),
+            MatchJobPriority::Background,
+            Some(&mut remaining_budget),
+        )
+        .await
+        {
+            Ok(_) => {
+                refreshed += 1;
+                page_views.remove(&candidate.id);
+            }
+            Err(e) => {
+                log!("Error refreshing summoner {}: {:?}", candidate.id, e);
+                if let Err(e) =
+                    release_summoner_update(db, candidate.id, claim.previous_matches_updated_at)
+                        .await
+                {
+                    log!("Error releasing summoner {}: {:?}", candidate.id, e);
+                }
+            }
+        }
+        if remaining_budget == 0 {
+            break;
+        }
+    }
+    refund_api_budget(db, remaining_budget).await?;
+    Ok(refreshed)
+}
+
+/// Takes up to `wanted` requests from the shared budget, which allows `api_budget` requests per
+/// `period` across the cluster. Returns the requests granted.
+async fn claim_api_budget(
+    db: &PgPool,
+    period: Duration,
+    api_budget: usize,
+    wanted: usize,
+) -> AppResult<usize> {
+    sqlx::query_scalar::<_, i32>(
+        r#"
+        WITH current AS (
+            SELECT
+                name,
+                period_start <= NOW() - make_interval(secs => $2) as expired,
+                CASE
+                    WHEN period_start <= NOW() - make_interval(secs => $2) THEN 0
+                    ELSE spent
+                END as spent
+            FROM riot_api_budgets
+            WHERE name = $1
+            FOR UPDATE
+        ), granted AS (
+            SELECT name, expired, spent, LEAST($4, GREATEST($3 - spent, 0)) as granted
+            FROM current
+        )
+        UPDATE riot_api_budgets as rab
+        SET period_start = CASE WHEN granted.expired THEN NOW() ELSE rab.period_start END,
+            spent = granted.spent + granted.granted
+        FROM granted
+        WHERE rab.name = granted.name
+        RETURNING granted.granted
+        "#,
+    )
+    .bind(API_BUDGET_NAME)
+    .bind(period.as_secs_f64())
+    .bind(api_budget as i32)
+    .bind(wanted as i32)
+    .fetch_optional(db)
+    .await
+    .map(|granted| granted.unwrap_or_default().max(0) as usize)
+    .map_err(|e| e.into())
+}
+
+/// Gives the requests granted but not spent back to the shared budget.
+async fn refund_api_budget(db: &PgPool, unspent: usize) -> AppResult<()> {
+    if unspent == 0 {
+        return Ok(());
+    }
+    sqlx::query("UPDATE riot_api_budgets SET spent = GREATEST(spent - $2, 0) WHERE name = $1")
+        .bind(API_BUDGET_NAME)
+        .bind(unspent as i32)
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+pub fn refresh_priority(
+    page_views: u32,
+    is_subscribed: bool,
+    is_pro: bool,
+    matches_updated_at: Option<NaiveDateTime>,
+    now: NaiveDateTime,
+) -> u64 {
+    let staleness_minutes = matches_updated_at
+        .map(|updated_at| (now - updated_at).num_minutes().max(0) as u64)
+        .unwrap_or(MAX_STALENESS_MINUTES)
+        .min(MAX_STALENESS_MINUTES);
+    page_views as u64 * PAGE_VIEW_WEIGHT
+        + if is_subscribed {
+            LIVE_SUBSCRIPTION_WEIGHT
+        } else {
+            0
+        }
+        + if is_pro { PRO_PLAYER_WEIGHT } else { 0 }
+        + staleness_minutes / 10
+}
+
+/// Halves every page view counter so old popularity fades out.
+fn decay_page_views(page_views: &SummonerPageViews) {
+    page_views.retain(|_, views| {
+        *views /= 2;
+        *views > 0
+    });
+}
+
+async fn find_refresh_candidates(
+    db: &PgPool,
+    summoner_ids: &[i32],
+    min_staleness: Duration,
+) -> AppResult<Vec<RefreshCandidate>> {
+    sqlx::query_as::<_, RefreshCandidate>(
+        r#"
+        SELECT
+            ss.id,
+            ss.platform,
+            ss.pro_player_slug IS NOT NULL as is_pro,
+            ss.matches_updated_at
+        FROM summoners as ss
+        WHERE (ss.id = ANY($1) OR ss.pro_player_slug IS NOT NULL)
+          AND (ss.matches_updated_at IS NULL
+            OR ss.matches_updated_at < NOW() - make_interval(secs => $2))
+        "#,
+    )
+    .bind(summoner_ids)
+    .bind(min_staleness.as_secs_f64())
+    .fetch_all(db)
+    .await
+    .map_err(|e| e.into())
+}
+
+async fn find_scout_candidates(
+    db: &PgPool,
+    summoner_ids: &[i32],
+) -> AppResult<Vec<RefreshCandidate>> {
+    sqlx::query_as::<_, RefreshCandidate>(
+        r#"
+        SELECT
+            ss.id,
+            ss.platform,
+            ss.pro_player_slug IS NOT NULL as is_pro,
+            ss.matches_updated_at
+        FROM summoners as ss
+        WHERE ss.id = ANY($1)
+        "#,
+    )
+    .bind(summoner_ids)
+    .fetch_all(db)
+    .await
+    .map_err(|e| e.into())
+}
+
+#[derive(FromRow)]
+pub struct RefreshCandidate {
+    pub id: i32,
+    pub platform: PlatformRouteDb,
+    pub is_pro: bool,
+    pub matches_updated_at: Option<NaiveDateTime>,
+}
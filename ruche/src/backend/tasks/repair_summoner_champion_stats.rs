@@ -0,0 +1,200 @@
+use crate::backend::ssr::AppResult;
+use crate::backend::task_director::Task;
+use axum::async_trait;
+use leptos::logging::log;
+use sqlx::PgPool;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::Arc;
+use tokio::time::{Duration, Instant};
+
+/// Walks the summoners by batch and rebuilds the `summoner_champion_stats` rows of the ones whose
+/// aggregated match count no longer matches their participant rows.
+pub struct SummonerChampionStatsRepairTask {
+    db: PgPool,
+    batch_size: i64,
+    update_interval: Duration,
+    last_summoner_id: Arc<AtomicI32>,
+    next_run: Instant,
+    running: Arc<AtomicBool>,
+}
+
+impl SummonerChampionStatsRepairTask {
+    pub fn new(db: PgPool, batch_size: i64, update_interval: Duration, on_startup: bool) -> Self {
+        let next_run = if on_startup {
+            Instant::now()
+        } else {
+            Instant::now() + update_interval
+        };
+        Self {
+            db,
+            batch_size,
+            update_interval,
+            last_summoner_id: Arc::new(AtomicI32::new(0)),
+            next_run,
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+#[async_trait]
+impl Task for SummonerChampionStatsRepairTask {
+    async fn execute(&self) {
+        let after_summoner_id = self.last_summoner_id.load(Ordering::SeqCst);
+        match repair_summoner_champion_stats(&self.db, after_summoner_id, self.batch_size).await {
+            Ok((last_summoner_id, repaired)) => {
+                // start over once every summoner was checked
+                self.last_summoner_id
+                    .store(last_summoner_id.unwrap_or_default(), Ordering::SeqCst);
+                if repaired > 0 {
+                    log!("Repaired champion stats of {} summoners", repaired);
+                }
+            }
+            Err(e) => {
+                log!("Error repairing summoner champion stats: {:?}", e);
+            }
+        }
+    }
+
+    fn next_execution(&self) -> Instant {
+        self.next_run
+    }
+
+    fn update_schedule(&mut self) {
+        self.next_run = Instant::now() + self.update_interval;
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    fn set_running(&self, running: bool) {
+        self.running.store(running, Ordering::SeqCst);
+    }
+
+    fn clone_box(&self) -> Box<dyn Task> {
+        Box::new(Self {
+            db: self.db.clone(),
+            batch_size: self.batch_size,
+            update_interval: self.update_interval,
+            last_summoner_id: self.last_summoner_id.clone(),
+            next_run: self.next_run,
+            running: self.running.clone(),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "SummonerChampionStatsRepairTask"
+    }
+
+    fn allow_concurrent(&self) -> bool {
+        false // Do not allow concurrent executions
+    }
+
+    fn cluster_singleton(&self) -> bool {
+        true
+    }
+}
+
+/// Checks the `batch_size` summoners following `after_summoner_id`, returns the last checked
+/// summoner id, none when there is no summoner left, and the number of repaired summoners.
+pub async fn repair_summoner_champion_stats(
+    db: &PgPool,
+    after_summoner_id: i32,
+    batch_size: i64,
+) -> AppResult<(Option<i32>, usize)> {
+    let summoner_ids =
+        sqlx::query_scalar::<_, i32>("SELECT id FROM summoners WHERE id > $1 ORDER BY id LIMIT $2")
+            .bind(after_summoner_id)
+            .bind(batch_size)
+            .fetch_all(db)
+            .await?;
+    let Some(last_summoner_id) = summoner_ids.last().copied() else {
+        return Ok((None, 0));
+    };
+
+    let drifted_summoner_ids = sqlx::query_scalar::<_, i32>(
+        r#"
+        SELECT batch.id
+        FROM UNNEST($1::INT[]) AS batch(id)
+                 LEFT JOIN (SELECT lmp.summoner_id, count(*) AS total_matches
+                            FROM lol_match_participants as lmp
+                                     INNER JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+                            WHERE lmp.summoner_id = ANY($1)
+                              AND lm.queue_id IS NOT NULL
+                              AND lm.version IS NOT NULL
+                            GROUP BY lmp.summoner_id) as raw ON raw.summoner_id = batch.id
+                 LEFT JOIN (SELECT summoner_id, sum(total_matches) AS total_matches
+                            FROM summoner_champion_stats
+                            WHERE summoner_id = ANY($1)
+                            GROUP BY summoner_id) as aggregated ON aggregated.summoner_id = batch.id
+        WHERE coalesce(raw.total_matches, 0) != coalesce(aggregated.total_matches, 0)
+        "#,
+    )
+    .bind(&summoner_ids)
+    .fetch_all(db)
+    .await?;
+    if !drifted_summoner_ids.is_empty() {
+        let mut tx = db.begin().await?;
+        rebuild_summoner_champion_stats(&mut tx, &drifted_summoner_ids).await?;
+        tx.commit().await?;
+    }
+    Ok((Some(last_summoner_id), drifted_summoner_ids.len()))
+}
+
+/// Recomputes every `summoner_champion_stats` row of the summoners from their participant rows.
+pub async fn rebuild_summoner_champion_stats(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    summoner_ids: &[i32],
+) -> AppResult<()> {
+    sqlx::query("DELETE FROM summoner_champion_stats WHERE summoner_id = ANY($1)")
+        .bind(summoner_ids)
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query(
+        r#"
+        INSERT INTO summoner_champion_stats (summoner_id, champion_id, queue_id, version, total_matches,
+                                             total_wins, total_kills, total_deaths, total_assists, total_kda,
+                                             total_kill_participation, total_gold_earned, total_cs,
+                                             total_damage_dealt_to_champions, total_damage_taken,
+                                             total_double_kills, total_triple_kills, total_quadra_kills,
+                                             total_penta_kills, arena_placements)
+        SELECT lmp.summoner_id,
+               lmp.champion_id,
+               lm.queue_id,
+               lm.version,
+               count(*),
+               count(*) FILTER (WHERE lmp.won),
+               sum(lmp.kills),
+               sum(lmp.deaths),
+               sum(lmp.assists),
+               sum(lmp.kda),
+               sum(lmp.kill_participation),
+               sum(lmp.gold_earned),
+               sum(lmp.cs),
+               sum(lmp.damage_dealt_to_champions),
+               sum(lmp.damage_taken),
+               sum(lmp.double_kills),
+               sum(lmp.triple_kills),
+               sum(lmp.quadra_kills),
+               sum(lmp.penta_kills),
+               ARRAY [count(*) FILTER (WHERE lmp.subteam_placement = 1),
+                   count(*) FILTER (WHERE lmp.subteam_placement = 2),
+                   count(*) FILTER (WHERE lmp.subteam_placement = 3),
+                   count(*) FILTER (WHERE lmp.subteam_placement = 4),
+                   count(*) FILTER (WHERE lmp.subteam_placement = 5),
+                   count(*) FILTER (WHERE lmp.subteam_placement = 6),
+                   count(*) FILTER (WHERE lmp.subteam_placement = 7),
+                   count(*) FILTER (WHERE lmp.subteam_placement >= 8)]::INTEGER[]
+        FROM lol_match_participants as lmp
+                 INNER JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+        WHERE lmp.summoner_id = ANY($1)
+          AND lm.queue_id IS NOT NULL
+          AND lm.version IS NOT NULL
+        GROUP BY lmp.summoner_id, lmp.champion_id, lm.queue_id, lm.version
+        "#,
+    )
+    .bind(summoner_ids)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,196 @@
+use crate::backend::ssr::AppResult;
+use crate::backend::task_director::Task;
+use axum::async_trait;
+use leptos::logging::log;
+use sqlx::PgPool;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::time::{Duration, Instant};
+
+/// Rebuilds the site-wide `champion_stats` and `champion_stats_matches` aggregates
+/// of every patch played during the last `recent_days`; older patches are left untouched.
+pub struct UpdateChampionStatsTask {
+    db: PgPool,
+    recent_days: i32,
+    update_interval: Duration,
+    next_run: Instant,
+    running: Arc<AtomicBool>,
+}
+
+impl UpdateChampionStatsTask {
+    pub fn new(db: PgPool, recent_days: i32, update_interval: Duration, on_startup: bool) -> Self {
+        let next_run = if on_startup {
+            Instant::now()
+        } else {
+            Instant::now() + update_interval
+        };
+        Self {
+            db,
+            recent_days,
+            update_interval,
+            next_run,
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+#[async_trait]
+impl Task for UpdateChampionStatsTask {
+    async fn execute(&self) {
+        let start = Instant::now();
+        match update_champion_stats(&self.db, self.recent_days).await {
+            Ok(versions) => {
+                log!(
+                    "Champion stats updated for patches {:?} in {:?}",
+                    versions,
+                    start.elapsed()
+                );
+            }
+            Err(e) => {
+                log!("Error updating champion stats: {:?}", e);
+            }
+        }
+    }
+
+    fn next_execution(&self) -> Instant {
+        self.next_run
+    }
+
+    fn update_schedule(&mut self) {
+        self.next_run = Instant::now() + self.update_interval;
+    }
+
+    fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    fn set_running(&self, running: bool) {
+        self.running.store(running, Ordering::SeqCst);
+    }
+
+    fn clone_box(&self) -> Box<dyn Task> {
+        Box::new(Self {
+            db: self.db.clone(),
+            recent_days: self.recent_days,
+            update_interval: self.update_interval,
+            next_run: self.next_run,
+            running: self.running.clone(),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "UpdateChampionStatsTask"
+    }
+
+    fn allow_concurrent(&self) -> bool {
+        false // Do not allow concurrent executions
+    }
+
+    fn cluster_singleton(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the rebuilt patches.
+pub async fn update_champion_stats(db: &PgPool, recent_days: i32) -> AppResult<Vec<String>> {
+    let versions = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT DISTINCT version
+        FROM lol_matches
+        WHERE match_end >= NOW() - make_interval(days => $1)
+          AND version IS NOT NULL
+          AND updated = true
+          AND trashed = false
+        "#,
+    )
+    .bind(recent_days)
+    .fetch_all(db)
+    .await?;
+    if versions.is_empty() {
+        return Ok(versions);
+    }
+
+    let mut tx = db.begin().await?;
+    sqlx::query("DELETE FROM champion_stats WHERE version = ANY($1)")
+        .bind(&versions)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM champion_stats_matches WHERE version = ANY($1)")
+        .bind(&versions)
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(
+        r#"
+        INSERT INTO champion_stats_matches (platform, queue_id, version, total_matches, total_ban_matches)
+        SELECT lm.platform,
+               lm.queue_id,
+               lm.version,
+               count(*),
+               count(*) FILTER (WHERE EXISTS (SELECT 1 FROM lol_match_bans as lmb WHERE lmb.lol_match_id = lm.id))
+        FROM lol_matches as lm
+        WHERE lm.version = ANY($1)
+          AND lm.platform IS NOT NULL
+          AND lm.queue_id IS NOT NULL
+          AND lm.updated = true
+          AND lm.trashed = false
+        GROUP BY lm.platform, lm.queue_id, lm.version
+        "#,
+    )
+    .bind(&versions)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        r#"
+        INSERT INTO champion_stats (platform, queue_id, version, champion_id, total_picks, total_wins,
+                                    total_bans, total_kills, total_deaths, total_assists)
+        SELECT platform,
+               queue_id,
+               version,
+               champion_id,
+               coalesce(picks.total_picks, 0),
+               coalesce(picks.total_wins, 0),
+               coalesce(bans.total_bans, 0),
+               coalesce(picks.total_kills, 0),
+               coalesce(picks.total_deaths, 0),
+               coalesce(picks.total_assists, 0)
+        FROM (SELECT lm.platform,
+                     lm.queue_id,
+                     lm.version,
+                     lmp.champion_id,
+                     count(*)                             AS total_picks,
+                     count(*) FILTER (WHERE lmp.won)      AS total_wins,
+                     sum(lmp.kills)                       AS total_kills,
+                     sum(lmp.deaths)                      AS total_deaths,
+                     sum(lmp.assists)                     AS total_assists
+              FROM lol_match_participants as lmp
+                       INNER JOIN lol_matches as lm ON lm.id = lmp.lol_match_id
+              WHERE lm.version = ANY($1)
+                AND lm.platform IS NOT NULL
+                AND lm.queue_id IS NOT NULL
+                AND lm.updated = true
+                AND lm.trashed = false
+              GROUP BY lm.platform, lm.queue_id, lm.version, lmp.champion_id) as picks
+                 FULL OUTER JOIN
+             (SELECT lm.platform,
+                     lm.queue_id,
+                     lm.version,
+                     lmb.champion_id,
+                     -- a champion banned by both teams counts once per match
+                     count(DISTINCT lmb.lol_match_id) AS total_bans
+              FROM lol_match_bans as lmb
+                       INNER JOIN lol_matches as lm ON lm.id = lmb.lol_match_id
+              WHERE lm.version = ANY($1)
+                AND lm.platform IS NOT NULL
+                AND lm.queue_id IS NOT NULL
+                AND lm.updated = true
+                AND lm.trashed = false
+              GROUP BY lm.platform, lm.queue_id, lm.version, lmb.champion_id) as bans
+             USING (platform, queue_id, version, champion_id)
+        "#,
+    )
+    .bind(&versions)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(versions)
+}
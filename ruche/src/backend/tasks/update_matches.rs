@@ -1,25 +1,36 @@
 pub mod bulk_lol_match_participants;
 pub mod bulk_lol_matches;
 pub mod bulk_summoners;
-
-use crate::backend::ssr::{AppError, AppResult, PlatformRouteDb};
+pub mod match_archive;
+pub mod match_ingestion_queue;
+
+use crate::backend::health::HealthState;
+use crate::backend::matches_stats_cache::MatchesStatsCache;
+use crate::backend::server_fn_cache::ServerFnCache;
+use crate::backend::sse_events::publish_sse_events;
+use crate::backend::ssr::{AppResult, PlatformRouteDb};
 use crate::backend::task_director::Task;
+use crate::backend::tasks::repair_summoner_champion_stats::rebuild_summoner_champion_stats;
 use crate::backend::tasks::update_matches::bulk_lol_match_participants::bulk_insert_lol_match_participants;
 use crate::backend::tasks::update_matches::bulk_lol_matches::{
-    bulk_trashed_matches, bulk_update_matches,
+    bulk_insert_match_bans, bulk_trashed_matches, bulk_update_matches,
 };
 use crate::backend::tasks::update_matches::bulk_summoners::{
     bulk_insert_summoners, bulk_update_summoners,
 };
-use crate::ssr::{RiotApiState, SubscriberMap};
-use crate::utils::{ProPlayerSlug, SSEEvent};
+use crate::backend::tasks::update_matches::match_archive::bulk_archive_matches;
+use crate::backend::tasks::update_matches::match_ingestion_queue::{
+    complete_match_jobs, fail_match_jobs, get_due_match_jobs,
+};
+use crate::ssr::RiotApiState;
+use crate::utils::{version_to_major_minor, ProPlayerSlug, SSEEvent};
 use crate::DB_CHUNK_SIZE;
 use axum::async_trait;
 use chrono::NaiveDateTime;
 use common::consts;
 use common::consts::platform_route::PlatformRoute;
 use futures::stream::{FuturesOrdered, FuturesUnordered, StreamExt};
-use itertools::Itertools;
+use itertools::{Either, Itertools};
 use leptos::logging::log;
 use riven::consts::Champion;
 use sqlx::types::chrono::{DateTime, Utc};
@@ -34,7 +45,10 @@ pub struct UpdateMatchesTask {
     db: PgPool,
     api: RiotApiState,
     update_interval: Duration,
-    update_matches_sender: Arc<SubscriberMap>,
+    matches_stats_cache: Arc<MatchesStatsCache>,
+    server_fn_cache: Arc<ServerFnCache>,
+    health: Arc<HealthState>,
+    archive_matches: bool,
     next_run: Instant,
     running: Arc<AtomicBool>,
 }
@@ -44,14 +58,20 @@ impl UpdateMatchesTask {
         db: PgPool,
         api: RiotApiState,
         update_interval: Duration,
-        update_matches_sender: Arc<SubscriberMap>,
+        matches_stats_cache: Arc<MatchesStatsCache>,
+        server_fn_cache: Arc<ServerFnCache>,
+        health: Arc<HealthState>,
+        archive_matches: bool,
     ) -> Self {
         let next_run = Instant::now() + update_interval;
         Self {
             db,
             api,
             update_interval,
-            update_matches_sender,
+            matches_stats_cache,
+            server_fn_cache,
+            health,
+            archive_matches,
             next_run,
             running: Arc::new(AtomicBool::new(false)),
         }
@@ -61,23 +81,57 @@ impl UpdateMatchesTask {
 #[async_trait]
 impl Task for UpdateMatchesTask {
     async fn execute(&self) {
-        while let Ok(matches) = get_not_updated_match(&self.db, 100).await {
+        while let Ok(matches) = get_due_match_jobs(&self.db, 100).await {
+            if matches.is_empty() {
+                break;
+            }
             let start = Instant::now();
             let match_len = matches.len();
-            match update_matches_task(&self.db, &self.api, matches).await {
+            let lol_match_ids = matches.iter().map(|match_| match_.id).collect_vec();
+            let mut failed_lol_match_ids = HashSet::new();
+            match update_matches_task(
+                &self.db,
+                &self.api,
+                &self.health,
+                matches,
+                self.archive_matches,
+                &mut failed_lol_match_ids,
+            )
+            .await
+            {
                 Ok(summoner_ids) => {
-                    for id in summoner_ids {
-                        if let Some(sender) = self.update_matches_sender.get(&id) {
-                            let _ = sender.send(SSEEvent::SummonerMatches(0));
-                        }
+                    for id in summoner_ids.iter() {
+                        self.matches_stats_cache.invalidate(*id);
+                        self.server_fn_cache.invalidate(*id);
+                    }
+                    let sse_events = summoner_ids
+                        .into_iter()
+                        .map(|id| (id, SSEEvent::SummonerMatches(0)))
+                        .collect_vec();
+                    if let Err(e) = publish_sse_events(&self.db, &sse_events).await {
+                        log!("Error publishing sse events: {:?}", e);
                     }
                     log!("Updated {} matches in {:?}", match_len, start.elapsed());
                 }
                 Err(e) => {
                     log!("Error updating matches: {:?}", e);
+                    // retry the whole batch later instead of looping on it, the jobs already
+                    // failed on their own are not counted twice
+                    let error = e.to_string();
+                    let failures = lol_match_ids
+                        .into_iter()
+                        .filter(|id| !failed_lol_match_ids.contains(id))
+                        .map(|id| (id, error.clone()))
+                        .collect_vec();
+                    if let Err(e) = fail_match_jobs(&self.db, &failures).await {
+                        log!("Error rescheduling match jobs: {:?}", e);
+                        break;
+                    }
                 }
             };
         }
+        self.matches_stats_cache.remove_expired();
+        self.server_fn_cache.remove_expired();
     }
 
     fn next_execution(&self) -> Instant {
@@ -101,7 +155,10 @@ impl Task for UpdateMatchesTask {
             db: self.db.clone(),
             api: self.api.clone(),
             update_interval: self.update_interval,
-            update_matches_sender: self.update_matches_sender.clone(),
+            matches_stats_cache: self.matches_stats_cache.clone(),
+            server_fn_cache: self.server_fn_cache.clone(),
+            health: self.health.clone(),
+            archive_matches: self.archive_matches,
             next_run: self.next_run,
             running: self.running.clone(),
         })
@@ -114,12 +171,19 @@ impl Task for UpdateMatchesTask {
     fn allow_concurrent(&self) -> bool {
         false // Do not allow concurrent executions
     }
+
+    fn cluster_singleton(&self) -> bool {
+        true
+    }
 }
 
 async fn update_matches_task(
     db: &PgPool,
     api: &RiotApiState,
+    health: &HealthState,
     matches_to_update: Vec<LolMatchNotUpdated>,
+    archive_matches: bool,
+    failed_lol_match_ids: &mut HashSet<i32>,
 ) -> AppResult<HashSet<i32>> {
     let match_data_futures = matches_to_update.iter().map(|match_| {
         let api = Arc::clone(api);
@@ -132,18 +196,51 @@ async fn update_matches_task(
     });
 
     let match_raw_datas: Vec<_> = FuturesOrdered::from_iter(match_data_futures)
-        .filter_map(|result| async move { result.ok() })
         .collect()
         .await;
 
-    let (trashed_matches, match_datas): (Vec<_>, Vec<_>) = match_raw_datas
+    let (fetch_failures, fetched_matches): (Vec<_>, Vec<_>) = match_raw_datas
         .into_iter()
         .zip(matches_to_update.into_iter())
-        .partition(|(match_, _)| {
+        .partition_map(|(result, match_)| match result {
+            Ok(match_data) => Either::Right((match_data, match_)),
+            Err(e) => Either::Left((match_.id, e.to_string())),
+        });
+    if !fetched_matches.is_empty() {
+        health.record_riot_api_call(true);
+    }
+    if !fetch_failures.is_empty() {
+        health.record_riot_api_call(false);
+        log!("Failed to fetch {} matches", fetch_failures.len());
+        fail_match_jobs(db, &fetch_failures).await?;
+        failed_lol_match_ids.extend(fetch_failures.iter().map(|(id, _)| *id));
+    }
+    if archive_matches {
+        let archives = fetched_matches
+            .iter()
+            .filter_map(|(match_data, match_)| match_data.as_ref().map(|data| (match_.id, data)))
+            .collect_vec();
+        bulk_archive_matches(db, &archives).await?;
+    }
+
+    let (unpublished_matches, fetched_matches): (Vec<_>, Vec<_>) = fetched_matches
+        .into_iter()
+        .partition(|(match_, match_not_updated)| {
+            match_.is_none() && match_not_updated.wait_for_publication
+        });
+    if !unpublished_matches.is_empty() {
+        let retries = unpublished_matches
+            .iter()
+            .map(|(_, match_)| (match_.id, "match not published yet".to_string()))
+            .collect_vec();
+        fail_match_jobs(db, &retries).await?;
+        failed_lol_match_ids.extend(retries.iter().map(|(id, _)| *id));
+    }
+
+    let (trashed_matches, match_datas): (Vec<_>, Vec<_>) =
+        fetched_matches.into_iter().partition(|(match_, _)| {
             if let Some(match_) = match_ {
-                match_.info.game_mode == riven::consts::GameMode::STRAWBERRY
-                    || match_.info.game_version.is_empty()
-                    || match_.info.game_id == 0
+                match_.info.game_version.is_empty() || match_.info.game_id == 0
             } else {
                 true
             }
@@ -154,36 +251,8 @@ async fn update_matches_task(
         .collect_vec();
 
     // Collect TempSummoner data from match data
-    let mut participants_map = HashMap::new();
-    for (match_data, _) in match_datas.iter() {
-        let platform_code = match_data
-            .metadata
-            .match_id
-            .split('_')
-            .next()
-            .unwrap_or_default();
-        let match_platform = consts::platform_route::PlatformRoute::from(platform_code);
-
-        for participant in &match_data.info.participants {
-            if participant.puuid == "BOT" {
-                continue;
-            }
-            participants_map
-                .entry(participant.puuid.clone())
-                .or_insert_with(|| TempSummoner {
-                    puuid: participant.puuid.clone(),
-                    game_name: participant.riot_id_game_name.clone().unwrap_or_default(),
-                    tag_line: participant.riot_id_tagline.clone(),
-                    platform: match_platform.to_string(),
-                    summoner_level: participant.summoner_level,
-                    profile_icon_id: participant.profile_icon as u16,
-                    updated_at: DateTime::from_timestamp_millis(
-                        match_data.info.game_end_timestamp.unwrap_or(0),
-                    )
-                    .expect("update match task:timestamp error"),
-                });
-        }
-    }
+    let participants_map =
+        collect_match_summoners(match_datas.iter().map(|(match_data, _)| match_data));
 
     // Separate summoners into those to insert and update
     let puuids: Vec<String> = participants_map.keys().cloned().collect();
@@ -279,150 +348,293 @@ async fn update_matches_task(
     let match_participants: Vec<TempParticipant> = match_datas
         .iter()
         .flat_map(|(match_data, match_)| {
-            let won_team_id = match_data
-                .info
-                .teams
-                .iter()
-                .find(|team| team.win)
-                .map(|team| team.team_id);
-
-            let team_kills: HashMap<riven::consts::Team, i32> = match_data
-                .info
-                .teams
-                .iter()
-                .map(|team| (team.team_id, team.objectives.champion.kills))
-                .collect();
-
-            // Instead of returning an iterator, collect the results into a Vec
-            match_data
-                .info
-                .participants
-                .iter()
-                .filter_map(|participant| {
-                    if participant.puuid == "BOT" {
-                        return None;
-                    }
-                    let summoner_id =
-                        if let Some(summoner_id) = summoner_map.get(&participant.puuid) {
-                            *summoner_id
-                        } else {
-                            return None;
-                        };
-                    let team_kill_count = *team_kills.get(&participant.team_id).unwrap_or(&0);
-
-                    let kda = if participant.deaths == 0 {
-                        (participant.kills + participant.assists) as f64
-                    } else {
-                        (participant.kills + participant.assists) as f64 / participant.deaths as f64
-                    };
-                    let kda = (kda * 100.0).round() / 100.0;
-
-                    let kill_participation = if team_kill_count == 0 {
-                        0.0
-                    } else {
-                        (participant.kills + participant.assists) as f64 / team_kill_count as f64
-                    };
-                    let kill_participation = (kill_participation * 100.0).round() / 100.0;
-                    let champion_id = Champion::try_from(participant.champion_name.as_str())
-                        .unwrap()
-                        .0;
-                    Some(TempParticipant {
-                        champion_id,
-                        summoner_id,
-                        lol_match_id: match_.id,
-                        summoner_spell1_id: participant.summoner1_id,
-                        summoner_spell2_id: participant.summoner2_id,
-                        team_id: participant.team_id as i32,
-                        won: Some(participant.team_id) == won_team_id,
-                        kill_participation,
-                        champ_level: participant.champ_level,
-                        kda,
-                        kills: participant.kills,
-                        deaths: participant.deaths,
-                        assists: participant.assists,
-                        damage_dealt_to_champions: participant.total_damage_dealt_to_champions,
-                        damage_taken: participant.total_damage_taken,
-                        gold_earned: participant.gold_earned,
-                        wards_placed: participant.wards_placed,
-                        cs: participant.total_minions_killed,
-                        cs_per_minute: participant.total_minions_killed as f64
-                            / (match_data.info.game_duration as f64 / 60.0),
-                        double_kills: participant.double_kills,
-                        triple_kills: participant.triple_kills,
-                        quadra_kills: participant.quadra_kills,
-                        penta_kills: participant.penta_kills,
-                        perk_defense_id: participant.perks.stat_perks.defense,
-                        perk_flex_id: participant.perks.stat_perks.flex,
-                        perk_offense_id: participant.perks.stat_perks.offense,
-                        perk_primary_style_id: participant
-                            .perks
-                            .styles
-                            .first()
-                            .map_or(0, |style| style.style),
-                        perk_sub_style_id: participant
-                            .perks
-                            .styles
-                            .get(1)
-                            .map_or(0, |style| style.style),
-                        perk_primary_selection_id: participant
-                            .perks
-                            .styles
-                            .first()
-                            .and_then(|style| style.selections.first())
-                            .map_or(0, |sel| sel.perk),
-                        perk_primary_selection1_id: participant
-                            .perks
-                            .styles
-                            .first()
-                            .and_then(|style| style.selections.get(1))
-                            .map_or(0, |sel| sel.perk),
-                        perk_primary_selection2_id: participant
-                            .perks
-                            .styles
-                            .first()
-                            .and_then(|style| style.selections.get(2))
-                            .map_or(0, |sel| sel.perk),
-                        perk_primary_selection3_id: participant
-                            .perks
-                            .styles
-                            .first()
-                            .and_then(|style| style.selections.get(3))
-                            .map_or(0, |sel| sel.perk),
-                        perk_sub_selection1_id: participant
-                            .perks
-                            .styles
-                            .get(1)
-                            .and_then(|style| style.selections.first())
-                            .map_or(0, |sel| sel.perk),
-                        perk_sub_selection2_id: participant
-                            .perks
-                            .styles
-                            .get(1)
-                            .and_then(|style| style.selections.get(1))
-                            .map_or(0, |sel| sel.perk),
-                        item0_id: participant.item0,
-                        item1_id: participant.item1,
-                        item2_id: participant.item2,
-                        item3_id: participant.item3,
-                        item4_id: participant.item4,
-                        item5_id: participant.item5,
-                        item6_id: participant.item6,
-                    })
-                })
-                .collect::<Vec<_>>() // Collect the iterator into a Vec
+            build_match_participants(match_data, match_.id, &summoner_map)
         })
-        .collect(); // Collect the Vecs from each closure into a single Vec
+        .collect();
 
-    // Bulk insert participants
+    // Bulk insert participants, replacing the ones of the matches requeued for a refetch
+    let lol_match_ids = match_datas
+        .iter()
+        .map(|(_, match_)| match_.id)
+        .collect_vec();
+    let mut tx = db.begin().await?;
+    let replaced_participants = sqlx::query_as::<_, (i32, i32)>(
+        "DELETE FROM lol_match_participants WHERE lol_match_id = ANY($1) RETURNING lol_match_id, summoner_id",
+    )
+    .bind(&lol_match_ids)
+    .fetch_all(&mut *tx)
+    .await?;
     for chunk in match_participants.chunks(DB_CHUNK_SIZE) {
-        bulk_insert_lol_match_participants(db, chunk).await?;
+        bulk_insert_lol_match_participants(&mut tx, chunk).await?;
+    }
+    if !replaced_participants.is_empty() {
+        // the insert only adds to the aggregates, recompute them without the deleted rows
+        let replaced_lol_match_ids = replaced_participants
+            .iter()
+            .map(|(lol_match_id, _)| *lol_match_id)
+            .collect::<HashSet<_>>();
+        let affected_summoner_ids = replaced_participants
+            .iter()
+            .map(|(_, summoner_id)| *summoner_id)
+            .chain(
+                match_participants
+                    .iter()
+                    .filter(|participant| {
+                        replaced_lol_match_ids.contains(&participant.lol_match_id)
+                    })
+                    .map(|participant| participant.summoner_id),
+            )
+            .unique()
+            .collect_vec();
+        for chunk in affected_summoner_ids.chunks(DB_CHUNK_SIZE) {
+            rebuild_summoner_champion_stats(&mut tx, chunk).await?;
+        }
+    }
+    tx.commit().await?;
+    let match_bans = match_datas
+        .iter()
+        .map(|(match_data, match_)| (match_.id, match_data))
+        .collect_vec();
+    for chunk in match_bans.chunks(DB_CHUNK_SIZE) {
+        bulk_insert_match_bans(db, chunk).await?;
     }
     // Bulk update matches
+    let done_lol_match_ids = match_datas
+        .iter()
+        .map(|(_, match_)| match_.id)
+        .chain(trashed_matches.iter().map(|(_, match_)| match_.id))
+        .collect_vec();
     bulk_update_matches(db, match_datas).await?;
     bulk_trashed_matches(db, trashed_matches).await?;
+    complete_match_jobs(db, &done_lol_match_ids).await?;
     Ok(summoner_map.into_values().collect::<HashSet<i32>>())
 }
 
+/// Collects the summoners of the given matches by puuid, bots excluded.
+pub fn collect_match_summoners<'a>(
+    match_datas: impl Iterator<Item = &'a riven::models::match_v5::Match>,
+) -> HashMap<String, TempSummoner> {
+    let mut participants_map = HashMap::new();
+    for match_data in match_datas {
+        let platform_code = match_data
+            .metadata
+            .match_id
+            .split('_')
+            .next()
+            .unwrap_or_default();
+        let match_platform = consts::platform_route::PlatformRoute::from(platform_code);
+
+        for participant in &match_data.info.participants {
+            if participant.puuid == "BOT" {
+                continue;
+            }
+            participants_map
+                .entry(participant.puuid.clone())
+                .or_insert_with(|| TempSummoner {
+                    puuid: participant.puuid.clone(),
+                    game_name: participant.riot_id_game_name.clone().unwrap_or_default(),
+                    tag_line: participant.riot_id_tagline.clone(),
+                    platform: match_platform.to_string(),
+                    summoner_level: participant.summoner_level,
+                    profile_icon_id: participant.profile_icon as u16,
+                    updated_at: DateTime::from_timestamp_millis(
+                        match_data.info.game_end_timestamp.unwrap_or(0),
+                    )
+                    .expect("update match task:timestamp error"),
+                });
+        }
+    }
+    participants_map
+}
+
+/// Maps the riot match participants to rows of `lol_match_participants`,
+/// skipping bots and summoners missing from `summoner_map`.
+pub fn build_match_participants(
+    match_data: &riven::models::match_v5::Match,
+    lol_match_id: i32,
+    summoner_map: &HashMap<String, i32>,
+) -> Vec<TempParticipant> {
+    let won_team_id = match_data
+        .info
+        .teams
+        .iter()
+        .find(|team| team.win)
+        .map(|team| team.team_id);
+
+    let team_kills: HashMap<riven::consts::Team, i32> = match_data
+        .info
+        .teams
+        .iter()
+        .map(|team| (team.team_id, team.objectives.champion.kills))
+        .collect();
+
+    // Arena riot teams are meaningless, players are ranked by 2 players subteams.
+    // Swarm is PvE, every player is in the same team.
+    let is_arena = match_data.info.game_mode == riven::consts::GameMode::CHERRY;
+    let use_participant_win =
+        is_arena || match_data.info.game_mode == riven::consts::GameMode::STRAWBERRY;
+    let subteam_kills: HashMap<i32, i32> = if is_arena {
+        match_data
+            .info
+            .participants
+            .iter()
+            .map(|participant| {
+                (
+                    participant.player_subteam_id.unwrap_or_default(),
+                    participant.kills,
+                )
+            })
+            .into_grouping_map()
+            .sum()
+    } else {
+        HashMap::new()
+    };
+
+    let queue_id = match_data.info.queue_id.0 as i32;
+    let version = if match_data.info.game_version.is_empty() {
+        String::new()
+    } else {
+        version_to_major_minor(match_data.info.game_version.as_str())
+    };
+    match_data
+        .info
+        .participants
+        .iter()
+        .filter_map(|participant| {
+            if participant.puuid == "BOT" {
+                return None;
+            }
+            let summoner_id = if let Some(summoner_id) = summoner_map.get(&participant.puuid) {
+                *summoner_id
+            } else {
+                return None;
+            };
+            let team_kill_count = if is_arena {
+                *subteam_kills
+                    .get(&participant.player_subteam_id.unwrap_or_default())
+                    .unwrap_or(&0)
+            } else {
+                *team_kills.get(&participant.team_id).unwrap_or(&0)
+            };
+
+            let kda = if participant.deaths == 0 {
+                (participant.kills + participant.assists) as f64
+            } else {
+                (participant.kills + participant.assists) as f64 / participant.deaths as f64
+            };
+            let kda = (kda * 100.0).round() / 100.0;
+
+            let kill_participation = if team_kill_count == 0 {
+                0.0
+            } else {
+                (participant.kills + participant.assists) as f64 / team_kill_count as f64
+            };
+            let kill_participation = (kill_participation * 100.0).round() / 100.0;
+            let champion_id = Champion::try_from(participant.champion_name.as_str())
+                .unwrap()
+                .0;
+            Some(TempParticipant {
+                champion_id,
+                summoner_id,
+                lol_match_id,
+                queue_id,
+                version: version.clone(),
+                summoner_spell1_id: participant.summoner1_id,
+                summoner_spell2_id: participant.summoner2_id,
+                team_id: participant.team_id as i32,
+                won: if use_participant_win {
+                    participant.win
+                } else {
+                    Some(participant.team_id) == won_team_id
+                },
+                kill_participation,
+                champ_level: participant.champ_level,
+                kda,
+                kills: participant.kills,
+                deaths: participant.deaths,
+                assists: participant.assists,
+                damage_dealt_to_champions: participant.total_damage_dealt_to_champions,
+                damage_taken: participant.total_damage_taken,
+                gold_earned: participant.gold_earned,
+                wards_placed: participant.wards_placed,
+                cs: participant.total_minions_killed,
+                cs_per_minute: participant.total_minions_killed as f64
+                    / (match_data.info.game_duration as f64 / 60.0),
+                double_kills: participant.double_kills,
+                triple_kills: participant.triple_kills,
+                quadra_kills: participant.quadra_kills,
+                penta_kills: participant.penta_kills,
+                perk_defense_id: participant.perks.stat_perks.defense,
+                perk_flex_id: participant.perks.stat_perks.flex,
+                perk_offense_id: participant.perks.stat_perks.offense,
+                perk_primary_style_id: participant
+                    .perks
+                    .styles
+                    .first()
+                    .map_or(0, |style| style.style),
+                perk_sub_style_id: participant
+                    .perks
+                    .styles
+                    .get(1)
+                    .map_or(0, |style| style.style),
+                perk_primary_selection_id: participant
+                    .perks
+                    .styles
+                    .first()
+                    .and_then(|style| style.selections.first())
+                    .map_or(0, |sel| sel.perk),
+                perk_primary_selection1_id: participant
+                    .perks
+                    .styles
+                    .first()
+                    .and_then(|style| style.selections.get(1))
+                    .map_or(0, |sel| sel.perk),
+                perk_primary_selection2_id: participant
+                    .perks
+                    .styles
+                    .first()
+                    .and_then(|style| style.selections.get(2))
+                    .map_or(0, |sel| sel.perk),
+                perk_primary_selection3_id: participant
+                    .perks
+                    .styles
+                    .first()
+                    .and_then(|style| style.selections.get(3))
+                    .map_or(0, |sel| sel.perk),
+                perk_sub_selection1_id: participant
+                    .perks
+                    .styles
+                    .get(1)
+                    .and_then(|style| style.selections.first())
+                    .map_or(0, |sel| sel.perk),
+                perk_sub_selection2_id: participant
+                    .perks
+                    .styles
+                    .get(1)
+                    .and_then(|style| style.selections.get(1))
+                    .map_or(0, |sel| sel.perk),
+                item0_id: participant.item0,
+                item1_id: participant.item1,
+                item2_id: participant.item2,
+                item3_id: participant.item3,
+                item4_id: participant.item4,
+                item5_id: participant.item5,
+                item6_id: participant.item6,
+                player_subteam_id: participant.player_subteam_id.filter(|_| is_arena),
+                subteam_placement: participant.subteam_placement.filter(|_| is_arena),
+                team_position: Some(participant.team_position.clone())
+                    .filter(|position| !position.is_empty()),
+                augment1_id: participant.player_augment1.unwrap_or_default(),
+                augment2_id: participant.player_augment2.unwrap_or_default(),
+                augment3_id: participant.player_augment3.unwrap_or_default(),
+                augment4_id: participant.player_augment4.unwrap_or_default(),
+                augment5_id: participant.player_augment5.unwrap_or_default(),
+                augment6_id: participant.player_augment6.unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
 #[derive(Clone, Debug)]
 pub struct TempSummoner {
     pub game_name: String,
@@ -450,6 +662,9 @@ pub struct TempParticipant {
     pub champion_id: i16,
     pub summoner_id: i32,
     pub lol_match_id: i32,
+    /// Only used by the `summoner_champion_stats` aggregates, not stored on the participant.
+    pub queue_id: i32,
+    pub version: String,
     pub summoner_spell1_id: i32,
     pub summoner_spell2_id: i32,
     pub team_id: i32,
@@ -488,25 +703,16 @@ pub struct TempParticipant {
     pub item4_id: i32,
     pub item5_id: i32,
     pub item6_id: i32,
-}
-
-pub async fn get_not_updated_match(db: &PgPool, limit: i32) -> AppResult<Vec<LolMatchNotUpdated>> {
-    let result = sqlx::query_as::<_, LolMatchNotUpdated>(
-        r#"
-            SELECT id, match_id, platform, updated FROM lol_matches
-            WHERE updated = false
-            ORDER BY match_id DESC
-            LIMIT $1;
-        "#,
-    )
-    .bind(limit)
-    .fetch_all(db)
-    .await?;
-    if !result.is_empty() {
-        Ok(result)
-    } else {
-        Err(AppError::CustomError("No matches to update".to_string()))
-    }
+    pub player_subteam_id: Option<i32>,
+    pub subteam_placement: Option<i32>,
+    /// Empty positions (arena, aram) are stored as NULL.
+    pub team_position: Option<String>,
+    pub augment1_id: i32,
+    pub augment2_id: i32,
+    pub augment3_id: i32,
+    pub augment4_id: i32,
+    pub augment5_id: i32,
+    pub augment6_id: i32,
 }
 
 pub async fn fetch_existing_summoners(
@@ -593,7 +799,7 @@ pub async fn find_conflicting_summoners(
     .collect())
 }
 
-pub async  fn delete_summoner_account_by_id(db: &PgPool, id: i32) -> AppResult<()> {
+pub async fn delete_summoner_account_by_id(db: &PgPool, id: i32) -> AppResult<()> {
     sqlx::query("DELETE FROM summoners WHERE id = $1")
         .bind(id)
         .execute(db)
@@ -642,4 +848,6 @@ pub struct LolMatchNotUpdated {
     pub match_id: String,
     pub platform: PlatformRouteDb,
     pub updated: bool,
+    /// Ended live game, retried while riot has not published it.
+    pub wait_for_publication: bool,
 }
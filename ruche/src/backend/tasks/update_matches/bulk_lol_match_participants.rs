@@ -1,9 +1,12 @@
 use crate::backend::ssr::AppResult;
 use crate::backend::tasks::update_matches::TempParticipant;
 use itertools::Itertools;
+use std::collections::HashMap;
 
+/// Inserts the participants and adds them to the `summoner_champion_stats` aggregates in the
+/// caller transaction.
 pub async fn bulk_insert_lol_match_participants(
-    db: &sqlx::PgPool,
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
     participants: &[TempParticipant],
 ) -> AppResult<()> {
     let (
@@ -169,6 +172,43 @@ pub async fn bulk_insert_lol_match_participants(
         })
         .multiunzip();
 
+    let (
+        player_subteam_ids,
+        subteam_placements,
+        augment1_ids,
+        augment2_ids,
+        augment3_ids,
+        augment4_ids,
+        augment5_ids,
+        augment6_ids,
+        team_positions,
+    ): (
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+    ) = participants
+        .iter()
+        .map(|p| {
+            (
+                p.player_subteam_id,
+                p.subteam_placement,
+                p.augment1_id,
+                p.augment2_id,
+                p.augment3_id,
+                p.augment4_id,
+                p.augment5_id,
+                p.augment6_id,
+                p.team_position.clone(),
+            )
+        })
+        .multiunzip();
+
     let sql = r#"
             INSERT INTO lol_match_participants (
                 champion_id,
@@ -211,7 +251,16 @@ pub async fn bulk_insert_lol_match_participants(
                 item3_id,
                 item4_id,
                 item5_id,
-                item6_id
+                item6_id,
+                player_subteam_id,
+                subteam_placement,
+                augment1_id,
+                augment2_id,
+                augment3_id,
+                augment4_id,
+                augment5_id,
+                augment6_id,
+                team_position
             )
             SELECT * FROM UNNEST (
                 $1::INT[],
@@ -254,7 +303,16 @@ pub async fn bulk_insert_lol_match_participants(
                 $38::INT[],
                 $39::INT[],
                 $40::INT[],
-                $41::INT[]
+                $41::INT[],
+                $42::INT[],
+                $43::INT[],
+                $44::INT[],
+                $45::INT[],
+                $46::INT[],
+                $47::INT[],
+                $48::INT[],
+                $49::INT[],
+                $50::VARCHAR[]
             );
         "#;
 
@@ -300,8 +358,182 @@ pub async fn bulk_insert_lol_match_participants(
         .bind(&item4_ids)
         .bind(&item5_ids)
         .bind(&item6_ids)
-        .execute(db)
+        .bind(&player_subteam_ids)
+        .bind(&subteam_placements)
+        .bind(&augment1_ids)
+        .bind(&augment2_ids)
+        .bind(&augment3_ids)
+        .bind(&augment4_ids)
+        .bind(&augment5_ids)
+        .bind(&augment6_ids)
+        .bind(&team_positions)
+        .execute(&mut **tx)
         .await?;
+    bulk_increment_summoner_champion_stats(tx, participants).await?;
+    Ok(())
+}
 
+/// Adds the participants to the `summoner_champion_stats` aggregates, in the transaction
+/// inserting them so both stay consistent.
+pub async fn bulk_increment_summoner_champion_stats(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    participants: &[TempParticipant],
+) -> AppResult<()> {
+    let mut aggregates: HashMap<(i32, i32, i32, &str), SummonerChampionStatsDelta> = HashMap::new();
+    for p in participants.iter().filter(|p| !p.version.is_empty()) {
+        let delta = aggregates
+            .entry((
+                p.summoner_id,
+                p.champion_id as i32,
+                p.queue_id,
+                p.version.as_str(),
+            ))
+            .or_default();
+        delta.total_matches += 1;
+        delta.total_wins += p.won as i32;
+        delta.total_kills += p.kills as i64;
+        delta.total_deaths += p.deaths as i64;
+        delta.total_assists += p.assists as i64;
+        delta.total_kda += p.kda;
+        delta.total_kill_participation += p.kill_participation;
+        delta.total_gold_earned += p.gold_earned as i64;
+        delta.total_cs += p.cs as i64;
+        delta.total_damage_dealt_to_champions += p.damage_dealt_to_champions as i64;
+        delta.total_damage_taken += p.damage_taken as i64;
+        delta.total_double_kills += p.double_kills;
+        delta.total_triple_kills += p.triple_kills;
+        delta.total_quadra_kills += p.quadra_kills;
+        delta.total_penta_kills += p.penta_kills;
+        if let Some(placement) = p.subteam_placement.filter(|placement| *placement > 0) {
+            delta.arena_placements[(placement.min(8) - 1) as usize] += 1;
+        }
+    }
+    if aggregates.is_empty() {
+        return Ok(());
+    }
+
+    let (summoner_ids, champion_ids, queue_ids, versions, deltas): (
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+        Vec<_>,
+    ) = aggregates
+        .into_iter()
+        .map(|((summoner_id, champion_id, queue_id, version), delta)| {
+            (summoner_id, champion_id, queue_id, version, delta)
+        })
+        .multiunzip();
+    let placement = |index: usize| {
+        deltas
+            .iter()
+            .map(|delta| delta.arena_placements[index])
+            .collect_vec()
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO summoner_champion_stats AS scs (summoner_id, champion_id, queue_id, version, total_matches,
+                                             total_wins, total_kills, total_deaths, total_assists, total_kda,
+                                             total_kill_participation, total_gold_earned, total_cs,
+                                             total_damage_dealt_to_champions, total_damage_taken,
+                                             total_double_kills, total_triple_kills, total_quadra_kills,
+                                             total_penta_kills, arena_placements)
+        SELECT summoner_id, champion_id, queue_id, version, total_matches, total_wins, total_kills,
+               total_deaths, total_assists, total_kda, total_kill_participation, total_gold_earned,
+               total_cs, total_damage_dealt_to_champions, total_damage_taken, total_double_kills,
+               total_triple_kills, total_quadra_kills, total_penta_kills,
+               ARRAY [p1, p2, p3, p4, p5, p6, p7, p8]
+        FROM UNNEST($1::INT[], $2::INT[], $3::INT[], $4::VARCHAR(5)[], $5::INT[], $6::INT[],
+                    $7::BIGINT[], $8::BIGINT[], $9::BIGINT[], $10::FLOAT8[], $11::FLOAT8[],
+                    $12::BIGINT[], $13::BIGINT[], $14::BIGINT[], $15::BIGINT[], $16::INT[],
+                    $17::INT[], $18::INT[], $19::INT[], $20::INT[], $21::INT[], $22::INT[],
+                    $23::INT[], $24::INT[], $25::INT[], $26::INT[], $27::INT[])
+                 AS data(summoner_id, champion_id, queue_id, version, total_matches, total_wins,
+                         total_kills, total_deaths, total_assists, total_kda, total_kill_participation,
+                         total_gold_earned, total_cs, total_damage_dealt_to_champions,
+                         total_damage_taken, total_double_kills, total_triple_kills,
+                         total_quadra_kills, total_penta_kills, p1, p2, p3, p4, p5, p6, p7, p8)
+        ON CONFLICT (summoner_id, champion_id, queue_id, version) DO UPDATE
+            SET total_matches                   = scs.total_matches + EXCLUDED.total_matches,
+                total_wins                      = scs.total_wins + EXCLUDED.total_wins,
+                total_kills                     = scs.total_kills + EXCLUDED.total_kills,
+                total_deaths                    = scs.total_deaths + EXCLUDED.total_deaths,
+                total_assists                   = scs.total_assists + EXCLUDED.total_assists,
+                total_kda                       = scs.total_kda + EXCLUDED.total_kda,
+                total_kill_participation        = scs.total_kill_participation + EXCLUDED.total_kill_participation,
+                total_gold_earned               = scs.total_gold_earned + EXCLUDED.total_gold_earned,
+                total_cs                        = scs.total_cs + EXCLUDED.total_cs,
+                total_damage_dealt_to_champions = scs.total_damage_dealt_to_champions + EXCLUDED.total_damage_dealt_to_champions,
+                total_damage_taken              = scs.total_damage_taken + EXCLUDED.total_damage_taken,
+                total_double_kills              = scs.total_double_kills + EXCLUDED.total_double_kills,
+                total_triple_kills              = scs.total_triple_kills + EXCLUDED.total_triple_kills,
+                total_quadra_kills              = scs.total_quadra_kills + EXCLUDED.total_quadra_kills,
+                total_penta_kills               = scs.total_penta_kills + EXCLUDED.total_penta_kills,
+                arena_placements                = ARRAY(SELECT current + added
+                                                        FROM unnest(scs.arena_placements, EXCLUDED.arena_placements)
+                                                                 WITH ORDINALITY AS t(current, added, position)
+                                                        ORDER BY position)
+        "#,
+    )
+    .bind(&summoner_ids)
+    .bind(&champion_ids)
+    .bind(&queue_ids)
+    .bind(&versions)
+    .bind(deltas.iter().map(|delta| delta.total_matches).collect_vec())
+    .bind(deltas.iter().map(|delta| delta.total_wins).collect_vec())
+    .bind(deltas.iter().map(|delta| delta.total_kills).collect_vec())
+    .bind(deltas.iter().map(|delta| delta.total_deaths).collect_vec())
+    .bind(deltas.iter().map(|delta| delta.total_assists).collect_vec())
+    .bind(deltas.iter().map(|delta| delta.total_kda).collect_vec())
+    .bind(
+        deltas
+            .iter()
+            .map(|delta| delta.total_kill_participation)
+            .collect_vec(),
+    )
+    .bind(deltas.iter().map(|delta| delta.total_gold_earned).collect_vec())
+    .bind(deltas.iter().map(|delta| delta.total_cs).collect_vec())
+    .bind(
+        deltas
+            .iter()
+            .map(|delta| delta.total_damage_dealt_to_champions)
+            .collect_vec(),
+    )
+    .bind(deltas.iter().map(|delta| delta.total_damage_taken).collect_vec())
+    .bind(deltas.iter().map(|delta| delta.total_double_kills).collect_vec())
+    .bind(deltas.iter().map(|delta| delta.total_triple_kills).collect_vec())
+    .bind(deltas.iter().map(|delta| delta.total_quadra_kills).collect_vec())
+    .bind(deltas.iter().map(|delta| delta.total_penta_kills).collect_vec())
+    .bind(placement(0))
+    .bind(placement(1))
+    .bind(placement(2))
+    .bind(placement(3))
+    .bind(placement(4))
+    .bind(placement(5))
+    .bind(placement(6))
+    .bind(placement(7))
+    .execute(&mut **tx)
+    .await?;
     Ok(())
 }
+
+#[derive(Default)]
+struct SummonerChampionStatsDelta {
+    total_matches: i32,
+    total_wins: i32,
+    total_kills: i64,
+    total_deaths: i64,
+    total_assists: i64,
+    total_kda: f64,
+    total_kill_participation: f64,
+    total_gold_earned: i64,
+    total_cs: i64,
+    total_damage_dealt_to_champions: i64,
+    total_damage_taken: i64,
+    total_double_kills: i32,
+    total_triple_kills: i32,
+    total_quadra_kills: i32,
+    total_penta_kills: i32,
+    arena_placements: [i32; 8],
+}
@@ -5,23 +5,48 @@ use itertools::Itertools;
 use riven::models::match_v5::Match;
 use sqlx::PgPool;
 
+/// Marks the matches as trashed, keeping the queue and the mode of the ones riot returned so they
+/// can be told apart later.
 pub async fn bulk_trashed_matches(
     db: &PgPool,
     matches: Vec<(Option<Match>, LolMatchNotUpdated)>,
 ) -> AppResult<()> {
-    let match_ids = matches
+    let (match_ids, queue_ids, modes): (Vec<i32>, Vec<Option<i32>>, Vec<Option<String>>) = matches
         .iter()
-        .map(|(_, db_match)| db_match.id)
-        .collect::<Vec<i32>>();
+        .map(|(match_data, db_match)| {
+            (
+                db_match.id,
+                match_data
+                    .as_ref()
+                    .map(|match_data| match_data.info.queue_id.0 as i32),
+                match_data
+                    .as_ref()
+                    .map(|match_data| match_data.info.game_mode.to_string()),
+            )
+        })
+        .multiunzip();
     let sql = r"
         UPDATE lol_matches
         SET
             trashed = true,
-            updated = true
-        WHERE id = ANY($1)
-        RETURNING id;
+            updated = true,
+            queue_id = data.queue_id,
+            game_mode = data.game_mode
+        FROM (
+            SELECT
+                UNNEST($1::INT[]) AS id,
+                UNNEST($2::INT[]) AS queue_id,
+                UNNEST($3::VARCHAR(15)[]) AS game_mode
+        ) AS data
+        WHERE lol_matches.id = data.id
+        RETURNING lol_matches.id;
         ";
-    sqlx::query(sql).bind(match_ids).fetch_all(db).await?;
+    sqlx::query(sql)
+        .bind(match_ids)
+        .bind(queue_ids)
+        .bind(modes)
+        .fetch_all(db)
+        .await?;
     Ok(())
 }
 
@@ -105,3 +130,57 @@ pub async fn bulk_update_matches(
 
     Ok(())
 }
+
+/// Stores the champion bans of the given matches, keyed by `lol_matches.id`.
+pub async fn bulk_insert_match_bans(
+    db: impl sqlx::PgExecutor<'_>,
+    matches: &[(i32, &Match)],
+) -> AppResult<()> {
+    let (lol_match_ids, team_ids, pick_turns, champion_ids): (
+        Vec<i32>,
+        Vec<i32>,
+        Vec<i32>,
+        Vec<i32>,
+    ) = matches
+        .iter()
+        .flat_map(|(lol_match_id, match_data)| {
+            match_data.info.teams.iter().flat_map(move |team| {
+                team.bans
+                    .iter()
+                    .filter(|ban| ban.champion_id.0 > 0)
+                    .map(move |ban| {
+                        (
+                            *lol_match_id,
+                            team.team_id as i32,
+                            ban.pick_turn,
+                            ban.champion_id.0 as i32,
+                        )
+                    })
+            })
+        })
+        .multiunzip();
+    if lol_match_ids.is_empty() {
+        return Ok(());
+    }
+    let sql = r"
+        INSERT INTO
+            lol_match_bans
+            (lol_match_id, team_id, pick_turn, champion_id)
+        SELECT * FROM UNNEST(
+            $1::INT[],
+            $2::INT[],
+            $3::INT[],
+            $4::INT[]
+        )
+        ON CONFLICT (lol_match_id, team_id, pick_turn) DO UPDATE
+            SET champion_id = EXCLUDED.champion_id;
+        ";
+    sqlx::query(sql)
+        .bind(lol_match_ids)
+        .bind(team_ids)
+        .bind(pick_turns)
+        .bind(champion_ids)
+        .execute(db)
+        .await?;
+    Ok(())
+}
@@ -47,7 +47,7 @@ pub async fn bulk_update_summoners(db: &sqlx::PgPool, summoners: &[TempSummoner]
 }
 
 pub async fn bulk_insert_summoners(
-    db: &sqlx::PgPool,
+    db: impl sqlx::PgExecutor<'_>,
     summoners: &[TempSummoner],
 ) -> AppResult<HashMap<String, SummonerFull>> {
     let (game_names, tag_lines, puuids, platforms, summoner_levels, profile_icon_ids, updated_ats) =
@@ -0,0 +1,261 @@
+use crate::backend::ssr::AppResult;
+use crate::backend::tasks::repair_summoner_champion_stats::rebuild_summoner_champion_stats;
+use crate::backend::tasks::update_matches::bulk_lol_match_participants::bulk_insert_lol_match_participants;
+use crate::backend::tasks::update_matches::bulk_lol_matches::bulk_insert_match_bans;
+use crate::backend::tasks::update_matches::bulk_summoners::bulk_insert_summoners;
+use crate::backend::tasks::update_matches::match_ingestion_queue::MatchJobPriority;
+use crate::backend::tasks::update_matches::{
+    build_match_participants, collect_match_summoners, fetch_existing_summoners, TempParticipant,
+};
+use crate::DB_CHUNK_SIZE;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use itertools::Itertools;
+use leptos::logging::log;
+use riven::models::match_v5::{Match, Timeline};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::io::Read;
+
+pub fn compress_json<T: Serialize>(value: &T) -> AppResult<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    serde_json::to_writer(&mut encoder, value)?;
+    Ok(encoder.finish()?)
+}
+
+pub fn decompress_json<T: DeserializeOwned>(data: &[u8]) -> AppResult<T> {
+    let mut json = Vec::new();
+    GzDecoder::new(data).read_to_end(&mut json)?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+/// Stores the raw match payloads, keyed by `lol_matches.id`.
+pub async fn bulk_archive_matches(db: &PgPool, matches: &[(i32, &Match)]) -> AppResult<()> {
+    if matches.is_empty() {
+        return Ok(());
+    }
+    let (lol_match_ids, match_jsons): (Vec<_>, Vec<_>) = matches
+        .iter()
+        .map(|(lol_match_id, match_data)| {
+            compress_json(match_data).map(|json| (*lol_match_id, json))
+        })
+        .collect::<AppResult<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+    let sql = r"
+        INSERT INTO
+            lol_match_archives
+            (lol_match_id, match_json)
+        SELECT * FROM UNNEST(
+            $1::INT[],
+            $2::BYTEA[]
+        )
+        ON CONFLICT (lol_match_id) DO UPDATE
+            SET match_json  = EXCLUDED.match_json,
+                archived_at = NOW();
+        ";
+    sqlx::query(sql)
+        .bind(lol_match_ids)
+        .bind(match_jsons)
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+pub async fn archive_timeline(
+    db: &PgPool,
+    lol_match_id: i32,
+    timeline: &Timeline,
+) -> AppResult<()> {
+    sqlx::query(
+        r"
+        INSERT INTO lol_match_archives (lol_match_id, timeline_json)
+        VALUES ($1, $2)
+        ON CONFLICT (lol_match_id) DO UPDATE
+            SET timeline_json = EXCLUDED.timeline_json,
+                archived_at   = NOW();
+        ",
+    )
+    .bind(lol_match_id)
+    .bind(compress_json(timeline)?)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// Rebuilds `lol_match_participants` and `lol_match_bans` of every archived, not trashed match from the stored payloads,
+/// without any riot api call. Summoners missing from the database are inserted from the match data.
+/// Each batch runs in its own transaction, a failing batch leaves its matches untouched.
+/// With `missing_team_position`, only the Summoner's Rift matches having participants without
+/// `team_position` are rebuilt. Returns the number of reprocessed matches.
+pub async fn reprocess_archived_matches(
+    db: &PgPool,
+    batch_size: i64,
+    missing_team_position: bool,
+) -> AppResult<usize> {
+    let mut last_lol_match_id = 0;
+    let mut reprocessed = 0;
+    loop {
+        let archives =
+            find_archived_matches(db, last_lol_match_id, batch_size, missing_team_position).await?;
+        let Some(last) = archives.last() else {
+            break;
+        };
+        last_lol_match_id = last.lol_match_id;
+
+        let match_datas = archives
+            .into_iter()
+            .filter_map(
+                |archive| match decompress_json::<Match>(archive.match_json.as_slice()) {
+                    Ok(match_data) => Some((archive.lol_match_id, match_data)),
+                    Err(e) => {
+                        log!(
+                            "Invalid archive for match {}: {:?}",
+                            archive.lol_match_id,
+                            e
+                        );
+                        None
+                    }
+                },
+            )
+            .collect_vec();
+
+        let mut tx = db.begin().await?;
+        let summoners =
+            collect_match_summoners(match_datas.iter().map(|(_, match_data)| match_data));
+        let puuids = summoners.keys().cloned().collect_vec();
+        let mut summoner_map: HashMap<String, i32> = fetch_existing_summoners(db, &puuids)
+            .await?
+            .into_iter()
+            .map(|(puuid, (id, _))| (puuid, id))
+            .collect();
+        let summoners_to_insert = summoners
+            .into_values()
+            .filter(|summoner| {
+                !summoner_map.contains_key(&summoner.puuid)
+                    && !summoner.game_name.trim().is_empty()
+                    && !summoner.tag_line.trim().is_empty()
+            })
+            .collect_vec();
+        for chunk in summoners_to_insert.chunks(DB_CHUNK_SIZE) {
+            summoner_map.extend(
+                bulk_insert_summoners(&mut *tx, chunk)
+                    .await?
+                    .into_iter()
+                    .map(|(puuid, summoner_full)| (puuid, summoner_full.id)),
+            );
+        }
+
+        let lol_match_ids = match_datas.iter().map(|(id, _)| *id).collect_vec();
+        let participants: Vec<TempParticipant> = match_datas
+            .iter()
+            .flat_map(|(lol_match_id, match_data)| {
+                build_match_participants(match_data, *lol_match_id, &summoner_map)
+            })
+            .collect();
+        let deleted_summoner_ids = sqlx::query_scalar::<_, i32>(
+            "DELETE FROM lol_match_participants WHERE lol_match_id = ANY($1) RETURNING summoner_id",
+        )
+        .bind(&lol_match_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+        for chunk in participants.chunks(DB_CHUNK_SIZE) {
+            bulk_insert_lol_match_participants(&mut tx, chunk).await?;
+        }
+        // the insert only adds to the aggregates, recompute them without the deleted rows
+        let affected_summoner_ids = deleted_summoner_ids
+            .into_iter()
+            .chain(
+                participants
+                    .iter()
+                    .map(|participant| participant.summoner_id),
+            )
+            .unique()
+            .collect_vec();
+        let match_bans = match_datas
+            .iter()
+            .map(|(lol_match_id, match_data)| (*lol_match_id, match_data))
+            .collect_vec();
+        for chunk in match_bans.chunks(DB_CHUNK_SIZE) {
+            bulk_insert_match_bans(&mut *tx, chunk).await?;
+        }
+        for chunk in affected_summoner_ids.chunks(DB_CHUNK_SIZE) {
+            rebuild_summoner_champion_stats(&mut tx, chunk).await?;
+        }
+        tx.commit().await?;
+        reprocessed += lol_match_ids.len();
+        log!("Reprocessed {} archived matches", reprocessed);
+    }
+    Ok(reprocessed)
+}
+
+async fn find_archived_matches(
+    db: &PgPool,
+    after_lol_match_id: i32,
+    limit: i64,
+    missing_team_position: bool,
+) -> AppResult<Vec<LolMatchArchiveModel>> {
+    sqlx::query_as::<_, LolMatchArchiveModel>(
+        r#"
+            SELECT lma.lol_match_id, lma.match_json
+            FROM lol_match_archives as lma
+                INNER JOIN lol_matches as lm ON lm.id = lma.lol_match_id
+            WHERE lma.lol_match_id > $1
+              AND lma.match_json IS NOT NULL
+              AND lm.trashed = false
+              AND ($3 = false OR (lm.map_id = 11 AND EXISTS (SELECT 1
+                                                            FROM lol_match_participants as lmp
+                                                            WHERE lmp.lol_match_id = lm.id
+                                                              AND lmp.team_position IS NULL)))
+            ORDER BY lma.lol_match_id
+            LIMIT $2;
+        "#,
+    )
+    .bind(after_lol_match_id)
+    .bind(limit)
+    .bind(missing_team_position)
+    .fetch_all(db)
+    .await
+    .map_err(|e| e.into())
+}
+
+/// Queues for a refetch from the riot api the stored, not trashed matches without archive, the
+/// update matches task replaces their participants. With `missing_team_position`, only the
+/// Summoner's Rift matches having participants without `team_position`. Returns the number of queued matches.
+pub async fn requeue_unarchived_matches(
+    db: &PgPool,
+    missing_team_position: bool,
+) -> AppResult<u64> {
+    let result = sqlx::query(
+        r#"
+            INSERT INTO match_ingestion_jobs (lol_match_id, priority)
+            SELECT lm.id, $1
+            FROM lol_matches as lm
+            WHERE lm.updated = true
+              AND lm.trashed = false
+              AND NOT EXISTS (SELECT 1
+                              FROM lol_match_archives as lma
+                              WHERE lma.lol_match_id = lm.id
+                                AND lma.match_json IS NOT NULL)
+              AND ($2 = false OR (lm.map_id = 11 AND EXISTS (SELECT 1
+                                                            FROM lol_match_participants as lmp
+                                                            WHERE lmp.lol_match_id = lm.id
+                                                              AND lmp.team_position IS NULL)))
+            ON CONFLICT (lol_match_id) DO NOTHING;
+        "#,
+    )
+    .bind(MatchJobPriority::Background as i16)
+    .bind(missing_team_position)
+    .execute(db)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+#[derive(sqlx::FromRow)]
+pub struct LolMatchArchiveModel {
+    pub lol_match_id: i32,
+    pub match_json: Vec<u8>,
+}
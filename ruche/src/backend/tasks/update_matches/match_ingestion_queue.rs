@@ -0,0 +1,236 @@
+use crate::backend::ssr::{AppResult, PlatformRouteDb};
+use crate::backend::tasks::update_matches::LolMatchNotUpdated;
+use chrono::NaiveDateTime;
+use sqlx::PgPool;
+use std::collections::HashSet;
+
+/// A job is marked as failed, and no longer retried, after this many attempts.
+pub const MAX_JOB_ATTEMPTS: i32 = 6;
+/// Delay before the first retry, doubled on each following attempt.
+pub const BASE_RETRY_DELAY_SECS: i32 = 30;
+pub const MAX_RETRY_DELAY_SECS: i32 = 6 * 60 * 60;
+/// Riot never publishes a match as soon as the game ends.
+pub const ENDED_LIVE_GAME_DELAY_SECS: i32 = 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(i16)]
+pub enum MatchJobPriority {
+    Background = 0,
+    EndedLiveGame = 5,
+    LiveScout = 7,
+    User = 10,
+}
+
+/// Inserts the unknown match ids into `lol_matches` and queues them for ingestion.
+/// Already queued jobs are raised to `priority` if it is higher.
+pub async fn enqueue_match_ids(
+    db: &PgPool,
+    match_ids: &[String],
+    priority: MatchJobPriority,
+) -> AppResult<()> {
+    enqueue_match_jobs(db, match_ids, priority, 0, false).await
+}
+
+/// Queues the games that just ended, they are first fetched after `ENDED_LIVE_GAME_DELAY_SECS`
+/// and retried with the backoff until riot publishes them.
+pub async fn enqueue_ended_live_games(db: &PgPool, match_ids: &[String]) -> AppResult<()> {
+    if match_ids.is_empty() {
+        return Ok(());
+    }
+    enqueue_match_jobs(
+        db,
+        match_ids,
+        MatchJobPriority::EndedLiveGame,
+        ENDED_LIVE_GAME_DELAY_SECS,
+        true,
+    )
+    .await
+}
+
+/// Inserts the unknown match ids into `lol_matches` and queues the not yet ingested ones, first
+/// attempted after `delay_secs`. A job already queued keeps the highest priority and waits for the
+/// publication once asked to.
+async fn enqueue_match_jobs(
+    db: &PgPool,
+    match_ids: &[String],
+    priority: MatchJobPriority,
+    delay_secs: i32,
+    wait_for_publication: bool,
+) -> AppResult<()> {
+    let platforms = match_ids
+        .iter()
+        .map(|x| {
+            let match_id_split = x.split("_").collect::<Vec<&str>>();
+            PlatformRouteDb::from_raw_str(match_id_split[0])
+        })
+        .collect::<Vec<_>>();
+    let sql = r"
+        WITH inserted AS (
+            INSERT INTO
+                lol_matches
+                (match_id, platform)
+            SELECT * FROM UNNEST(
+                $1::VARCHAR(17)[],
+                $2::platform_type[]
+            )
+            ON CONFLICT (match_id) DO NOTHING
+            RETURNING id
+        ), to_queue AS (
+            SELECT id FROM inserted
+            UNION
+            SELECT id FROM lol_matches WHERE match_id = ANY($1) AND updated = false
+        )
+        INSERT INTO match_ingestion_jobs (lol_match_id, priority, next_attempt_at, wait_for_publication)
+        SELECT id, $3, NOW() + make_interval(secs => $4), $5 FROM to_queue
+        ON CONFLICT (lol_match_id) DO UPDATE
+            SET priority = GREATEST(match_ingestion_jobs.priority, EXCLUDED.priority),
+                wait_for_publication = match_ingestion_jobs.wait_for_publication OR EXCLUDED.wait_for_publication;
+        ";
+    sqlx::query(sql)
+        .bind(match_ids)
+        .bind(platforms)
+        .bind(priority as i16)
+        .bind(delay_secs as f64)
+        .bind(wait_for_publication)
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+/// Returns the due jobs, user-triggered first, then the ones waiting for the longest time.
+pub async fn get_due_match_jobs(db: &PgPool, limit: i32) -> AppResult<Vec<LolMatchNotUpdated>> {
+    sqlx::query_as::<_, LolMatchNotUpdated>(
+        r#"
+            SELECT lm.id, lm.match_id, lm.platform, lm.updated, mij.wait_for_publication
+            FROM match_ingestion_jobs as mij
+                INNER JOIN lol_matches as lm ON lm.id = mij.lol_match_id
+            WHERE mij.failed = false
+              AND mij.next_attempt_at <= NOW()
+            ORDER BY mij.priority DESC, mij.next_attempt_at, lm.match_id DESC
+            LIMIT $1;
+        "#,
+    )
+    .bind(limit)
+    .fetch_all(db)
+    .await
+    .map_err(|e| e.into())
+}
+
+/// Returns the riot match ids among `match_ids` whose job is still queued, neither completed nor
+/// failed for good.
+pub async fn find_open_match_jobs(db: &PgPool, match_ids: &[String]) -> AppResult<HashSet<String>> {
+    sqlx::query_scalar::<_, String>(
+        r#"
+            SELECT lm.match_id
+            FROM match_ingestion_jobs as mij
+                INNER JOIN lol_matches as lm ON lm.id = mij.lol_match_id
+            WHERE lm.match_id = ANY($1)
+              AND mij.failed = false
+        "#,
+    )
+    .bind(match_ids)
+    .fetch_all(db)
+    .await
+    .map(|match_ids| match_ids.into_iter().collect())
+    .map_err(|e| e.into())
+}
+
+pub async fn complete_match_jobs(db: &PgPool, lol_match_ids: &[i32]) -> AppResult<()> {
+    if lol_match_ids.is_empty() {
+        return Ok(());
+    }
+    sqlx::query("DELETE FROM match_ingestion_jobs WHERE lol_match_id = ANY($1)")
+        .bind(lol_match_ids)
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+/// Records a failed attempt for each job and schedules the next one with an exponential backoff.
+pub async fn fail_match_jobs(db: &PgPool, failures: &[(i32, String)]) -> AppResult<()> {
+    if failures.is_empty() {
+        return Ok(());
+    }
+    let (lol_match_ids, errors): (Vec<_>, Vec<_>) = failures.iter().cloned().unzip();
+    let sql = r"
+        UPDATE match_ingestion_jobs
+        SET
+            attempts = match_ingestion_jobs.attempts + 1,
+            last_error = data.last_error,
+            failed = match_ingestion_jobs.attempts + 1 >= $3,
+            next_attempt_at = NOW() + make_interval(secs => LEAST(
+                $4::FLOAT8 * POWER(2, match_ingestion_jobs.attempts),
+                $5::FLOAT8
+            ))
+        FROM (
+            SELECT
+                UNNEST($1::INT[]) AS lol_match_id,
+                UNNEST($2::TEXT[]) AS last_error
+        ) AS data
+        WHERE match_ingestion_jobs.lol_match_id = data.lol_match_id;
+        ";
+    sqlx::query(sql)
+        .bind(lol_match_ids)
+        .bind(errors)
+        .bind(MAX_JOB_ATTEMPTS)
+        .bind(BASE_RETRY_DELAY_SECS as f64)
+        .bind(MAX_RETRY_DELAY_SECS as f64)
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+pub async fn find_failed_match_jobs(db: &PgPool, limit: i64) -> AppResult<Vec<MatchJobModel>> {
+    sqlx::query_as::<_, MatchJobModel>(
+        r#"
+            SELECT
+                lm.match_id,
+                mij.priority,
+                mij.attempts,
+                mij.last_error,
+                mij.next_attempt_at,
+                mij.created_at
+            FROM match_ingestion_jobs as mij
+                INNER JOIN lol_matches as lm ON lm.id = mij.lol_match_id
+            WHERE mij.failed = true
+            ORDER BY mij.next_attempt_at DESC
+            LIMIT $1;
+        "#,
+    )
+    .bind(limit)
+    .fetch_all(db)
+    .await
+    .map_err(|e| e.into())
+}
+
+/// Resets the failed jobs for the given riot match ids, or every failed job when `match_ids` is empty.
+/// Returns the number of requeued jobs.
+pub async fn requeue_failed_match_jobs(db: &PgPool, match_ids: &[String]) -> AppResult<u64> {
+    let sql = r"
+        UPDATE match_ingestion_jobs
+        SET
+            attempts = 0,
+            failed = false,
+            next_attempt_at = NOW()
+        FROM lol_matches
+        WHERE lol_matches.id = match_ingestion_jobs.lol_match_id
+          AND match_ingestion_jobs.failed = true
+          AND (cardinality($1::VARCHAR(17)[]) = 0 OR lol_matches.match_id = ANY($1));
+        ";
+    sqlx::query(sql)
+        .bind(match_ids)
+        .execute(db)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(|e| e.into())
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct MatchJobModel {
+    pub match_id: String,
+    pub priority: i16,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
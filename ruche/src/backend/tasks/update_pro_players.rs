@@ -86,6 +86,10 @@ impl Task for UpdateProPlayerTask {
     fn allow_concurrent(&self) -> bool {
         false // Do not allow concurrent executions
     }
+
+    fn cluster_singleton(&self) -> bool {
+        true
+    }
 }
 
 pub async fn update_pro_player(db: &PgPool, api: RiotApiState) -> AppResult<()> {
@@ -8,7 +8,10 @@ pub const DB_CHUNK_SIZE: usize = 500;
 
 #[cfg(feature = "ssr")]
 pub mod ssr {
+    use crate::backend::health;
     use crate::backend::live_game_cache;
+    use crate::backend::matches_stats_cache;
+    use crate::backend::server_fn_cache;
     use crate::backend::server_fns::get_encounter::ssr::find_summoner_puuid_by_id;
     use crate::backend::server_fns::get_live_game::ssr;
     use crate::utils::{Puuid, SSEEvent};
@@ -40,6 +43,8 @@ pub mod ssr {
 
     pub type RiotApiState = Arc<RiotApi>;
     pub type SubscriberMap = DashMap<i32, Sender<SSEEvent>>;
+    /// Summoner page views since the last background refresh, by summoner id.
+    pub type SummonerPageViews = DashMap<i32, u32>;
 
     #[derive(Clone, axum::extract::FromRef)]
     pub struct AppState {
@@ -47,8 +52,17 @@ pub mod ssr {
         pub riot_api: RiotApiState,
         pub db: PgPool,
         pub live_game_cache: Arc<live_game_cache::LiveGameCache>,
+        pub matches_stats_cache: Arc<matches_stats_cache::MatchesStatsCache>,
+        pub server_fn_cache: Arc<server_fn_cache::ServerFnCache>,
         pub max_matches: usize,
+        pub summoner_update_cooldown: Duration,
+        /// Keep the compressed raw match and timeline payloads, see `match_archive`.
+        pub match_archive_enabled: bool,
         pub summoner_updated_sender: Arc<SubscriberMap>,
+        pub summoner_page_views: Arc<SummonerPageViews>,
+        pub health: Arc<health::HealthState>,
+        /// Bearer token of the `/stats/*` endpoints, they are disabled without it.
+        pub stats_token: Option<String>,
     }
 
     pub fn init_riot_api() -> RiotApi {
@@ -110,6 +124,7 @@ pub mod ssr {
                         let inner_sender = sender.clone();
                         let db = state.db.clone();
                         let riot_api = state.riot_api.clone();
+                        let health = state.health.clone();
                         let live_game_cache = state.live_game_cache.clone();
                         let platform_route = PlatformRoute::from(platform_route.as_str());
                         tokio::spawn(async move {
@@ -119,10 +134,15 @@ pub mod ssr {
                                     .unwrap()
                                     .as_str(),
                             );
-                            let live_game =
-                                ssr::get_live_game_data(&db, &riot_api, puuid, platform_route)
-                                    .await
-                                    .unwrap();
+                            let live_game = ssr::get_live_game_data(
+                                &db,
+                                &riot_api,
+                                &health,
+                                puuid,
+                                platform_route,
+                            )
+                            .await
+                            .unwrap();
                             if let Some((summoner_ids, live_game)) = live_game {
                                 live_game_cache.set_game_data(
                                     live_game.game_id,
@@ -173,6 +193,9 @@ pub mod ssr {
                             Some(Ok(SSEEvent::LiveGame(None))) => {
                                 Some(SSEEvent::LiveGame(None))
                             }
+                            Some(Ok(SSEEvent::LiveScout(progress))) => {
+                                Some(SSEEvent::LiveScout(progress))
+                            }
                             _ => None,
                         };
                     }
@@ -192,6 +215,47 @@ pub mod ssr {
         Sse::new(stream).keep_alive(KeepAlive::default())
     }
 
+    /// The internal endpoints answer 404 unless `STATS_TOKEN` is set and sent as bearer token.
+    fn authorize_stats(state: &AppState, headers: &http::HeaderMap) -> Result<(), StatusCode> {
+        let token = state.stats_token.as_deref().ok_or(StatusCode::NOT_FOUND)?;
+        let authorized = headers
+            .get(http::header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|value| value == token);
+        if authorized {
+            Ok(())
+        } else {
+            Err(StatusCode::UNAUTHORIZED)
+        }
+    }
+
+    /// Hit and miss counters of the server fn response cache, as plain text.
+    pub async fn server_fn_cache_stats(
+        State(state): State<AppState>,
+        headers: http::HeaderMap,
+    ) -> Result<String, StatusCode> {
+        authorize_stats(&state, &headers)?;
+        let stats = state.server_fn_cache.stats();
+        Ok(format!(
+            "enabled {}\nentries {}\nhits {}\nmisses {}\n",
+            stats.enabled, stats.entries, stats.hits, stats.misses
+        ))
+    }
+
+    /// Size, hit/miss and eviction counters of the live game cache, as plain text.
+    pub async fn live_game_cache_stats(
+        State(state): State<AppState>,
+        headers: http::HeaderMap,
+    ) -> Result<String, StatusCode> {
+        authorize_stats(&state, &headers)?;
+        let stats = state.live_game_cache.stats();
+        Ok(format!(
+            "games {}\nsummoners {}\nhits {}\nmisses {}\nexpired {}\nevicted {}\n",
+            stats.games, stats.summoners, stats.hits, stats.misses, stats.expired, stats.evicted
+        ))
+    }
+
     pub async fn serve(
         app: Router,
         is_prod: bool,
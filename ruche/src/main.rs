@@ -3,6 +3,7 @@
 async fn main() -> ruche::backend::ssr::AppResult<()> {
     use axum::routing::get;
     use axum::Router;
+    use clap::Parser;
     use dashmap::DashMap;
     use dotenv::dotenv;
     use leptos::logging::log;
@@ -10,15 +11,25 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
     use leptos_axum::{generate_route_list, LeptosRoutes};
     use memory_serve::{load_assets, CacheControl, MemoryServe};
     use ruche::app::*;
+    use ruche::backend::cli::{run_command, Cli};
+    use ruche::backend::health::{healthz, readyz, HealthState};
     use ruche::backend::live_game_cache::LiveGameCache;
+    use ruche::backend::matches_stats_cache::MatchesStatsCache;
+    use ruche::backend::server_fn_cache::ServerFnCache;
+    use ruche::backend::sse_events::forward_sse_events;
     use ruche::backend::task_director::TaskDirector;
     use ruche::backend::tasks::generate_sitemap::GenerateSiteMapTask;
     use ruche::backend::tasks::handle_live_game_cache::HandleLiveGameCacheTask;
+    use ruche::backend::tasks::refresh_summoners::{RefreshSummonersTask, ScoutLiveGamesTask};
+    use ruche::backend::tasks::repair_summoner_champion_stats::SummonerChampionStatsRepairTask;
     use ruche::backend::tasks::sse_broadcast_match_updated_cleanup::SummonerUpdatedSenderCleanupTask;
+    use ruche::backend::tasks::update_champion_stats::UpdateChampionStatsTask;
     use ruche::backend::tasks::update_matches::UpdateMatchesTask;
     use ruche::backend::tasks::update_pro_players::UpdateProPlayerTask;
     use ruche::ssr::get_sitemap;
+    use ruche::ssr::live_game_cache_stats;
     use ruche::ssr::serve;
+    use ruche::ssr::server_fn_cache_stats;
     use ruche::ssr::sse_broadcast_match_updated;
     use ruche::ssr::AppState;
     use ruche::ssr::{init_database, init_riot_api};
@@ -31,6 +42,12 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
     use tower_http::CompressionLevel;
 
     dotenv().ok();
+    let cli = Cli::parse();
+    if let Some(command) = cli.command {
+        let db = init_database().await;
+        return run_command(&db, command).await;
+    }
+
     let conf = get_configuration(None).unwrap();
     let mut leptos_options = conf.leptos_options;
     let env_type = dotenv::var("ENV").unwrap_or("DEV".to_string());
@@ -40,18 +57,122 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
         .unwrap_or_else(|_| "1500".to_string())
         .parse()?;
 
+    let summoner_update_cooldown = tokio::time::Duration::from_secs(
+        dotenv::var("SUMMONER_UPDATE_COOLDOWN")
+            .unwrap_or_else(|_| "120".to_string())
+            .parse()?,
+    );
+
     let update_interval_duration = tokio::time::Duration::from_secs(
         dotenv::var("MATCH_TASK_UPDATE_INTERVAL")
             .unwrap_or_else(|_| "5".to_string())
             .parse()?,
     );
 
+    let refresh_summoners_interval_duration = tokio::time::Duration::from_secs(
+        dotenv::var("REFRESH_SUMMONERS_INTERVAL")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse()?,
+    );
+
+    let refresh_summoners_api_budget = dotenv::var("REFRESH_SUMMONERS_API_BUDGET")
+        .unwrap_or_else(|_| "30".to_string())
+        .parse()?;
+
+    let live_scout_max_matches = dotenv::var("LIVE_SCOUT_MAX_MATCHES")
+        .unwrap_or_else(|_| "20".to_string())
+        .parse()?;
+
+    let refresh_summoners_min_staleness = tokio::time::Duration::from_secs(
+        dotenv::var("REFRESH_SUMMONERS_MIN_STALENESS")
+            .unwrap_or_else(|_| "1800".to_string())
+            .parse()?,
+    );
+
     let live_game_cache_interval_duration = tokio::time::Duration::from_secs(
         dotenv::var("LIVE_GAME_CACHE_UPDATE_INTERVAL")
             .unwrap_or_else(|_| "30".to_string())
             .parse()?,
     );
 
+    let live_game_poll_max_interval = tokio::time::Duration::from_secs(
+        dotenv::var("LIVE_GAME_POLL_MAX_INTERVAL")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse()?,
+    );
+
+    let live_game_poll_api_budget = dotenv::var("LIVE_GAME_POLL_API_BUDGET")
+        .unwrap_or_else(|_| "100".to_string())
+        .parse()?;
+
+    let live_game_cache_ttl = std::time::Duration::from_secs(
+        dotenv::var("LIVE_GAME_CACHE_TTL")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse()?,
+    );
+
+    let live_game_cache_max_games = dotenv::var("LIVE_GAME_CACHE_MAX_GAMES")
+        .unwrap_or_else(|_| "1000".to_string())
+        .parse()?;
+
+    let matches_stats_cache_ttl = std::time::Duration::from_secs(
+        dotenv::var("MATCHES_STATS_CACHE_TTL")
+            .unwrap_or_else(|_| "600".to_string())
+            .parse()?,
+    );
+
+    let server_fn_cache_enabled = dotenv::var("SERVER_FN_CACHE_ENABLED")
+        .unwrap_or("true".to_string())
+        .eq("true");
+
+    let server_fn_cache_max_entries = dotenv::var("SERVER_FN_CACHE_MAX_ENTRIES")
+        .unwrap_or_else(|_| "10000".to_string())
+        .parse()?;
+
+    let server_fn_cache_ttl = std::time::Duration::from_secs(
+        dotenv::var("SERVER_FN_CACHE_TTL")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse()?,
+    );
+
+    let stats_token = dotenv::var("STATS_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty());
+
+    let health_riot_api_window = std::time::Duration::from_secs(
+        dotenv::var("HEALTH_RIOT_API_WINDOW")
+            .unwrap_or_else(|_| "600".to_string())
+            .parse()?,
+    );
+
+    let champion_stats_interval_duration = tokio::time::Duration::from_secs(
+        dotenv::var("CHAMPION_STATS_INTERVAL")
+            .unwrap_or_else(|_| "3600".to_string())
+            .parse()?,
+    );
+
+    let champion_stats_recent_days = dotenv::var("CHAMPION_STATS_RECENT_DAYS")
+        .unwrap_or_else(|_| "30".to_string())
+        .parse()?;
+
+    let champion_stats_task_on_startup = dotenv::var("CHAMPION_STATS_TASK_ON_STARTUP")
+        .unwrap_or("false".to_string())
+        .eq("true");
+
+    let summoner_champion_stats_repair_interval = tokio::time::Duration::from_secs(
+        dotenv::var("SUMMONER_CHAMPION_STATS_REPAIR_INTERVAL")
+            .unwrap_or_else(|_| "300".to_string())
+            .parse()?,
+    );
+
+    let summoner_champion_stats_repair_batch = dotenv::var("SUMMONER_CHAMPION_STATS_REPAIR_BATCH")
+        .unwrap_or_else(|_| "500".to_string())
+        .parse()?;
+
+    let match_archive_enabled = dotenv::var("MATCH_ARCHIVE_ENABLED")
+        .unwrap_or("false".to_string())
+        .eq("true");
+
     let lol_pro_task_on_startup = dotenv::var("LOL_PRO_TASK_ON_STARTUP")
         .unwrap_or("false".to_string())
         .eq("true");
@@ -62,6 +183,24 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
     log!("Starting Ruche as {}", env_type);
     log!("Update interval duration: {:?}", update_interval_duration);
     log!("Max matches: {}", max_matches);
+    log!("Summoner update cooldown: {:?}", summoner_update_cooldown);
+    log!(
+        "Refresh summoners every {:?} with an api budget of {}",
+        refresh_summoners_interval_duration,
+        refresh_summoners_api_budget
+    );
+    log!(
+        "Champion stats every {:?} over the patches of the last {} days",
+        champion_stats_interval_duration,
+        champion_stats_recent_days
+    );
+    log!(
+        "Server fn cache enabled: {} ({} entries, {:?} ttl)",
+        server_fn_cache_enabled,
+        server_fn_cache_max_entries,
+        server_fn_cache_ttl
+    );
+    log!("Match archive enabled: {}", match_archive_enabled);
     log!("LOL Pro Task on Startup: {}", lol_pro_task_on_startup);
     log!("Site Map Task on Startup: {}", site_map_task_on_startup);
 
@@ -77,14 +216,28 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
     let site_address = leptos_options.site_addr;
     let db = init_database().await;
     let riot_api = Arc::new(init_riot_api());
-    let live_game_cache = Arc::new(LiveGameCache::new(std::time::Duration::from_secs(60)));
+    let live_game_cache = Arc::new(LiveGameCache::new(
+        live_game_cache_ttl,
+        live_game_cache_max_games,
+    ));
+    let matches_stats_cache = Arc::new(MatchesStatsCache::new(matches_stats_cache_ttl));
+    let server_fn_cache = Arc::new(ServerFnCache::new(
+        server_fn_cache_enabled,
+        server_fn_cache_max_entries,
+        server_fn_cache_ttl,
+    ));
     let summoner_updated_sender = Arc::new(DashMap::new());
-    let mut task_director = TaskDirector::default();
+    let summoner_page_views = Arc::new(DashMap::new());
+    let health = Arc::new(HealthState::new(health_riot_api_window));
+    let mut task_director = TaskDirector::new(db.clone(), Arc::clone(&health));
     task_director.add_task(HandleLiveGameCacheTask::new(
         db.clone(),
         riot_api.clone(),
         live_game_cache.clone(),
         summoner_updated_sender.clone(),
+        Arc::clone(&health),
+        live_game_poll_max_interval,
+        live_game_poll_api_budget,
         live_game_cache_interval_duration,
     ));
 
@@ -93,7 +246,52 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
         db.clone(),
         Arc::clone(&riot_api),
         update_interval_duration,
+        Arc::clone(&matches_stats_cache),
+        Arc::clone(&server_fn_cache),
+        Arc::clone(&health),
+        match_archive_enabled,
+    ));
+
+    // keep viewed, followed live and pro summoners fresh without waiting for an update click
+    task_director.add_task(RefreshSummonersTask::new(
+        db.clone(),
+        Arc::clone(&riot_api),
+        Arc::clone(&summoner_page_views),
         Arc::clone(&summoner_updated_sender),
+        Arc::clone(&health),
+        max_matches,
+        refresh_summoners_api_budget,
+        refresh_summoners_min_staleness,
+        refresh_summoners_interval_duration,
+    ));
+
+    // the scouts live in the live game cache of the instance they were requested from
+    task_director.add_task(ScoutLiveGamesTask::new(
+        db.clone(),
+        Arc::clone(&riot_api),
+        Arc::clone(&summoner_page_views),
+        Arc::clone(&live_game_cache),
+        Arc::clone(&health),
+        live_scout_max_matches,
+        refresh_summoners_api_budget,
+        refresh_summoners_min_staleness,
+        refresh_summoners_interval_duration,
+    ));
+
+    // site-wide champion aggregates of the tier list
+    task_director.add_task(UpdateChampionStatsTask::new(
+        db.clone(),
+        champion_stats_recent_days,
+        champion_stats_interval_duration,
+        champion_stats_task_on_startup,
+    ));
+
+    // fix per summoner champion aggregates that drifted from the participant rows
+    task_director.add_task(SummonerChampionStatsRepairTask::new(
+        db.clone(),
+        summoner_champion_stats_repair_batch,
+        summoner_champion_stats_repair_interval,
+        false,
     ));
 
     // cleanup sse_broadcast_match_updated subscriptions
@@ -118,13 +316,29 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
         task_director.run().await;
     });
 
+    // sse events are published through postgres NOTIFY so that any instance can run the tasks
+    tokio::spawn(forward_sse_events(
+        db.clone(),
+        Arc::clone(&summoner_updated_sender),
+        Arc::clone(&matches_stats_cache),
+        Arc::clone(&server_fn_cache),
+        Arc::clone(&live_game_cache),
+    ));
+
     let app_state = AppState {
         leptos_options: leptos_options.clone(),
         riot_api,
         db,
         live_game_cache,
+        matches_stats_cache,
+        server_fn_cache,
         max_matches,
+        summoner_update_cooldown,
+        match_archive_enabled,
         summoner_updated_sender,
+        summoner_page_views,
+        health,
+        stats_token,
     };
 
     let routes = generate_route_list(App);
@@ -160,7 +374,11 @@ async fn main() -> ruche::backend::ssr::AppResult<()> {
             "/sse/match_updated/:platform_route/:summoner_id",
             get(sse_broadcast_match_updated),
         )
+        .route("/stats/server_fn_cache", get(server_fn_cache_stats))
+        .route("/stats/live_game_cache", get(live_game_cache_stats))
         .route("/sitemap-index.xml", get(get_sitemap))
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
         .fallback(leptos_axum::file_and_error_handler::<LeptosOptions, _>(
             shell,
         ))
@@ -112,6 +112,17 @@ pub fn format_with_spaces(number: u32) -> String {
     result
 }
 
+pub fn format_placement(placement: u16) -> String {
+    // 1 -> 1st, 2 -> 2nd, 3 -> 3rd, 4 -> 4th
+    let suffix = match placement {
+        1 => "st",
+        2 => "nd",
+        3 => "rd",
+        _ => "th",
+    };
+    format!("{}{}", placement, suffix)
+}
+
 pub fn summoner_to_slug(game_name: &str, tag_line: &str) -> String {
     format!(
         "{}-{}",
@@ -133,6 +144,47 @@ pub fn parse_summoner_slug(slug: &str) -> (String, String) {
     (game_name, String::new())
 }
 
+pub const MULTISEARCH_MAX_PLAYERS: usize = 10;
+
+/// Extracts riot ids from a pasted champion select lobby ("Name#TAG joined the lobby" lines)
+/// or from a comma separated list of riot ids. Lines are read in order, a player who left the lobby
+/// is removed until they join again, and duplicates are removed.
+pub fn parse_multisearch_riot_ids(text: &str) -> Vec<(String, String)> {
+    let mut riot_ids: Vec<(String, String)> = Vec::new();
+    for entry in text.split(['\n', ',']) {
+        let entry = entry.trim();
+        let Some((game_name, rest)) = entry.split_once('#') else {
+            continue;
+        };
+        let game_name = game_name.trim();
+        let tag_line = rest.split_whitespace().next().unwrap_or_default();
+        if game_name.is_empty() || tag_line.is_empty() {
+            continue;
+        }
+        let position = riot_ids.iter().position(|(gn, tl)| {
+            gn.eq_ignore_ascii_case(game_name) && tl.eq_ignore_ascii_case(tag_line)
+        });
+        match (entry.ends_with("left the lobby"), position) {
+            (true, Some(position)) => {
+                riot_ids.remove(position);
+            }
+            (false, None) => riot_ids.push((game_name.to_string(), tag_line.to_string())),
+            _ => {}
+        }
+    }
+    riot_ids.truncate(MULTISEARCH_MAX_PLAYERS);
+    riot_ids
+}
+
+pub fn multisearch_url(platform: &str, riot_ids: &str, current_riot_id: &str) -> String {
+    format!(
+        "/platform/{}/multisearch?riot_ids={}&me={}",
+        platform,
+        urlencoding::encode(riot_ids),
+        urlencoding::encode(current_riot_id)
+    )
+}
+
 pub fn summoner_url(platform: &str, game_name: &str, tag_line: &str) -> String {
     format!(
         "/platform/{}/summoners/{}",
@@ -178,6 +230,15 @@ pub fn format_float_to_2digits(value: f32) -> String {
 pub enum SSEEvent {
     LiveGame(Option<u16>),
     SummonerMatches(u16),
+    LiveScout(LiveScoutProgress),
+}
+
+/// Progress of a live game scout, `version` changes each time more participant matches are stored.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default, Encode, Decode)]
+pub struct LiveScoutProgress {
+    pub version: u16,
+    pub done: u16,
+    pub total: u16,
 }
 
 impl SSEEvent {
@@ -188,6 +249,10 @@ impl SSEEvent {
                 format!("0:{}", value.map(|v| v.to_string()).unwrap_or_default())
             }
             SSEEvent::SummonerMatches(value) => format!("1:{}", value),
+            SSEEvent::LiveScout(progress) => format!(
+                "2:{}/{}/{}",
+                progress.version, progress.done, progress.total
+            ),
         }
     }
 
@@ -204,6 +269,19 @@ impl SSEEvent {
                 Ok(value) => Ok(SSEEvent::SummonerMatches(value)),
                 Err(_) => Err("Invalid u16 value"),
             },
+            "2" => match parts[1]
+                .split('/')
+                .map(|part| part.parse::<u16>())
+                .collect::<Result<Vec<_>, _>>()
+                .as_deref()
+            {
+                Ok([version, done, total]) => Ok(SSEEvent::LiveScout(LiveScoutProgress {
+                    version: *version,
+                    done: *done,
+                    total: *total,
+                })),
+                _ => Err("Invalid live scout progress"),
+            },
             _ => Err("Invalid event type"),
         }
     }
@@ -1,9 +1,11 @@
 use leptos::prelude::ElementChild;
-use leptos::prelude::{Children, ClassAttribute, Get, ReadSignal, Show};
+use leptos::prelude::{use_context, Children, ClassAttribute, Get, OnAttribute, ReadSignal, Show};
 use leptos::{component, view, IntoView, Params};
 use leptos_router::params::Params;
 
+use crate::views::components::asset_tooltip::{AssetTooltipContext, AssetTooltipTarget};
 use bitcode::{Decode, Encode};
+use common::consts::augment::Augment;
 use common::consts::champion::Champion;
 use common::consts::item::Item;
 use common::consts::perk::Perk;
@@ -14,7 +16,9 @@ use leptos::prelude::CustomAttribute;
 use leptos_router::NavigateOptions;
 use std::fmt::{Debug, Formatter};
 
+pub mod champion_tier_list_page;
 pub mod components;
+pub mod multisearch_page;
 pub mod platform_type_page;
 pub mod summoner_page;
 
@@ -52,14 +56,26 @@ pub fn ImgBg(
     class: Option<String>,
     parent_class: Option<String>,
     children: Option<Children>,
+    #[prop(optional)] tooltip: Option<AssetTooltipTarget>,
 ) -> impl IntoView {
     let class_ = class.unwrap_or_default();
     let children = children.map(|c| c());
+    let tooltip_context = use_context::<AssetTooltipContext>();
+    let show_tooltip = move |ev: leptos::ev::MouseEvent| {
+        if let (Some(context), Some(target)) = (tooltip_context, tooltip) {
+            context.show(target, ev.client_x(), ev.client_y());
+        }
+    };
+    let hide_tooltip = move |_: leptos::ev::MouseEvent| {
+        if let Some(context) = tooltip_context {
+            context.hide();
+        }
+    };
     view! {
         {
             match parent_class{
-                None => Either::Right(view!{<div class=class_ /> {children}}),
-                Some(parent_class) => Either::Left(view!{<div class=parent_class><div class=class_ />{children}</div>})
+                None => Either::Right(view!{<div class=class_ on:mouseenter=show_tooltip on:mouseleave=hide_tooltip /> {children}}),
+                Some(parent_class) => Either::Left(view!{<div class=parent_class><div class=class_ on:mouseenter=show_tooltip on:mouseleave=hide_tooltip />{children}</div>})
             }
         }
     }
@@ -78,6 +94,7 @@ pub fn ImgPerk(
             <ImgBg
                 class=class.map(|class| format!("{} {}" ,class, perk.get_class_name()))
                 parent_class=parent_class
+                tooltip=AssetTooltipTarget::Perk(perk)
             children
             />
         }
@@ -95,6 +112,7 @@ pub fn ImgSummonerSpell(
         <ImgBg
             class=class.map(|class| format!("{} {}" ,class, summoner_spell.get_class_name()))
             parent_class=parent_class
+            tooltip=AssetTooltipTarget::SummonerSpell(summoner_spell)
     children
         />
     }
@@ -110,6 +128,23 @@ pub fn ImgItem(
         <ImgBg
             class=class.map(|class| format!("{} {}" ,class, item.get_class_name()))
             parent_class=parent_class
+            tooltip=AssetTooltipTarget::Item(item)
+            children
+        />
+    }
+}
+
+#[component]
+pub fn ImgAugment(
+    augment: Augment,
+    #[prop(optional)] class: Option<String>,
+    #[prop(optional)] parent_class: Option<String>,
+    #[prop(optional)] children: Option<Children>,
+) -> impl IntoView {
+    view! {
+        <ImgBg
+            class=class.map(|class| format!("{} {}" ,class, augment.get_class_name()))
+            parent_class=parent_class
             children
         />
     }
@@ -126,6 +161,7 @@ pub fn ImgChampion(
         <ImgBg
             class=class.map(|class| format!("{} {}" ,class, champion.get_class_name()))
             parent_class=parent_class
+            tooltip=AssetTooltipTarget::Champion(champion)
             children
         />
     }
@@ -144,20 +180,120 @@ pub fn PendingLoading(pending: ReadSignal<bool>, children: Children) -> impl Int
     }
 }
 
+/// Raw `filters[..]` query values, lists are comma separated.
 #[derive(Params, PartialEq, Clone, Default)]
 pub struct MatchFiltersSearch {
-    pub queue_id: Option<u8>,
-    pub champion_id: Option<u16>,
+    pub queue_id: Option<String>,
+    pub champion_id: Option<String>,
     pub start_date: Option<String>,
     pub end_date: Option<String>,
+    pub start_patch: Option<String>,
+    pub end_patch: Option<String>,
+    pub season_id: Option<String>,
+    pub result: Option<String>,
+    pub role: Option<String>,
+    pub min_duration: Option<String>,
+    pub max_duration: Option<String>,
+    pub exclude_remakes: Option<String>,
+    pub played_with: Option<String>,
+    pub played_with_side: Option<String>,
 }
 
-#[derive(Debug, Encode, Decode, Default, PartialEq, Clone, Copy)]
+/// Games shorter than this many seconds are considered remakes.
+pub const REMAKE_MAX_DURATION: i32 = 300;
+
+#[derive(Debug, Encode, Decode, Default, PartialEq, Clone)]
 pub struct BackEndMatchFiltersSearch {
     pub start_date: Option<CompactDate>,
     pub end_date: Option<CompactDate>,
-    pub champion_id: Option<u16>,
-    pub queue_id: Option<u8>,
+    pub champion_ids: Vec<u16>,
+    pub queue_ids: Vec<u8>,
+    pub start_patch: Option<CompactPatch>,
+    pub end_patch: Option<CompactPatch>,
+    pub season_id: Option<u16>,
+    pub result: Option<MatchResultFilter>,
+    pub role: Option<RoleFilter>,
+    /// In minutes.
+    pub min_duration: Option<u16>,
+    /// In minutes.
+    pub max_duration: Option<u16>,
+    pub exclude_remakes: bool,
+    pub played_with: Option<PlayedWithFilter>,
+}
+
+#[derive(Debug, Encode, Decode, PartialEq, Clone, Copy)]
+pub enum MatchResultFilter {
+    Win,
+    Loss,
+}
+
+impl MatchResultFilter {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "win" => Some(Self::Win),
+            "loss" => Some(Self::Loss),
+            _ => None,
+        }
+    }
+}
+
+/// Position played by the filtered summoner, matched against `team_position`.
+#[derive(Debug, Encode, Decode, PartialEq, Clone, Copy)]
+pub enum RoleFilter {
+    Top,
+    Jungle,
+    Middle,
+    Bottom,
+    Utility,
+}
+
+impl RoleFilter {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "top" => Some(Self::Top),
+            "jungle" => Some(Self::Jungle),
+            "middle" => Some(Self::Middle),
+            "bottom" => Some(Self::Bottom),
+            "utility" => Some(Self::Utility),
+            _ => None,
+        }
+    }
+
+    pub fn to_team_position(self) -> &'static str {
+        match self {
+            Self::Top => "TOP",
+            Self::Jungle => "JUNGLE",
+            Self::Middle => "MIDDLE",
+            Self::Bottom => "BOTTOM",
+            Self::Utility => "UTILITY",
+        }
+    }
+}
+
+/// Only keeps the games where the given Riot ID also played.
+#[derive(Debug, Encode, Decode, PartialEq, Clone)]
+pub struct PlayedWithFilter {
+    pub game_name: String,
+    pub tag_line: String,
+    pub side: PlayedWithSide,
+}
+
+#[derive(Debug, Encode, Decode, PartialEq, Clone, Copy, Default)]
+pub enum PlayedWithSide {
+    #[default]
+    Any,
+    Ally,
+    Enemy,
+}
+
+impl PlayedWithSide {
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "ally" => Self::Ally,
+            "enemy" => Self::Enemy,
+            _ => Self::Any,
+        }
+    }
 }
 
 impl BackEndMatchFiltersSearch {
@@ -170,17 +306,186 @@ impl BackEndMatchFiltersSearch {
     pub fn end_date_to_naive(&self) -> Option<chrono::NaiveDateTime> {
         crate::backend::ssr::parse_date(self.end_date.map(|x| x.to_string()))
     }
-    pub fn from_signals(
-        queue_id: Option<String>,
-        champion_id: Option<String>,
-        start_date: Option<String>,
-        end_date: Option<String>,
-    ) -> Self {
+
+    /// Whether a filter needs the `lol_matches` row of the participant.
+    pub fn needs_lol_match(&self) -> bool {
+        !self.queue_ids.is_empty()
+            || self.start_date.is_some()
+            || self.end_date.is_some()
+            || self.start_patch.is_some()
+            || self.end_patch.is_some()
+            || self.season_id.is_some()
+            || self.min_duration.is_some()
+            || self.max_duration.is_some()
+            || self.exclude_remakes
+    }
+
+    /// Appends every filter condition, `participant` being the alias of the filtered summoner
+    /// `lol_match_participants` row and `lm` the joined `lol_matches`.
+    #[cfg(feature = "ssr")]
+    pub fn push_sql_filters(
+        &self,
+        query: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>,
+        participant: &str,
+    ) {
+        use common::consts::queue::Queue;
+
+        if !self.champion_ids.is_empty() {
+            query.push(format!(" AND {}.champion_id = ANY(", participant));
+            query.push_bind(
+                self.champion_ids
+                    .iter()
+                    .map(|champion_id| *champion_id as i32)
+                    .collect::<Vec<_>>(),
+            );
+            query.push(")");
+        }
+        if !self.queue_ids.is_empty() {
+            query.push(" AND lm.queue_id = ANY(");
+            query.push_bind(
+                self.queue_ids
+                    .iter()
+                    .map(|queue_id| Queue::from(*queue_id).to_u16() as i32)
+                    .collect::<Vec<_>>(),
+            );
+            query.push(")");
+        }
+        if let Some(start_date) = self.start_date_to_naive() {
+            query.push(" AND lm.match_end >= ");
+            query.push_bind(start_date);
+        }
+        if let Some(end_date) = self.end_date_to_naive() {
+            query.push(" AND lm.match_end <= ");
+            query.push_bind(end_date);
+        }
+        self.push_sql_version_filters(query, "lm.version");
+        if let Some(result) = self.result {
+            query.push(format!(" AND {}.won = ", participant));
+            query.push_bind(result == MatchResultFilter::Win);
+        }
+        if let Some(role) = self.role {
+            query.push(format!(" AND {}.team_position = ", participant));
+            query.push_bind(role.to_team_position());
+        }
+        if let Some(min_duration) = self.min_duration {
+            query.push(" AND lm.match_duration >= ");
+            query.push_bind(min_duration as i32 * 60);
+        }
+        if let Some(max_duration) = self.max_duration {
+            query.push(" AND lm.match_duration <= ");
+            query.push_bind(max_duration as i32 * 60);
+        }
+        if self.exclude_remakes {
+            query.push(" AND (lm.match_duration IS NULL OR lm.match_duration >= ");
+            query.push_bind(REMAKE_MAX_DURATION);
+            query.push(")");
+        }
+        if let Some(played_with) = &self.played_with {
+            query.push(format!(
+                " AND EXISTS (SELECT 1 FROM lol_match_participants as pw JOIN summoners as pws ON pws.id = pw.summoner_id WHERE pw.lol_match_id = {0}.lol_match_id AND pw.summoner_id != {0}.summoner_id",
+                participant
+            ));
+            match played_with.side {
+                PlayedWithSide::Any => {}
+                PlayedWithSide::Ally => {
+                    query.push(format!(" AND pw.team_id = {}.team_id", participant));
+                }
+                PlayedWithSide::Enemy => {
+                    query.push(format!(" AND pw.team_id != {}.team_id", participant));
+                }
+            }
+            query.push(" AND lower(pws.game_name) = lower(");
+            query.push_bind(played_with.game_name.clone());
+            query.push(") AND lower(pws.tag_line) = lower(");
+            query.push_bind(played_with.tag_line.clone());
+            query.push("))");
+        }
+    }
+
+    /// Patch range and season filters on a `VARCHAR(5)` version column, compared as `INT[]` like the
+    /// `idx_lol_matches_version_parts` index.
+    #[cfg(feature = "ssr")]
+    pub fn push_sql_version_filters(
+        &self,
+        query: &mut sqlx::QueryBuilder<'_, sqlx::Postgres>,
+        version_column: &str,
+    ) {
+        if let Some(start_patch) = self.start_patch {
+            query.push(format!(
+                " AND string_to_array({}, '.')::INT[] >= ",
+                version_column
+            ));
+            query.push_bind(start_patch.to_pg_array());
+        }
+        if let Some(end_patch) = self.end_patch {
+            query.push(format!(
+                " AND string_to_array({}, '.')::INT[] <= ",
+                version_column
+            ));
+            query.push_bind(end_patch.to_pg_array());
+        }
+        if let Some(season_id) = self.season_id {
+            query.push(format!(
+                " AND string_to_array({}, '.')::INT[] >= (SELECT string_to_array(start_version, '.')::INT[] FROM seasons WHERE id = ",
+                version_column
+            ));
+            query.push_bind(season_id as i32);
+            query.push(format!(
+                ") AND string_to_array({}, '.')::INT[] <= (SELECT coalesce(string_to_array(end_version, '.')::INT[], '{{32767}}') FROM seasons WHERE id = ",
+                version_column
+            ));
+            query.push_bind(season_id as i32);
+            query.push(")");
+        }
+    }
+
+    /// Without an end patch, the start patch selects a single patch.
+    pub fn from_search(search: &MatchFiltersSearch) -> Self {
+        fn parse_list<T: std::str::FromStr>(value: &Option<String>) -> Vec<T> {
+            value
+                .as_deref()
+                .unwrap_or_default()
+                .split(',')
+                .filter_map(|x| x.trim().parse().ok())
+                .collect()
+        }
+        let parse_minutes = |value: &Option<String>| {
+            value
+                .as_deref()
+                .and_then(|x| x.parse::<u16>().ok())
+                .filter(|x| *x > 0)
+        };
+        let start_patch = search.start_patch.as_deref().and_then(CompactPatch::parse);
         Self {
-            queue_id: queue_id.map(|x| x.parse::<u8>().unwrap_or_default()),
-            champion_id: champion_id.map(|x| x.parse::<u16>().unwrap_or_default()),
-            start_date: parse_date(start_date),
-            end_date: parse_date(end_date),
+            queue_ids: parse_list(&search.queue_id),
+            champion_ids: parse_list(&search.champion_id),
+            start_date: parse_date(search.start_date.clone()),
+            end_date: parse_date(search.end_date.clone()),
+            start_patch,
+            end_patch: search
+                .end_patch
+                .as_deref()
+                .and_then(CompactPatch::parse)
+                .or(start_patch),
+            season_id: search.season_id.as_deref().and_then(|x| x.parse().ok()),
+            result: search.result.as_deref().and_then(MatchResultFilter::parse),
+            role: search.role.as_deref().and_then(RoleFilter::parse),
+            min_duration: parse_minutes(&search.min_duration),
+            max_duration: parse_minutes(&search.max_duration),
+            exclude_remakes: search.exclude_remakes.as_deref() == Some("true"),
+            played_with: search
+                .played_with
+                .as_deref()
+                .and_then(|riot_id| riot_id.split_once('#'))
+                .map(|(game_name, tag_line)| (game_name.trim(), tag_line.trim()))
+                .filter(|(game_name, tag_line)| !game_name.is_empty() && !tag_line.is_empty())
+                .map(|(game_name, tag_line)| PlayedWithFilter {
+                    game_name: game_name.to_string(),
+                    tag_line: tag_line.to_string(),
+                    side: PlayedWithSide::parse(
+                        search.played_with_side.as_deref().unwrap_or_default(),
+                    ),
+                }),
         }
     }
 }
@@ -199,6 +504,40 @@ pub fn parse_date(date: Option<String>) -> Option<CompactDate> {
     })
 }
 
+/// Game patch as stored in `lol_matches.version`, major in the high byte, minor in the low byte.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Encode, Decode)]
+pub struct CompactPatch(u16);
+
+impl CompactPatch {
+    pub fn new(major: u8, minor: u8) -> Self {
+        CompactPatch(((major as u16) << 8) | minor as u16)
+    }
+
+    pub fn parse(version: &str) -> Option<Self> {
+        let (major, minor) = version.split_once('.')?;
+        Some(Self::new(major.parse().ok()?, minor.parse().ok()?))
+    }
+
+    pub fn major(&self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    pub fn minor(&self) -> u8 {
+        (self.0 & 0xFF) as u8
+    }
+
+    #[cfg(feature = "ssr")]
+    pub fn to_pg_array(self) -> Vec<i32> {
+        vec![self.major() as i32, self.minor() as i32]
+    }
+}
+
+impl std::fmt::Display for CompactPatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major(), self.minor())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Encode, Decode)]
 pub struct CompactDate(u16);
 
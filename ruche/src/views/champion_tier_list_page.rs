@@ -0,0 +1,247 @@
+use crate::app::{MetaStore, MetaStoreStoreFields};
+use crate::backend::server_fns::get_champion_tier_list::get_champion_tier_list;
+use crate::utils::{calculate_and_format_kda, format_float_to_2digits, format_with_spaces};
+use crate::views::{get_default_navigation_option, ImgChampion};
+use bitcode::{Decode, Encode};
+use common::consts::champion::Champion;
+use common::consts::platform_route::{PlatformRoute, PLATFORM_ROUTE_OPTIONS};
+use common::consts::queue::{Queue, QUEUE_OPTIONS};
+use itertools::Itertools;
+use leptos::either::Either;
+use leptos::prelude::*;
+use leptos::reactive::wrappers::write::SignalSetter;
+use leptos::{component, view, IntoView};
+use leptos_router::hooks::{query_signal_with_options, use_location};
+
+pub const DEFAULT_TIER_LIST_QUEUE: Queue = Queue::SummonersRift5v5RankedSolo;
+
+#[component]
+pub fn ChampionTierListPage() -> impl IntoView {
+    let meta_store = expect_context::<reactive_stores::Store<MetaStore>>();
+    let location = use_location();
+
+    let (queue_id, set_queue_id) =
+        query_signal_with_options::<String>("queue_id", get_default_navigation_option());
+    let (version, set_version) =
+        query_signal_with_options::<String>("patch", get_default_navigation_option());
+    let (region, set_region) =
+        query_signal_with_options::<String>("region", get_default_navigation_option());
+
+    meta_store
+        .title()
+        .set("Champion Tier List | Ruche".to_string());
+    meta_store.description().set("League Of Legends champion tier list by queue, patch and platform: win rates, pick rates, ban rates and KDA computed from every match stored on Ruche.".to_string());
+    meta_store.url().set(location.pathname.get_untracked());
+
+    let tier_list_resource = Resource::new_bitcode(
+        move || (queue_id(), version(), region()),
+        |(queue_id, version, region)| async move {
+            get_champion_tier_list(
+                region.map(|region| PlatformRoute::from(region.as_str())),
+                queue_id.and_then(|queue_id| queue_id.parse::<u8>().ok()),
+                version,
+            )
+            .await
+        },
+    );
+
+    let set_optional_value = move |setter: SignalSetter<Option<String>>, value: String| {
+        setter.set(if value.is_empty() { None } else { Some(value) });
+    };
+    let default_queue_id = (DEFAULT_TIER_LIST_QUEUE as u8).to_string();
+
+    view! {
+        <div class="flex justify-center">
+            <div class="my-card w-[768px]">
+                <div class="flex text-left space-x-2 justify-center">
+                    <div class="flex flex-col">
+                        <label for="tier_list_queue_id">Queue</label>
+                        <select
+                            class="my-select"
+                            name="queue_id"
+                            id="tier_list_queue_id"
+                            prop:value=move || queue_id().unwrap_or(default_queue_id.clone())
+                            on:change=move |e| set_optional_value(
+                                set_queue_id,
+                                event_target_value(&e),
+                            )
+                        >
+                            {QUEUE_OPTIONS
+                                .iter()
+                                .map(|(inner_queue_id, queue_name)| {
+                                    view! {
+                                        <option value=*inner_queue_id>
+                                            {queue_name.to_string()}
+                                        </option>
+                                    }
+                                })
+                                .collect_vec()}
+                        </select>
+                    </div>
+                    <div class="flex flex-col">
+                        <label for="tier_list_region">Platform</label>
+                        <select
+                            class="my-select"
+                            name="region"
+                            id="tier_list_region"
+                            prop:value=move || region().unwrap_or_default()
+                            on:change=move |e| set_optional_value(set_region, event_target_value(&e))
+                        >
+                            <option value="">All</option>
+                            {PLATFORM_ROUTE_OPTIONS
+                                .iter()
+                                .map(|pt| view! { <option value=pt.to_string()>{pt.to_string()}</option> })
+                                .collect_vec()}
+                        </select>
+                    </div>
+                    <Transition fallback=|| ()>
+                        {move || Suspend::new(async move {
+                            tier_list_resource
+                                .await
+                                .map(|tier_list| {
+                                    let selected_version = tier_list.version.unwrap_or_default();
+                                    view! {
+                                        <div class="flex flex-col">
+                                            <label for="tier_list_patch">Patch</label>
+                                            <select
+                                                class="my-select"
+                                                name="patch"
+                                                id="tier_list_patch"
+                                                prop:value=selected_version
+                                                on:change=move |e| set_optional_value(
+                                                    set_version,
+                                                    event_target_value(&e),
+                                                )
+                                            >
+                                                {tier_list
+                                                    .versions
+                                                    .into_iter()
+                                                    .map(|version| {
+                                                        view! { <option value=version.clone()>{version.clone()}</option> }
+                                                    })
+                                                    .collect_vec()}
+                                            </select>
+                                        </div>
+                                    }
+                                })
+                        })}
+                    </Transition>
+                </div>
+            </div>
+        </div>
+        <Transition fallback=move || {
+            view! { <div class="text-center">Loading Tier List</div> }
+        }>
+            {move || Suspend::new(async move {
+                match tier_list_resource.await {
+                    Ok(tier_list) => {
+                        if tier_list.champions.is_empty() {
+                            return Ok(
+                                Either::Left(
+                                    view! { <div class="text-center">No Champion Stats Found</div> },
+                                ),
+                            );
+                        }
+                        Ok(
+                            Either::Right(
+                                view! {
+                                    <div class="text-sm text-gray-400 my-2">
+                                        {format!("{} matches", format_with_spaces(tier_list.total_matches))}
+                                    </div>
+                                    <table class="w-[768px] mx-auto table-fixed bg-gray-700 border-collapse my-2 border border-gray-600">
+                                        <thead>
+                                            <tr class="bg-gray-800 text-sm h-[32px]">
+                                                <th class="border border-gray-700 w-[45px]">#</th>
+                                                <th class="border border-gray-700 text-left pl-2 w-[200px]">
+                                                    Champion
+                                                </th>
+                                                <th class="border border-gray-700">Tier</th>
+                                                <th class="border border-gray-700">Win Rate</th>
+                                                <th class="border border-gray-700">Pick Rate</th>
+                                                <th class="border border-gray-700">Ban Rate</th>
+                                                <th class="border border-gray-700">KDA</th>
+                                                <th class="border border-gray-700">Games</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {tier_list
+                                                .champions
+                                                .into_iter()
+                                                .enumerate()
+                                                .map(|(index, champion)| {
+                                                    let champion_enum = Champion::from(champion.champion_id);
+                                                    view! {
+                                                        <tr class="text-sm">
+                                                            <td class="border border-gray-600">{index + 1}</td>
+                                                            <td class="border border-gray-600 text-left pl-2">
+                                                                <div class="flex items-center">
+                                                                    <ImgChampion
+                                                                        champion=champion_enum
+                                                                        parent_class="my-1 w-8 h-8 sprite-wrapper".to_string()
+                                                                        class="rounded-full scale-66 sprite-inner".to_string()
+                                                                    />
+                                                                    <div class="ml-2">{champion_enum.to_str()}</div>
+                                                                </div>
+                                                            </td>
+                                                            <td class="border border-gray-600">{champion.tier}</td>
+                                                            <td class="border border-gray-600">
+                                                                {format!("{}%", format_float_to_2digits(champion.win_rate))}
+                                                            </td>
+                                                            <td class="border border-gray-600">
+                                                                {format!("{}%", format_float_to_2digits(champion.pick_rate))}
+                                                            </td>
+                                                            <td class="border border-gray-600">
+                                                                {format!("{}%", format_float_to_2digits(champion.ban_rate))}
+                                                            </td>
+                                                            <td class="border border-gray-600">
+                                                                {format!(
+                                                                    "{}:1",
+                                                                    calculate_and_format_kda(
+                                                                        champion.avg_kills,
+                                                                        champion.avg_deaths,
+                                                                        champion.avg_assists,
+                                                                    ),
+                                                                )}
+                                                            </td>
+                                                            <td class="border border-gray-600">
+                                                                {format_with_spaces(champion.total_picks)}
+                                                            </td>
+                                                        </tr>
+                                                    }
+                                                })
+                                                .collect_vec()}
+                                        </tbody>
+                                    </table>
+                                },
+                            ),
+                        )
+                    }
+                    Err(e) => Err(e),
+                }
+            })}
+        </Transition>
+    }
+}
+
+#[derive(Clone, Encode, Decode, Default)]
+pub struct ChampionTierList {
+    /// Patches having stats for the queue, newest first.
+    pub versions: Vec<String>,
+    pub version: Option<String>,
+    pub total_matches: u32,
+    pub champions: Vec<GlobalChampionStats>,
+}
+
+#[derive(Clone, Encode, Decode)]
+pub struct GlobalChampionStats {
+    pub champion_id: u16,
+    /// 1 is the best tier, the last one holds the rarely picked champions.
+    pub tier: u8,
+    pub total_picks: u32,
+    pub win_rate: f32,
+    pub pick_rate: f32,
+    pub ban_rate: f32,
+    pub avg_kills: f32,
+    pub avg_deaths: f32,
+    pub avg_assists: f32,
+}
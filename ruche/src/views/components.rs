@@ -1,2 +1,3 @@
+pub mod asset_tooltip;
 pub mod match_filters;
 pub mod pagination;
@@ -0,0 +1,180 @@
+use common::asset_metadata::AssetMetadata;
+use common::consts::champion::Champion;
+use common::consts::item::Item;
+use common::consts::perk::Perk;
+use common::consts::summoner_spell::SummonerSpell;
+use leptos::either::Either;
+use leptos::prelude::*;
+
+/// Icon whose metadata is shown on hover.
+#[derive(Clone, Copy)]
+pub enum AssetTooltipTarget {
+    Champion(Champion),
+    Item(Item),
+    Perk(Perk),
+    SummonerSpell(SummonerSpell),
+}
+
+/// Hovered icon and metadata shared by every icon of the app, the metadata is only fetched on
+/// the first hover.
+#[derive(Clone, Copy)]
+pub struct AssetTooltipContext {
+    metadata: RwSignal<Option<AssetMetadata>>,
+    requested: StoredValue<bool>,
+    hovered: RwSignal<Option<(AssetTooltipTarget, i32, i32)>>,
+}
+
+impl Default for AssetTooltipContext {
+    fn default() -> Self {
+        Self {
+            metadata: RwSignal::new(None),
+            requested: StoredValue::new(false),
+            hovered: RwSignal::new(None),
+        }
+    }
+}
+
+impl AssetTooltipContext {
+    pub fn show(&self, target: AssetTooltipTarget, x: i32, y: i32) {
+        self.load();
+        self.hovered.set(Some((target, x, y)));
+    }
+
+    pub fn hide(&self) {
+        self.hovered.set(None);
+    }
+
+    fn load(&self) {
+        if self.requested.get_value() {
+            return;
+        }
+        // a failed fetch is not retried, the tooltips fall back to the names
+        self.requested.set_value(true);
+        #[cfg(not(feature = "ssr"))]
+        {
+            let metadata = self.metadata;
+            leptos::task::spawn_local(async move {
+                match fetch_metadata().await {
+                    Ok(m) => metadata.set(Some(m)),
+                    Err(e) => leptos::logging::log!("Failed to load asset metadata: {}", e),
+                }
+            });
+        }
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+async fn fetch_metadata() -> Result<AssetMetadata, String> {
+    let url = format!(
+        "{}?v={}",
+        common::asset_metadata::ASSET_METADATA_PATH,
+        env!("ASSET_METADATA_HASH")
+    );
+    let bytes = gloo_net::http::Request::get(&url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?
+        .binary()
+        .await
+        .map_err(|e| e.to_string())?;
+    bitcode::decode(&bytes).map_err(|e| e.to_string())
+}
+
+struct TooltipContent {
+    name: String,
+    subtitle: Option<String>,
+    stats: Vec<String>,
+    description: String,
+}
+
+impl TooltipContent {
+    fn from_name(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            subtitle: None,
+            stats: Vec::new(),
+            description: String::new(),
+        }
+    }
+}
+
+fn tooltip_content(
+    target: AssetTooltipTarget,
+    metadata: Option<&AssetMetadata>,
+) -> Option<TooltipContent> {
+    match target {
+        AssetTooltipTarget::Champion(champion) => Some(
+            metadata
+                .and_then(|metadata| metadata.champion(champion as u16))
+                .map(|champion| TooltipContent {
+                    name: champion.name.clone(),
+                    subtitle: Some(champion.title.clone()),
+                    stats: vec![champion.tags.join(", ")],
+                    description: String::new(),
+                })
+                .unwrap_or_else(|| TooltipContent::from_name(champion.name())),
+        ),
+        // ids missing from the generated names have no tooltip before the metadata is loaded
+        AssetTooltipTarget::Item(item) => metadata
+            .and_then(|metadata| metadata.item(item.0))
+            .map(|item| TooltipContent {
+                name: item.name.clone(),
+                subtitle: (item.gold > 0).then(|| format!("{} gold", item.gold)),
+                stats: item.stats.clone(),
+                description: item.description.clone(),
+            })
+            .or_else(|| item.name().map(TooltipContent::from_name)),
+        AssetTooltipTarget::Perk(perk) => Some(
+            metadata
+                .and_then(|metadata| metadata.perk(perk as u16))
+                .map(|perk| TooltipContent {
+                    name: perk.name.clone(),
+                    subtitle: None,
+                    stats: Vec::new(),
+                    description: perk.description.clone(),
+                })
+                .unwrap_or_else(|| TooltipContent::from_name(perk.name())),
+        ),
+        AssetTooltipTarget::SummonerSpell(summoner_spell) => Some(
+            metadata
+                .and_then(|metadata| metadata.summoner_spell(summoner_spell as u16))
+                .map(|summoner_spell| TooltipContent {
+                    name: summoner_spell.name.clone(),
+                    subtitle: Some(format!("{}s cooldown", summoner_spell.cooldown)),
+                    stats: Vec::new(),
+                    description: summoner_spell.description.clone(),
+                })
+                .unwrap_or_else(|| TooltipContent::from_name(summoner_spell.name())),
+        ),
+    }
+}
+
+#[component]
+pub fn AssetTooltip() -> impl IntoView {
+    let context = expect_context::<AssetTooltipContext>();
+    move || {
+        context.hovered.get().and_then(|(target, x, y)| {
+            context
+                .metadata
+                .with(|metadata| tooltip_content(target, metadata.as_ref()))
+                .map(|content| {
+                    view! {
+                        <div
+                            class="fixed z-50 pointer-events-none max-w-xs bg-gray-800 border border-gray-600 rounded p-2 text-xs text-left"
+                            style=format!("left: {}px; top: {}px;", x + 12, y + 12)
+                        >
+                            <div class="font-bold text-sm">{content.name}</div>
+                            {match content.subtitle {
+                                Some(subtitle) => Either::Left(view! { <div class="text-yellow-400">{subtitle}</div> }),
+                                None => Either::Right(()),
+                            }}
+                            <div class="text-gray-300 my-1">
+                                {content.stats.into_iter().filter(|stat| !stat.is_empty()).map(|stat| view! { <div>{stat}</div> }).collect::<Vec<_>>()}
+                            </div>
+                            <div class="text-gray-400 whitespace-pre-line">{content.description}</div>
+                        </div>
+                    }
+                })
+        })
+    }
+}
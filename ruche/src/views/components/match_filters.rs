@@ -1,5 +1,9 @@
-use crate::views::{get_default_navigation_option, BackEndMatchFiltersSearch};
-use common::consts::champion::CHAMPION_OPTIONS;
+use crate::backend::server_fns::get_match_filter_options::get_match_filter_options;
+use crate::views::{
+    get_default_navigation_option, BackEndMatchFiltersSearch, CompactPatch, MatchFiltersSearch,
+};
+use bitcode::{Decode, Encode};
+use common::consts::champion::{Champion, CHAMPION_OPTIONS};
 use common::consts::queue::QUEUE_OPTIONS;
 use itertools::Itertools;
 use leptos::context::provide_context;
@@ -24,121 +28,227 @@ pub fn MatchFilters(children: Children) -> impl IntoView {
     let (queue_id, set_queue_id) =
         query_signal_with_options::<String>("filters[queue_id]", get_default_navigation_option());
 
-    let filters_signal = RwSignal::new(BackEndMatchFiltersSearch::from_signals(
-        queue_id(),
-        champion_id(),
-        start_date(),
-        end_date(),
-    ));
+    let (start_patch, set_start_patch) = query_signal_with_options::<String>(
+        "filters[start_patch]",
+        get_default_navigation_option(),
+    );
+
+    let (end_patch, set_end_patch) = query_signal_with_options::<String>(
+        "filters[end_patch]",
+        get_default_navigation_option(),
+    );
+
+    let (season_id, set_season_id) =
+        query_signal_with_options::<String>("filters[season_id]", get_default_navigation_option());
+
+    let (result, set_result) =
+        query_signal_with_options::<String>("filters[result]", get_default_navigation_option());
+
+    let (role, set_role) =
+        query_signal_with_options::<String>("filters[role]", get_default_navigation_option());
+
+    let (min_duration, set_min_duration) = query_signal_with_options::<String>(
+        "filters[min_duration]",
+        get_default_navigation_option(),
+    );
+
+    let (max_duration, set_max_duration) = query_signal_with_options::<String>(
+        "filters[max_duration]",
+        get_default_navigation_option(),
+    );
+
+    let (exclude_remakes, set_exclude_remakes) = query_signal_with_options::<String>(
+        "filters[exclude_remakes]",
+        get_default_navigation_option(),
+    );
+
+    let (played_with, set_played_with) = query_signal_with_options::<String>(
+        "filters[played_with]",
+        get_default_navigation_option(),
+    );
+
+    let (played_with_side, set_played_with_side) = query_signal_with_options::<String>(
+        "filters[played_with_side]",
+        get_default_navigation_option(),
+    );
+
+    let current_search = move || MatchFiltersSearch {
+        queue_id: queue_id(),
+        champion_id: champion_id(),
+        start_date: start_date(),
+        end_date: end_date(),
+        start_patch: start_patch(),
+        end_patch: end_patch(),
+        season_id: season_id(),
+        result: result(),
+        role: role(),
+        min_duration: min_duration(),
+        max_duration: max_duration(),
+        exclude_remakes: exclude_remakes(),
+        played_with: played_with(),
+        played_with_side: played_with_side(),
+    };
+
+    let filters_signal = RwSignal::new(BackEndMatchFiltersSearch::from_search(&current_search()));
     provide_context(filters_signal);
 
-    enum FilterField {
-        StartDate,
-        EndDate,
-        ChampionId,
-        QueueId,
-    }
+    let filter_options = Resource::new_bitcode(|| (), |_| get_match_filter_options());
 
     let set_optional_value = move |setter: SignalSetter<Option<String>>,
                                    value: String,
                                    field: FilterField| {
         let value = if value.is_empty() { None } else { Some(value) };
         setter.set(value.clone());
-        let filters = match field {
-            FilterField::StartDate => BackEndMatchFiltersSearch::from_signals(
-                queue_id(),
-                champion_id(),
-                value,
-                end_date(),
-            ),
-            FilterField::EndDate => BackEndMatchFiltersSearch::from_signals(
-                queue_id(),
-                champion_id(),
-                start_date(),
-                value,
-            ),
-            FilterField::ChampionId => {
-                BackEndMatchFiltersSearch::from_signals(queue_id(), value, start_date(), end_date())
-            }
-            FilterField::QueueId => BackEndMatchFiltersSearch::from_signals(
-                value,
-                champion_id(),
-                start_date(),
-                end_date(),
-            ),
-        };
-        filters_signal.set(filters);
+        // the query signals are only updated after the navigation, use the new value directly
+        let mut search = current_search();
+        field.assign(&mut search, value);
+        filters_signal.set(BackEndMatchFiltersSearch::from_search(&search));
     };
 
     view! {
         <div class="flex justify-center">
             <div class="my-card w-[768px]">
-                <div class="flex text-left space-x-2 justify-center">
+                <div class="flex flex-wrap text-left gap-2 justify-center">
                     <div class="flex flex-col">
-                        <label for="champion_id">Champion</label>
+                        <label for="champion_id">Champions</label>
                         <select
                             name="champion_id"
                             class="my-select"
                             id="champion_id"
-                            prop:value=move || champion_id().unwrap_or_default()
+                            prop:value=""
                             on:change=move |e| set_optional_value(
                                 set_champion_id,
-                                event_target_value(&e),
+                                toggle_list_value(champion_id(), event_target_value(&e).as_str()),
                                 FilterField::ChampionId,
                             )
                         >
-                            <option value="" selected=move || champion_id().is_none()>
-                                All
-                            </option>
+                            <option value="">Add</option>
                             {CHAMPION_OPTIONS
                                 .iter()
                                 .map(|(id, champion)| {
-                                    view! {
-                                        <option
-                                            value=*id
-                                            selected=move || {
-                                                *id.to_string() == champion_id().unwrap_or_default()
-                                            }
-                                        >
-                                            {champion.to_string()}
-                                        </option>
-                                    }
+                                    view! { <option value=*id>{champion.to_string()}</option> }
                                 })
                                 .collect_vec()}
                         </select>
+                        <div class="flex flex-wrap gap-1 mt-1 max-w-[180px]">
+                            {move || {
+                                list_values(champion_id())
+                                    .into_iter()
+                                    .map(|id| {
+                                        let champion = id
+                                            .parse::<u16>()
+                                            .map(|id| Champion::from(id).to_str())
+                                            .unwrap_or_default();
+                                        view! {
+                                            <button
+                                                class="bg-gray-800 rounded px-1 text-xs"
+                                                title="Remove"
+                                                on:click=move |_| set_optional_value(
+                                                    set_champion_id,
+                                                    toggle_list_value(champion_id(), id.as_str()),
+                                                    FilterField::ChampionId,
+                                                )
+                                            >
+                                                {format!("{} x", champion)}
+                                            </button>
+                                        }
+                                    })
+                                    .collect_vec()
+                            }}
+                        </div>
                     </div>
                     <div class="flex flex-col">
-                        <label for="queue_id">Queue</label>
+                        <label for="queue_id">Queues</label>
                         <select
                             class="my-select"
                             name="queue_id"
                             id="queue_id"
-                            prop:value=move || queue_id().unwrap_or_default()
+                            prop:value=""
                             on:change=move |e| set_optional_value(
                                 set_queue_id,
-                                event_target_value(&e),
+                                toggle_list_value(queue_id(), event_target_value(&e).as_str()),
                                 FilterField::QueueId,
                             )
                         >
-                            <option value="" selected=move || queue_id().is_none()>
-                                All
-                            </option>
+                            <option value="">Add</option>
                             {QUEUE_OPTIONS
                                 .iter()
                                 .map(|(inner_queue_id, queue_name)| {
                                     view! {
-                                        <option
-                                            value=*inner_queue_id
-                                            selected=move || {
-                                                inner_queue_id.to_string() == queue_id().unwrap_or_default()
-                                            }
-                                        >
-                                            {queue_name.to_string()}
-                                        </option>
+                                        <option value=*inner_queue_id>{queue_name.to_string()}</option>
                                     }
                                 })
                                 .collect_vec()}
                         </select>
+                        <div class="flex flex-wrap gap-1 mt-1 max-w-[180px]">
+                            {move || {
+                                list_values(queue_id())
+                                    .into_iter()
+                                    .map(|id| {
+                                        let queue_name = QUEUE_OPTIONS
+                                            .iter()
+                                            .find(|(inner_queue_id, _)| {
+                                                inner_queue_id.to_string() == id
+                                            })
+                                            .map(|(_, queue_name)| *queue_name)
+                                            .unwrap_or_default();
+                                        view! {
+                                            <button
+                                                class="bg-gray-800 rounded px-1 text-xs"
+                                                title="Remove"
+                                                on:click=move |_| set_optional_value(
+                                                    set_queue_id,
+                                                    toggle_list_value(queue_id(), id.as_str()),
+                                                    FilterField::QueueId,
+                                                )
+                                            >
+                                                {format!("{} x", queue_name)}
+                                            </button>
+                                        }
+                                    })
+                                    .collect_vec()
+                            }}
+                        </div>
+                    </div>
+                    <div class="flex flex-col">
+                        <label for="result">Result</label>
+                        <select
+                            class="my-select"
+                            name="result"
+                            id="result"
+                            prop:value=move || result().unwrap_or_default()
+                            on:change=move |e| set_optional_value(
+                                set_result,
+                                event_target_value(&e),
+                                FilterField::Result,
+                            )
+                        >
+                            <option value="">All</option>
+                            <option value="win">Wins</option>
+                            <option value="loss">Losses</option>
+                        </select>
+                    </div>
+                    <div class="flex flex-col">
+                        <label for="role">Role</label>
+                        <select
+                            class="my-select"
+                            name="role"
+                            id="role"
+                            title="Matches stored before roles were recorded have no role until they are reprocessed"
+                            prop:value=move || role().unwrap_or_default()
+                            on:change=move |e| set_optional_value(
+                                set_role,
+                                event_target_value(&e),
+                                FilterField::Role,
+                            )
+                        >
+                            <option value="">All</option>
+                            <option value="top">Top</option>
+                            <option value="jungle">Jungle</option>
+                            <option value="middle">Mid</option>
+                            <option value="bottom">Bot</option>
+                            <option value="utility">Support</option>
+                        </select>
                     </div>
                     <div class="flex flex-col">
                         <label for="start_date">Start Date</label>
@@ -174,6 +284,187 @@ pub fn MatchFilters(children: Children) -> impl IntoView {
                             )
                         />
                     </div>
+                    <div class="flex flex-col">
+                        <label for="min_duration">Duration (min)</label>
+                        <div class="flex space-x-1">
+                            <input
+                                class="my-input w-16"
+                                type="number"
+                                min="0"
+                                placeholder="from"
+                                name="min_duration"
+                                id="min_duration"
+                                prop:value=move || min_duration().unwrap_or_default()
+                                on:change=move |e| set_optional_value(
+                                    set_min_duration,
+                                    event_target_value(&e),
+                                    FilterField::MinDuration,
+                                )
+                            />
+                            <input
+                                class="my-input w-16"
+                                type="number"
+                                min="0"
+                                placeholder="to"
+                                name="max_duration"
+                                id="max_duration"
+                                prop:value=move || max_duration().unwrap_or_default()
+                                on:change=move |e| set_optional_value(
+                                    set_max_duration,
+                                    event_target_value(&e),
+                                    FilterField::MaxDuration,
+                                )
+                            />
+                        </div>
+                        <label class="flex items-center text-xs mt-1">
+                            <input
+                                type="checkbox"
+                                class="mr-1"
+                                name="exclude_remakes"
+                                prop:checked=move || exclude_remakes().as_deref() == Some("true")
+                                on:change=move |e| set_optional_value(
+                                    set_exclude_remakes,
+                                    if event_target_checked(&e) {
+                                        "true".to_string()
+                                    } else {
+                                        String::new()
+                                    },
+                                    FilterField::ExcludeRemakes,
+                                )
+                            />
+                            Exclude remakes
+                        </label>
+                    </div>
+                    <div class="flex flex-col">
+                        <label for="played_with">Played With</label>
+                        <div class="flex space-x-1">
+                            <input
+                                class="my-input w-32"
+                                type="text"
+                                placeholder="Name#TAG"
+                                name="played_with"
+                                id="played_with"
+                                prop:value=move || played_with().unwrap_or_default()
+                                on:change=move |e| set_optional_value(
+                                    set_played_with,
+                                    event_target_value(&e),
+                                    FilterField::PlayedWith,
+                                )
+                            />
+                            <select
+                                class="my-select"
+                                name="played_with_side"
+                                id="played_with_side"
+                                prop:value=move || played_with_side().unwrap_or_default()
+                                on:change=move |e| set_optional_value(
+                                    set_played_with_side,
+                                    event_target_value(&e),
+                                    FilterField::PlayedWithSide,
+                                )
+                            >
+                                <option value="">Any team</option>
+                                <option value="ally">My team</option>
+                                <option value="enemy">Enemy team</option>
+                            </select>
+                        </div>
+                    </div>
+                    <Transition fallback=|| ()>
+                        {move || Suspend::new(async move {
+                            filter_options
+                                .await
+                                .map(|options| {
+                                    let patch_options = move |selected: Option<String>| {
+                                        options
+                                            .patches
+                                            .iter()
+                                            .map(|patch| {
+                                                let patch = patch.to_string();
+                                                view! {
+                                                    <option
+                                                        value=patch.clone()
+                                                        selected=selected.as_ref() == Some(&patch)
+                                                    >
+                                                        {patch.clone()}
+                                                    </option>
+                                                }
+                                            })
+                                            .collect_vec()
+                                    };
+                                    view! {
+                                        <div class="flex flex-col">
+                                            <label for="start_patch">Patch</label>
+                                            <select
+                                                class="my-select"
+                                                name="start_patch"
+                                                id="start_patch"
+                                                prop:value=move || start_patch().unwrap_or_default()
+                                                on:change=move |e| set_optional_value(
+                                                    set_start_patch,
+                                                    event_target_value(&e),
+                                                    FilterField::StartPatch,
+                                                )
+                                            >
+                                                <option value="" selected=start_patch().is_none()>
+                                                    All
+                                                </option>
+                                                {patch_options(start_patch())}
+                                            </select>
+                                        </div>
+                                        <div class="flex flex-col">
+                                            <label for="end_patch">To Patch</label>
+                                            <select
+                                                class="my-select"
+                                                name="end_patch"
+                                                id="end_patch"
+                                                prop:value=move || end_patch().unwrap_or_default()
+                                                on:change=move |e| set_optional_value(
+                                                    set_end_patch,
+                                                    event_target_value(&e),
+                                                    FilterField::EndPatch,
+                                                )
+                                            >
+                                                <option value="" selected=end_patch().is_none()>
+                                                    -
+                                                </option>
+                                                {patch_options(end_patch())}
+                                            </select>
+                                        </div>
+                                        <div class="flex flex-col">
+                                            <label for="season_id">Season</label>
+                                            <select
+                                                class="my-select"
+                                                name="season_id"
+                                                id="season_id"
+                                                prop:value=move || season_id().unwrap_or_default()
+                                                on:change=move |e| set_optional_value(
+                                                    set_season_id,
+                                                    event_target_value(&e),
+                                                    FilterField::SeasonId,
+                                                )
+                                            >
+                                                <option value="" selected=season_id().is_none()>
+                                                    All
+                                                </option>
+                                                {options
+                                                    .seasons
+                                                    .iter()
+                                                    .map(|season| {
+                                                        view! {
+                                                            <option
+                                                                value=season.id
+                                                                selected=season_id() == Some(season.id.to_string())
+                                                            >
+                                                                {season.name.clone()}
+                                                            </option>
+                                                        }
+                                                    })
+                                                    .collect_vec()}
+                                            </select>
+                                        </div>
+                                    }
+                                })
+                        })}
+                    </Transition>
                 </div>
             </div>
 
@@ -181,3 +472,75 @@ pub fn MatchFilters(children: Children) -> impl IntoView {
         {children()}
     }
 }
+
+#[derive(Clone, Copy)]
+enum FilterField {
+    StartDate,
+    EndDate,
+    ChampionId,
+    QueueId,
+    StartPatch,
+    EndPatch,
+    SeasonId,
+    Result,
+    Role,
+    MinDuration,
+    MaxDuration,
+    ExcludeRemakes,
+    PlayedWith,
+    PlayedWithSide,
+}
+
+impl FilterField {
+    fn assign(self, search: &mut MatchFiltersSearch, value: Option<String>) {
+        let field = match self {
+            FilterField::StartDate => &mut search.start_date,
+            FilterField::EndDate => &mut search.end_date,
+            FilterField::ChampionId => &mut search.champion_id,
+            FilterField::QueueId => &mut search.queue_id,
+            FilterField::StartPatch => &mut search.start_patch,
+            FilterField::EndPatch => &mut search.end_patch,
+            FilterField::SeasonId => &mut search.season_id,
+            FilterField::Result => &mut search.result,
+            FilterField::Role => &mut search.role,
+            FilterField::MinDuration => &mut search.min_duration,
+            FilterField::MaxDuration => &mut search.max_duration,
+            FilterField::ExcludeRemakes => &mut search.exclude_remakes,
+            FilterField::PlayedWith => &mut search.played_with,
+            FilterField::PlayedWithSide => &mut search.played_with_side,
+        };
+        *field = value;
+    }
+}
+
+fn list_values(list: Option<String>) -> Vec<String> {
+    list.unwrap_or_default()
+        .split(',')
+        .filter(|value| !value.is_empty())
+        .map(|value| value.to_string())
+        .collect()
+}
+
+/// Adds `value` to the comma separated `list`, or removes it when already present.
+fn toggle_list_value(list: Option<String>, value: &str) -> String {
+    let mut values = list_values(list);
+    if let Some(position) = values.iter().position(|x| x == value) {
+        values.remove(position);
+    } else if !value.is_empty() {
+        values.push(value.to_string());
+    }
+    values.join(",")
+}
+
+#[derive(Clone, Encode, Decode, Default)]
+pub struct MatchFilterOptions {
+    /// Patches of the stored matches, newest first.
+    pub patches: Vec<CompactPatch>,
+    pub seasons: Vec<SeasonOption>,
+}
+
+#[derive(Clone, Encode, Decode)]
+pub struct SeasonOption {
+    pub id: u16,
+    pub name: String,
+}
@@ -0,0 +1,283 @@
+use crate::app::{MetaStore, MetaStoreStoreFields};
+use crate::backend::server_fns::get_multisearch::get_multisearch;
+use crate::utils::{
+    calculate_and_format_kda, calculate_loss_and_win_rate, format_float_to_2digits,
+    multisearch_url, parse_multisearch_riot_ids, summoner_url, ProPlayerSlug,
+};
+use crate::views::summoner_page::summoner_champions_page::ChampionStats;
+use crate::views::{ImgChampion, ImgSrc};
+use bitcode::{Decode, Encode};
+use common::consts::champion::Champion;
+use common::consts::platform_route::PlatformRoute;
+use common::consts::profile_icon::ProfileIcon;
+use common::consts::HasStaticSrcAsset;
+use leptos::either::Either;
+use leptos::ev::SubmitEvent;
+use leptos::html::{Input, Textarea};
+use leptos::prelude::*;
+use leptos::{component, view, IntoView};
+use leptos_router::hooks::{use_navigate, use_params_map, use_query_map};
+
+#[component]
+pub fn MultiSearchPage() -> impl IntoView {
+    let meta_store = expect_context::<reactive_stores::Store<MetaStore>>();
+    let query = use_query_map();
+    let params = use_params_map();
+    let navigate = use_navigate();
+
+    let platform_type = move || {
+        params
+            .read()
+            .get("platform_type")
+            .unwrap_or(PlatformRoute::EUW1.to_string())
+    };
+    let riot_ids = move || query.read().get("riot_ids").unwrap_or_default();
+    let current_riot_id = move || query.read().get("me").unwrap_or_default();
+
+    meta_store.title().set("Multi-Search | Ruche".to_string());
+    meta_store.description().set("Look up a whole League Of Legends lobby at once on Ruche: paste the champion select and get recent win rates, top champions and shared games of every player.".to_string());
+
+    let multisearch_resource = Resource::new_bitcode(
+        move || (platform_type(), riot_ids(), current_riot_id()),
+        |(platform_type, riot_ids, current_riot_id)| async move {
+            let riot_ids = parse_multisearch_riot_ids(riot_ids.as_str());
+            if riot_ids.is_empty() {
+                return Ok(vec![]);
+            }
+            get_multisearch(
+                PlatformRoute::from(platform_type.as_str()),
+                riot_ids,
+                parse_multisearch_riot_ids(current_riot_id.as_str())
+                    .into_iter()
+                    .next(),
+            )
+            .await
+        },
+    );
+
+    let riot_ids_node = NodeRef::<Textarea>::new();
+    let current_riot_id_node = NodeRef::<Input>::new();
+
+    let on_submit = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        navigate(
+            multisearch_url(
+                platform_type().as_str(),
+                riot_ids_node
+                    .get()
+                    .expect("riot_ids not valid")
+                    .value()
+                    .as_str(),
+                current_riot_id_node
+                    .get()
+                    .expect("current riot id not valid")
+                    .value()
+                    .as_str(),
+            )
+            .as_str(),
+            Default::default(),
+        );
+    };
+
+    view! {
+        <div class="w-[768px] mx-auto">
+            <form on:submit=on_submit class="flex flex-col space-y-2 my-2">
+                <textarea
+                    class="my-input h-32"
+                    node_ref=riot_ids_node
+                    name="riot_ids"
+                    placeholder="Name#TAG joined the lobby\nName#TAG joined the lobby\nor Name#TAG, Name#TAG"
+                    prop:value=riot_ids
+                />
+                <div class="flex space-x-2">
+                    <input
+                        class="my-input grow"
+                        type="text"
+                        node_ref=current_riot_id_node
+                        name="me"
+                        placeholder="Your Riot ID (Name#TAG), to count your games with each player"
+                        prop:value=current_riot_id
+                    />
+                    <button class="my-button" type="submit">
+                        Search
+                    </button>
+                </div>
+            </form>
+        </div>
+        <Transition fallback=move || {
+            view! { <div class="text-center">Loading Players</div> }
+        }>
+            {move || Suspend::new(async move {
+                match multisearch_resource.await {
+                    Ok(players) => {
+                        Ok(
+                            view! {
+                                <div class="grid grid-cols-5 gap-2 my-2 text-left">
+                                    {players
+                                        .into_iter()
+                                        .map(|player| view! { <MultiSearchPlayerCard player /> })
+                                        .collect::<Vec<_>>()}
+                                </div>
+                            },
+                        )
+                    }
+                    Err(e) => Err(e),
+                }
+            })}
+        </Transition>
+    }
+}
+
+#[component]
+pub fn MultiSearchPlayerCard(player: MultiSearchPlayer) -> impl IntoView {
+    let Some(summoner) = player.summoner else {
+        return Either::Left(view! {
+            <div class="my-card text-sm">
+                <div class="truncate">{player.game_name}#{player.tag_line}</div>
+                <div class="text-gray-400">Not found</div>
+            </div>
+        });
+    };
+    let profile_icon = ProfileIcon(summoner.profile_icon_id);
+    let (recent_losses, recent_winrate) =
+        calculate_loss_and_win_rate(summoner.recent_wins, summoner.recent_matches);
+    Either::Right(view! {
+        <div class="my-card text-sm flex flex-col space-y-2">
+            <div class="flex items-center">
+                <ImgSrc
+                    alt=profile_icon.to_string()
+                    src=profile_icon.get_static_asset_url()
+                    class="w-8 h-8 rounded".to_string()
+                    height=32
+                    width=32
+                />
+                <div class="flex flex-col ml-2 min-w-0">
+                    <a
+                        href=summoner_url(
+                            summoner.platform.as_ref(),
+                            player.game_name.as_str(),
+                            player.tag_line.as_str(),
+                        )
+                        class="text-blue-300 hover:underline truncate"
+                    >
+                        {player.game_name.clone()}#{player.tag_line.clone()}
+                    </a>
+                    <div class="flex text-xs text-gray-400">
+                        <span>lvl. {summoner.summoner_level}</span>
+                        {summoner
+                            .pro_slug
+                            .map(|pps| {
+                                view! {
+                                    <a
+                                        target="_blank"
+                                        href=format!("https://lolpros.gg/player/{}", pps.as_ref())
+                                        class="bg-purple-800 text-gray-200 rounded px-1 ml-1"
+                                    >
+                                        PRO
+                                    </a>
+                                }
+                            })}
+                    </div>
+                </div>
+            </div>
+            <div>
+                <div class="text-xs text-gray-400">Last {summoner.recent_matches} games</div>
+                <div>
+                    {format!(
+                        "{}W {}L {}%",
+                        summoner.recent_wins,
+                        recent_losses as u16,
+                        format_float_to_2digits(recent_winrate),
+                    )}
+                </div>
+            </div>
+            <div class="flex flex-col">
+                {summoner
+                    .top_champions
+                    .into_iter()
+                    .map(|champion| {
+                        let champion_enum = Champion::from(champion.champion_id);
+                        view! {
+                            <div class="flex items-center">
+                                <ImgChampion
+                                    champion=champion_enum
+                                    parent_class="w-8 h-8 sprite-wrapper".to_string()
+                                    class="rounded-full scale-66 sprite-inner".to_string()
+                                />
+                                <div class="flex flex-col ml-1 text-xs">
+                                    <span>
+                                        {format!(
+                                            "{}% {}G",
+                                            format_float_to_2digits(champion.win_rate),
+                                            champion.total_matches,
+                                        )}
+                                    </span>
+                                    <span class="text-gray-400">
+                                        {calculate_and_format_kda(
+                                            champion.avg_kills,
+                                            champion.avg_deaths,
+                                            champion.avg_assists,
+                                        )}
+                                        :1 KDA
+                                    </span>
+                                </div>
+                            </div>
+                        }
+                    })
+                    .collect::<Vec<_>>()}
+            </div>
+            {summoner
+                .encounter
+                .map(|encounter| {
+                    let vs_match_count = encounter.match_count - encounter.with_match_count;
+                    view! {
+                        <div class="text-xs">
+                            <div>
+                                {format!(
+                                    "You with: {}W {}L",
+                                    encounter.with_win_count,
+                                    encounter.with_match_count - encounter.with_win_count,
+                                )}
+                            </div>
+                            <div>
+                                {format!(
+                                    "You against: {}W {}L",
+                                    encounter.vs_win_count,
+                                    vs_match_count - encounter.vs_win_count,
+                                )}
+                            </div>
+                        </div>
+                    }
+                })}
+        </div>
+    })
+}
+
+#[derive(Clone, Decode, Encode)]
+pub struct MultiSearchPlayer {
+    pub game_name: String,
+    pub tag_line: String,
+    pub summoner: Option<MultiSearchSummoner>,
+}
+
+#[derive(Clone, Decode, Encode)]
+pub struct MultiSearchSummoner {
+    pub id: i32,
+    pub platform: PlatformRoute,
+    pub profile_icon_id: u16,
+    pub summoner_level: u16,
+    pub pro_slug: Option<ProPlayerSlug>,
+    pub recent_matches: u16,
+    pub recent_wins: u16,
+    pub top_champions: Vec<ChampionStats>,
+    pub encounter: Option<MultiSearchEncounter>,
+}
+
+/// Games shared with the current user, wins are counted for the current user.
+#[derive(Clone, PartialEq, Eq, Debug, Decode, Encode)]
+pub struct MultiSearchEncounter {
+    pub match_count: u16,
+    pub with_match_count: u16,
+    pub with_win_count: u16,
+    pub vs_win_count: u16,
+}
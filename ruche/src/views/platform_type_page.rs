@@ -3,7 +3,7 @@ use crate::views::summoner_page::summoner_search_page::SummonerSearchPage;
 use leptos::prelude::*;
 use leptos::{component, view, IntoView};
 use leptos_router::components::Outlet;
-use leptos_router::hooks::use_location;
+use leptos_router::hooks::{use_location, use_params_map};
 
 #[component]
 pub fn PlatformTypePage() -> impl IntoView {
@@ -17,14 +17,20 @@ pub fn PlatformTypePage() -> impl IntoView {
         .image()
         .set("https://ruche.lol/assets/favicon.ico".to_string());
     meta_store.url().set(location.pathname.get());
+    let params = use_params_map();
     let req_include_summoner = move || location.pathname.get().contains("summoners");
+    let req_include_tool_page = move || {
+        let pathname = location.pathname.get();
+        pathname.ends_with("/multisearch") || pathname.ends_with("/champions")
+    };
+    let platform_type = move || params.read().get("platform_type").unwrap_or_default();
     view! {
         <div class="my-0 mx-auto max-w-5xl text-center">
             <a href="/" class="p-6 text-4xl my-4">
                 "Welcome to Ruche"
             </a>
             {move || {
-                (!req_include_summoner())
+                (!req_include_summoner() && !req_include_tool_page())
                     .then(|| {
                         view! {
                             <img src="/assets/logo.avif" class="w-[420px] h-[420px] mx-auto" />
@@ -32,6 +38,27 @@ pub fn PlatformTypePage() -> impl IntoView {
                     })
             }}
             <SummonerSearchPage is_summoner_page=Signal::derive(req_include_summoner) />
+            {move || {
+                (!req_include_summoner() && !req_include_tool_page())
+                    .then(|| {
+                        view! {
+                            <div class="flex justify-center space-x-4">
+                                <a
+                                    href=format!("/platform/{}/multisearch", platform_type())
+                                    class="text-blue-300 hover:underline"
+                                >
+                                    Multi-Search
+                                </a>
+                                <a
+                                    href=format!("/platform/{}/champions", platform_type())
+                                    class="text-blue-300 hover:underline"
+                                >
+                                    Champion Tier List
+                                </a>
+                            </div>
+                        }
+                    })
+            }}
             <Outlet />
         </div>
     }
@@ -1,7 +1,7 @@
 use crate::app::{MetaStore, MetaStoreStoreFields};
 use crate::backend::server_fns::get_summoner::get_summoner;
 use crate::backend::server_fns::update_summoner::UpdateSummoner;
-use crate::utils::{summoner_url, ProPlayerSlug, SSEEvent};
+use crate::utils::{summoner_url, DurationSince, LiveScoutProgress, ProPlayerSlug, SSEEvent};
 use crate::views::summoner_page::summoner_nav::SummonerNav;
 use crate::views::{ImgSrc, PendingLoading};
 use bitcode::{Decode, Encode};
@@ -62,19 +62,24 @@ pub fn SummonerPage() -> impl IntoView {
                     let (sse_match_update_version, set_sse_match_update_version) =
                         signal(None::<SSEMatchUpdateVersion>);
                     let (sse_in_live_game, set_sse_in_live_game) = signal(SSEInLiveGame::default());
+                    let (sse_live_scout_progress, set_sse_live_scout_progress) =
+                        signal(None::<SSELiveScoutProgress>);
 
                     provide_context(sse_match_update_version);
                     provide_context(sse_in_live_game);
+                    provide_context(sse_live_scout_progress);
                     provide_context(summoner.clone());
 
                     let update_summoner_action = ServerAction::<UpdateSummoner>::new();
                     let (pending, set_pending) = signal(false);
+                    let (updated_since, set_updated_since) = signal(summoner.updated_since);
                     Effect::new(move |_| {
                         let _ = update_summoner_action.version().get();
                         set_pending(false);
-                        if let Some(Ok(Some((level, profile_icon_id)))) =
+                        if let Some(Ok(Some((level, profile_icon_id, since)))) =
                             update_summoner_action.value().read_only().get()
                         {
+                            set_updated_since(Some(since));
                             if level != level_signal() {
                                 set_level(level);
                             }
@@ -134,6 +139,9 @@ pub fn SummonerPage() -> impl IntoView {
                             Some(SSEEvent::LiveGame(version)) => {
                                 set_sse_in_live_game(SSEInLiveGame(version));
                             }
+                            Some(SSEEvent::LiveScout(progress)) => {
+                                set_sse_live_scout_progress(Some(SSELiveScoutProgress(progress)));
+                            }
                             _ => {}
                         }
                     });
@@ -170,6 +178,16 @@ pub fn SummonerPage() -> impl IntoView {
                                     >
                                         <PendingLoading pending>Update</PendingLoading>
                                     </button>
+                                    {move || {
+                                        updated_since()
+                                            .map(|since| {
+                                                view! {
+                                                    <div class="text-xs text-gray-400 mt-1">
+                                                        Updated {since.to_string()}
+                                                    </div>
+                                                }
+                                            })
+                                    }}
 
                                 </div>
                             </div>
@@ -252,6 +270,7 @@ pub struct Summoner {
     pub profile_icon_id: u16,
     pub summoner_level: u16,
     pub platform: PlatformRoute,
+    pub updated_since: Option<DurationSince>,
 }
 
 impl Summoner {
@@ -269,3 +288,6 @@ pub struct SSEMatchUpdateVersion(pub u16);
 
 #[derive(Clone, PartialEq, Eq, Copy, Default)]
 pub struct SSEInLiveGame(pub Option<u16>);
+
+#[derive(Clone, PartialEq, Eq, Copy, Default)]
+pub struct SSELiveScoutProgress(pub LiveScoutProgress);
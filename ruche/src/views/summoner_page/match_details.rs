@@ -129,6 +129,14 @@ pub struct LolMatchParticipantDetails {
     pub deaths: u16,
     pub assists: u16,
     pub champ_level: u16,
+    pub player_subteam_id: u16,
+    pub subteam_placement: u16,
+    pub augment1_id: u16,
+    pub augment2_id: u16,
+    pub augment3_id: u16,
+    pub augment4_id: u16,
+    pub augment5_id: u16,
+    pub augment6_id: u16,
     pub wards_placed: u16,
     pub cs: u16,
     pub summoner_spell1_id: u16,
@@ -1,11 +1,14 @@
-use crate::utils::{format_with_spaces, summoner_encounter_url, summoner_url};
+use crate::utils::{format_placement, format_with_spaces, summoner_encounter_url, summoner_url};
 use crate::views::summoner_page::match_details::LolMatchParticipantDetails;
 use crate::views::summoner_page::Summoner;
-use crate::views::{ImgChampion, ImgItem, ImgPerk, ImgSummonerSpell};
+use crate::views::{ImgAugment, ImgChampion, ImgItem, ImgPerk, ImgSummonerSpell};
+use common::consts::augment::Augment;
 use common::consts::champion::Champion;
 use common::consts::item::Item;
 use common::consts::perk::Perk;
 use common::consts::summoner_spell::SummonerSpell;
+use itertools::Itertools;
+use leptos::either::Either;
 use leptos::prelude::*;
 use leptos::{component, view, IntoView};
 
@@ -15,6 +18,33 @@ pub fn MatchDetailsOverview(
     match_details: ReadSignal<Vec<LolMatchParticipantDetails>>,
 ) -> impl IntoView {
     let details = match_details();
+    // arena: one table per 2 players subteam, ordered by placement
+    if details.iter().any(|participant| participant.player_subteam_id > 0) {
+        let subteams = details
+            .into_iter()
+            .sorted_by_key(|participant| (participant.subteam_placement, participant.player_subteam_id))
+            .chunk_by(|participant| participant.player_subteam_id)
+            .into_iter()
+            .map(|(_, subteam)| subteam.collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        return Either::Left(view! {
+            <div>
+                {subteams
+                    .into_iter()
+                    .map(|participants| {
+                        let won = participants.first().map(|p| p.won).unwrap_or_default();
+                        let title = format!(
+                            "{} place",
+                            format_placement(
+                                participants.first().map(|p| p.subteam_placement).unwrap_or_default(),
+                            ),
+                        );
+                        view! { <MatchDetailsOverviewTable won title participants /> }
+                    })
+                    .collect::<Vec<_>>()}
+            </div>
+        });
+    }
     let (summoner_team, summoner_team_won) = {
         let detail = details
             .iter()
@@ -22,6 +52,13 @@ pub fn MatchDetailsOverview(
             .expect("Summoner id not found");
         (detail.team_id, detail.won)
     };
+    let team_title = |won: bool, team_id: u16| {
+        format!(
+            "{} {}",
+            if won { "Victory" } else { "Defeat" },
+            if team_id == 100 { "(Blue Team)" } else { "(Red Team)" },
+        )
+    };
     let other_team = if summoner_team == 100 { 200 } else { 100 };
     let first_team = details
         .iter()
@@ -33,27 +70,36 @@ pub fn MatchDetailsOverview(
         .filter(|participant| participant.team_id != summoner_team)
         .cloned()
         .collect::<Vec<_>>();
-    view! {
+    // swarm is PvE, every player is in the same team
+    if second_team.is_empty() {
+        let title = format!("{} (PvE)", if summoner_team_won { "Victory" } else { "Defeat" });
+        return Either::Right(Either::Left(view! {
+            <div>
+                <MatchDetailsOverviewTable won=summoner_team_won title participants=first_team />
+            </div>
+        }));
+    }
+    Either::Right(Either::Right(view! {
         <div>
             <MatchDetailsOverviewTable
                 won=summoner_team_won
-                team_id=summoner_team
+                title=team_title(summoner_team_won, summoner_team)
                 participants=first_team
             />
             <MatchDetailsOverviewTable
                 won=!summoner_team_won
-                team_id=other_team
+                title=team_title(!summoner_team_won, other_team)
                 participants=second_team
             />
 
         </div>
-    }
+    }))
 }
 
 #[component]
 pub fn MatchDetailsOverviewTable(
     won: bool,
-    team_id: u16,
+    title: String,
     participants: Vec<LolMatchParticipantDetails>,
 ) -> impl IntoView {
     let summoner = expect_context::<Summoner>();
@@ -72,10 +118,7 @@ pub fn MatchDetailsOverviewTable(
             </colgroup>
             <thead>
                 <tr>
-                    <th colspan="4">
-                        <span>{if won { "Victory" } else { "Defeat" }}</span>
-                        {if team_id == 100 { "(Blue Team)" } else { "(Red Team)" }}
-                    </th>
+                    <th colspan="4">{title}</th>
                     <th>KDA</th>
                     <th>Damage</th>
                     <th>Wards</th>
@@ -106,6 +149,17 @@ pub fn MatchDetailsOverviewTable(
                             .iter()
                             .filter_map(|i| Item::try_from(*i).ok())
                             .collect::<Vec<_>>();
+                        let augments = [
+                            participant.augment1_id,
+                            participant.augment2_id,
+                            participant.augment3_id,
+                            participant.augment4_id,
+                            participant.augment5_id,
+                            participant.augment6_id,
+                        ]
+                            .iter()
+                            .filter_map(|i| Augment::try_from(*i).ok())
+                            .collect::<Vec<_>>();
 
                         view! {
                             <tr
@@ -242,6 +296,14 @@ pub fn MatchDetailsOverviewTable(
                                             })
                                             .collect::<Vec<_>>()}
                                     </div>
+                                    <div class="flex gap-0.5 mt-0.5">
+                                        {augments
+                                            .iter()
+                                            .map(|augment| {
+                                                view! { <ImgAugment augment=*augment class="rounded".to_string() /> }
+                                            })
+                                            .collect::<Vec<_>>()}
+                                    </div>
                                 </td>
                             </tr>
                         }
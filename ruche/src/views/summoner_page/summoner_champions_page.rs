@@ -1,6 +1,8 @@
 use crate::app::{MetaStore, MetaStoreStoreFields};
 use crate::backend::server_fns::get_champions::get_champions;
-use crate::utils::{calculate_and_format_kda, format_float_to_2digits, format_with_spaces};
+use crate::utils::{
+    calculate_and_format_kda, format_float_to_2digits, format_placement, format_with_spaces,
+};
 use crate::views::summoner_page::{SSEMatchUpdateVersion, Summoner};
 use crate::views::{BackEndMatchFiltersSearch, ImgChampion};
 use bitcode::{Decode, Encode};
@@ -79,6 +81,7 @@ pub fn SummonerChampionsPage() -> impl IntoView {
                                                     <col width="48" />
                                                     <col width="48" />
                                                     <col width="48" />
+                                                    <col width="96" />
                                                 </colgroup>
                                                 <thead>
                                                     <tr class="bg-gray-800 text-sm h-[32px]">
@@ -226,6 +229,9 @@ pub fn SummonerChampionsPage() -> impl IntoView {
                                                                 Penta kills
                                                             </TableHeaderItem>
                                                         </th>
+                                                        <th class="border border-gray-700 height-inherit text-ellipsis whitespace-nowrap overflow-hidden">
+                                                            Arena Placements
+                                                        </th>
                                                     </tr>
                                                 </thead>
                                                 <tbody>
@@ -309,6 +315,11 @@ pub fn SummonerChampionsPage() -> impl IntoView {
                                                                     <td class="border border-gray-800 text-xs">
                                                                         {champion.total_penta_kills}
                                                                     </td>
+                                                                    <td class="border border-gray-800 text-xs">
+                                                                        <ArenaPlacementDistribution placements=champion
+                                                                            .arena_placements
+                                                                            .clone() />
+                                                                    </td>
                                                                 </tr>
                                                             }
                                                         }
@@ -336,6 +347,31 @@ pub fn SummonerChampionsPage() -> impl IntoView {
     }
 }
 
+/// One bar per placement, 1st on the left, relative to the most frequent one.
+#[component]
+pub fn ArenaPlacementDistribution(placements: Vec<u16>) -> impl IntoView {
+    let max_count = placements.iter().copied().max().unwrap_or_default().max(1);
+    view! {
+        <div class="flex items-end gap-0.5 h-[24px] px-1">
+            {placements
+                .into_iter()
+                .enumerate()
+                .map(|(index, count)| {
+                    view! {
+                        <div
+                            class="w-2 rounded-t-sm"
+                            class=("bg-blue-500", index < 4)
+                            class=("bg-red-500", index >= 4)
+                            style=format!("height:{}%", count as u32 * 100 / max_count as u32)
+                            title=format!("{} place: {}", format_placement(index as u16 + 1), count)
+                        ></div>
+                    }
+                })
+                .collect::<Vec<_>>()}
+        </div>
+    }
+}
+
 #[component]
 pub fn TableHeaderItem<S, R, T>(
     sort_type: TableSortType,
@@ -443,4 +479,6 @@ pub struct ChampionStats {
     pub total_quadra_kills: u16,
     pub total_penta_kills: u16,
     pub avg_kill_participation: u16,
+    /// Arena games count by placement, index 0 is the 1st place.
+    pub arena_placements: Vec<u16>,
 }
@@ -1,10 +1,13 @@
 use crate::app::{MetaStore, MetaStoreStoreFields};
 use crate::backend::server_fns::get_live_game::get_live_game;
+use crate::backend::server_fns::scout_live_game::ScoutLiveGame;
 use crate::utils::{
     calculate_and_format_kda, calculate_loss_and_win_rate, format_float_to_2digits,
     summoner_encounter_url, summoner_url, ProPlayerSlug, RiotMatchId,
 };
-use crate::views::summoner_page::{SSEInLiveGame, SSEMatchUpdateVersion, Summoner};
+use crate::views::summoner_page::{
+    SSEInLiveGame, SSELiveScoutProgress, SSEMatchUpdateVersion, Summoner,
+};
 use crate::views::{ImgChampion, ImgPerk, ImgSummonerSpell, PendingLoading};
 use bitcode::{Decode, Encode};
 use common::consts::champion::Champion;
@@ -22,22 +25,37 @@ pub fn SummonerLivePage() -> impl IntoView {
     let summoner = expect_context::<Summoner>();
     let sse_match_update_version = expect_context::<ReadSignal<Option<SSEMatchUpdateVersion>>>();
     let sse_in_live_game = expect_context::<ReadSignal<SSEInLiveGame>>();
+    let sse_live_scout_progress = expect_context::<ReadSignal<Option<SSELiveScoutProgress>>>();
     let meta_store = expect_context::<reactive_stores::Store<MetaStore>>();
 
     let (refresh_signal, set_refresh_signal) = signal(0);
     let (pending, set_pending) = signal(false);
+    let scout_action = ServerAction::<ScoutLiveGame>::new();
+    // the sse events are newer than the action response once the scout progressed
+    let scout_progress = move || {
+        let sse_progress = sse_live_scout_progress.get().map(|progress| progress.0);
+        let action_progress = scout_action.value().get().and_then(|result| result.ok());
+        sse_progress
+            .into_iter()
+            .chain(action_progress)
+            .max_by_key(|progress| progress.version)
+    };
     let live_game_resource = Resource::new_bitcode(
         move || {
             (
                 sse_in_live_game.get(),
                 sse_match_update_version.get().unwrap_or_default(),
+                sse_live_scout_progress
+                    .get()
+                    .map(|progress| progress.0.version)
+                    .unwrap_or_default(),
                 refresh_signal.get(),
                 summoner.id,
                 summoner.platform.to_string(),
                 set_pending,
             )
         },
-        |(_, _, refresh_version, id, platform_type, set_pending_value)| async move {
+        |(_, _, _, refresh_version, id, platform_type, set_pending_value)| async move {
             let r = get_live_game(
                 id,
                 PlatformRoute::from(platform_type.as_str()),
@@ -70,6 +88,29 @@ pub fn SummonerLivePage() -> impl IntoView {
                 >
                     <PendingLoading pending>Refresh</PendingLoading>
                 </button>
+                <button
+                    class="my-button flex items-center ml-2"
+                    title="Fetch the recent matches of every participant"
+                    disabled=move || {
+                        scout_action.pending().get()
+                            || scout_progress().is_some_and(|progress| progress.done < progress.total)
+                    }
+                    on:click=move |_| {
+                        scout_action
+                            .dispatch(ScoutLiveGame {
+                                summoner_id: summoner.id,
+                                platform_route: summoner.platform,
+                            });
+                    }
+                >
+                    {move || match scout_progress() {
+                        Some(progress) if progress.done < progress.total => {
+                            format!("Scouting {}/{}", progress.done, progress.total)
+                        }
+                        Some(_) => "Lobby Scouted".to_string(),
+                        None => "Scout Lobby".to_string(),
+                    }}
+                </button>
             </div>
             <Transition
                 fallback=move || {
@@ -92,6 +133,11 @@ pub fn SummonerLivePage() -> impl IntoView {
                                 .filter(|participant| participant.team_id == 200)
                                 .cloned()
                                 .collect::<Vec<_>>();
+                            let (first_team_bans, second_team_bans): (Vec<_>, Vec<_>) = result
+                                .bans
+                                .iter()
+                                .cloned()
+                                .partition(|ban| ban.team_id == 100);
                             Either::Right(
                                 view! {
                                     <div class="flex flex-col space-y-2">
@@ -106,7 +152,9 @@ pub fn SummonerLivePage() -> impl IntoView {
                                                 )}
                                             </div>
                                         </div>
+                                        <LiveGameBans bans=first_team_bans />
                                         <MatchLiveTable team_id=100 participants=first_team />
+                                        <LiveGameBans bans=second_team_bans />
                                         <MatchLiveTable team_id=200 participants=second_team />
 
                                     </div>
@@ -121,6 +169,51 @@ pub fn SummonerLivePage() -> impl IntoView {
     }
 }
 
+#[component]
+pub fn LiveGameBans(bans: Vec<LiveGameBan>) -> impl IntoView {
+    (!bans.is_empty())
+        .then(|| {
+            view! {
+                <div class="flex items-center space-x-1 text-xs">
+                    <span class="mr-1">Bans</span>
+                    {bans
+                        .into_iter()
+                        .map(|ban| {
+                            view! {
+                                <ImgChampion
+                                    champion=Champion::from(ban.champion_id)
+                                    class="scale-66 rounded-full block sprite-inner grayscale".to_string()
+                                    parent_class="w-8 h-8 sprite-wrapper".to_string()
+                                />
+                            }
+                        })
+                        .collect::<Vec<_>>()}
+                </div>
+            }
+        })
+}
+
+/// Short label of a riot team position.
+fn role_label(team_position: &str) -> &'static str {
+    match team_position {
+        "TOP" => "Top",
+        "JUNGLE" => "Jungle",
+        "MIDDLE" => "Mid",
+        "BOTTOM" => "Bot",
+        "UTILITY" => "Sup",
+        _ => "-",
+    }
+}
+
+/// Background of the premade badges by group.
+const PREMADE_GROUP_COLORS: [&str; 5] = [
+    "bg-yellow-600",
+    "bg-pink-600",
+    "bg-teal-600",
+    "bg-orange-600",
+    "bg-indigo-600",
+];
+
 #[component]
 pub fn MatchLiveTable(team_id: i32, participants: Vec<LiveGameParticipant>) -> impl IntoView {
     let summoner = expect_context::<Summoner>();
@@ -133,8 +226,8 @@ pub fn MatchLiveTable(team_id: i32, participants: Vec<LiveGameParticipant>) -> i
                 <col width="16" />
                 <col width="15" />
                 <col />
-                <col width="32" />
-                <col width="132" />
+                <col width="40" />
+                <col width="124" />
                 <col width="124" />
                 <col width="100" />
                 <col width="100" />
@@ -149,8 +242,8 @@ pub fn MatchLiveTable(team_id: i32, participants: Vec<LiveGameParticipant>) -> i
                         {if is_blue_team() { "Blue Team" } else { "Red Team" }}
                     </th>
                     <th class="text-left"></th>
-                    <th></th>
-                    <th>S2024</th>
+                    <th>Role</th>
+                    <th>Last 10</th>
                     <th>Ranked Stats</th>
                     <th colspan="2">Champion Stats</th>
 
@@ -167,6 +260,10 @@ pub fn MatchLiveTable(team_id: i32, participants: Vec<LiveGameParticipant>) -> i
                             participant.perk_primary_selection_id,
                         );
                         let perk_sub_style = Perk::from(participant.perk_sub_style_id);
+                        let (show_runes, set_show_runes) = signal(false);
+                        let perk_ids = participant.perk_ids.clone();
+                        let perk_primary_style = Perk::from(participant.perk_primary_style_id);
+                        let blue_team = is_blue_team();
 
                         view! {
                             <tr>
@@ -193,7 +290,11 @@ pub fn MatchLiveTable(team_id: i32, participants: Vec<LiveGameParticipant>) -> i
                                         parent_class="w-4 h-4 sprite-wrapper".to_string()
                                     />
                                 </td>
-                                <td class="py-1">
+                                <td
+                                    class="py-1 cursor-pointer"
+                                    title="Show the rune page"
+                                    on:click=move |_| set_show_runes.update(|show| *show = !*show)
+                                >
                                     <ImgPerk
                                         perk=perk_primary_selection
                                         parent_class="w-4 h-4 sprite-wrapper".to_string()
@@ -207,6 +308,22 @@ pub fn MatchLiveTable(team_id: i32, participants: Vec<LiveGameParticipant>) -> i
                                 </td>
                                 <td class="pl-[5px] py-1 text-ellipsis overflow-hidden text-left">
                                     <div class="flex items-center gap-1">
+                                        {participant
+                                            .premade_group
+                                            .map(|group| {
+                                                view! {
+                                                    <span
+                                                        title="Premade: often plays on the same team as the marked players"
+                                                        class=format!(
+                                                            "text-xs rounded px-0.5 text-center {}",
+                                                            PREMADE_GROUP_COLORS[(group as usize - 1)
+                                                                % PREMADE_GROUP_COLORS.len()],
+                                                        )
+                                                    >
+                                                        {format!("P{}", group)}
+                                                    </span>
+                                                }
+                                            })}
                                         {(participant.encounter_count > 0)
                                             .then(|| {
                                                 view! {
@@ -257,8 +374,31 @@ pub fn MatchLiveTable(team_id: i32, participants: Vec<LiveGameParticipant>) -> i
                                         Lvl. {participant.summoner_level}
                                     </span>
                                 </td>
-                                <td></td>
-                                <td></td>
+                                <td class="py-1">
+                                    {participant.main_role.as_deref().map_or("-", role_label)}
+                                </td>
+                                <td class="py-1 font-mono tracking-tighter">
+                                    {if participant.recent_results.is_empty() {
+                                        Either::Left(view! { <span>-</span> })
+                                    } else {
+                                        Either::Right(
+                                            participant
+                                                .recent_results
+                                                .iter()
+                                                .map(|won| {
+                                                    view! {
+                                                        <span
+                                                            class=("text-green-500", *won)
+                                                            class=("text-red-500", !*won)
+                                                        >
+                                                            {if *won { "W" } else { "L" }}
+                                                        </span>
+                                                    }
+                                                })
+                                                .collect::<Vec<_>>(),
+                                        )
+                                    }}
+                                </td>
                                 <td class="py-1">
                                     {match participant.ranked_stats {
                                         Some(ranked_stats) => {
@@ -340,6 +480,22 @@ pub fn MatchLiveTable(team_id: i32, participants: Vec<LiveGameParticipant>) -> i
                                     }}
                                 </td>
                             </tr>
+                            <Show when=move || show_runes()>
+                                <tr>
+                                    <td
+                                        colspan="9"
+                                        class="border-l-2 pl-2.5 py-1"
+                                        class=("border-red-500", !blue_team)
+                                        class=("border-blue-500", blue_team)
+                                    >
+                                        <LiveRunePage
+                                            perk_primary_style
+                                            perk_sub_style
+                                            perk_ids=perk_ids.clone()
+                                        />
+                                    </td>
+                                </tr>
+                            </Show>
                         }
                     })
                     .collect::<Vec<_>>()}
@@ -347,6 +503,40 @@ pub fn MatchLiveTable(team_id: i32, participants: Vec<LiveGameParticipant>) -> i
         </table>
     }
 }
+/// Spectator rune page: primary style and its 4 runes, secondary style and its 2 runes, then the
+/// 3 stat shards.
+#[component]
+pub fn LiveRunePage(
+    perk_primary_style: Perk,
+    perk_sub_style: Perk,
+    perk_ids: Vec<u16>,
+) -> impl IntoView {
+    let perk_at = |index: usize| Perk::from(perk_ids.get(index).copied().unwrap_or_default());
+    let rune_row = |perks: Vec<Perk>| {
+        perks
+            .into_iter()
+            .map(|perk| {
+                view! { <ImgPerk perk class="w-[28px] h-[28px] rounded".to_string() /> }
+            })
+            .collect::<Vec<_>>()
+    };
+    view! {
+        <div class="flex space-x-2 text-xs">
+            <div class="flex space-x-1.5">
+                {rune_row(
+                    vec![perk_primary_style, perk_at(0), perk_at(1), perk_at(2), perk_at(3)],
+                )}
+            </div>
+            <div class="border-l-2 flex space-x-1 border-gray-900 pl-1.5">
+                {rune_row(vec![perk_sub_style, perk_at(4), perk_at(5)])}
+            </div>
+            <div class="border-l-2 flex space-x-1 border-gray-900 pl-1.5">
+                {rune_row(vec![perk_at(6), perk_at(7), perk_at(8)])}
+            </div>
+        </div>
+    }
+}
+
 #[derive(Clone, Decode, Encode)]
 pub struct LiveGame {
     pub game_length: u16,
@@ -354,6 +544,15 @@ pub struct LiveGame {
     pub queue: Queue,
     pub game_id: RiotMatchId,
     pub participants: Vec<LiveGameParticipant>,
+    /// Spectator bans by pick turn, champions not banned are skipped.
+    pub bans: Vec<LiveGameBan>,
+}
+
+#[derive(Clone, Decode, Encode)]
+pub struct LiveGameBan {
+    pub champion_id: u16,
+    pub team_id: u16,
+    pub pick_turn: u8,
 }
 
 #[derive(Clone, Decode, Encode)]
@@ -362,10 +561,19 @@ pub struct LiveGameParticipant {
     pub champion_id: u16,
     pub team_id: u16,
     pub encounter_count: u16,
+    /// Shared by the participants who often play together on the same team, from 1.
+    pub premade_group: Option<u8>,
     pub summoner_spell1_id: u16,
     pub summoner_spell2_id: u16,
     pub perk_primary_selection_id: u16,
+    pub perk_primary_style_id: u16,
     pub perk_sub_style_id: u16,
+    /// Full spectator rune page, see `LiveRunePage`.
+    pub perk_ids: Vec<u16>,
+    /// Results of the last 10 stored matches, newest first.
+    pub recent_results: Vec<bool>,
+    /// Position played the most in those matches.
+    pub main_role: Option<String>,
     pub summoner_level: u16,
     pub platform: PlatformRoute,
     pub game_name: String,
@@ -2,28 +2,33 @@ use crate::app::{MetaStore, MetaStoreStoreFields};
 use crate::backend::server_fns::get_matches::get_matches;
 use crate::utils::{
     calculate_and_format_kda, calculate_loss_and_win_rate, format_duration,
-    format_float_to_2digits, summoner_encounter_url, summoner_url, DurationSince, ProPlayerSlug,
-    RiotMatchId,
+    format_float_to_2digits, format_placement, summoner_encounter_url, summoner_url,
+    DurationSince, ProPlayerSlug, RiotMatchId,
 };
 use crate::views::components::pagination::Pagination;
 use crate::views::summoner_page::match_details::MatchDetails;
 use crate::views::summoner_page::{SSEMatchUpdateVersion, Summoner};
 use crate::views::{
-    get_default_navigation_option, BackEndMatchFiltersSearch, ImgChampion, ImgItem, ImgPerk,
-    ImgSummonerSpell,
+    get_default_navigation_option, BackEndMatchFiltersSearch, ImgAugment, ImgChampion, ImgItem,
+    ImgPerk, ImgSummonerSpell,
 };
 use bitcode::{Decode, Encode};
+use common::consts::augment::Augment;
 use common::consts::champion::Champion;
 use common::consts::item::Item;
 use common::consts::perk::Perk;
 use common::consts::platform_route::PlatformRoute;
 use common::consts::queue::Queue;
 use common::consts::summoner_spell::SummonerSpell;
+use itertools::Itertools;
 use leptos::either::Either;
+use leptos::ev;
 use leptos::logging::log;
 use leptos::prelude::*;
 use leptos::{component, view, IntoView};
 use leptos_router::hooks::query_signal_with_options;
+use std::collections::{BTreeMap, HashMap};
+
 #[component]
 pub fn SummonerMatchesPage() -> impl IntoView {
     let summoner = expect_context::<Summoner>();
@@ -34,6 +39,9 @@ pub fn SummonerMatchesPage() -> impl IntoView {
     let (page_number, set_page_number) =
         query_signal_with_options::<u16>("page", get_default_navigation_option());
 
+    let (infinite_scroll, set_infinite_scroll) =
+        query_signal_with_options::<bool>("infinite", get_default_navigation_option());
+
     let (reset_page_number, set_reset_page_number) = signal::<bool>(false);
     Effect::new(move |_| {
         if reset_page_number() {
@@ -42,20 +50,92 @@ pub fn SummonerMatchesPage() -> impl IntoView {
         }
     });
 
+    // cursor of each page reached from its previous page, pages jumped to start from the closest
+    // cursor before them and skip the pages in between
+    let page_cursors = StoredValue::new(PageCursors::default());
+
     let matches_resource = Resource::new_bitcode(
         move || {
-            (
-                sse_match_update_version.get().unwrap_or_default(),
-                match_filters_updated.get(),
-                summoner.id,
-                page_number(),
-            )
+            let page_number = if infinite_scroll().unwrap_or_default() {
+                1
+            } else {
+                page_number().unwrap_or(1)
+            };
+            let version = sse_match_update_version.get().unwrap_or_default();
+            let filters = match_filters_updated.get();
+            let cursor = page_cursors
+                .with_value(|page_cursors| page_cursors.get(version, &filters, page_number));
+            (version, filters, summoner.id, page_number, cursor)
         },
-        |(_, filters, id, page_number)| async move {
-            get_matches(id, page_number.unwrap_or(1), Some(filters)).await
+        move |(version, filters, id, page_number, cursor)| async move {
+            let result = get_matches(id, page_number, cursor, Some(filters.clone())).await;
+            if let Ok(result) = &result {
+                if let Some(next_cursor) = result.next_cursor {
+                    page_cursors.update_value(|page_cursors| {
+                        page_cursors.set(version, filters, next_cursor)
+                    });
+                }
+            }
+            result
         },
     );
 
+    // infinite scroll: matches loaded after the first page
+    let more_matches = RwSignal::new(Vec::<SummonerMatch>::new());
+    let next_cursor = RwSignal::new(None::<MatchesCursor>);
+    let load_more_action = Action::new(
+        move |(cursor, filters): &(MatchesCursor, BackEndMatchFiltersSearch)| {
+            let cursor = *cursor;
+            let filters = filters.clone();
+            async move {
+                let result = get_matches(
+                    summoner.id,
+                    cursor.page,
+                    Some(cursor),
+                    Some(filters.clone()),
+                )
+                .await;
+                (filters, result)
+            }
+        },
+    );
+    Effect::new(move |_| {
+        if let Some((filters, Ok(result))) = load_more_action.value().get() {
+            if filters != match_filters_updated.get_untracked() {
+                return;
+            }
+            next_cursor.set(result.next_cursor);
+            more_matches.update(|matches| matches.extend(result.matches));
+        }
+    });
+    let load_more = move || {
+        if !infinite_scroll.get_untracked().unwrap_or_default()
+            || load_more_action.pending().get_untracked()
+        {
+            return;
+        }
+        if let Some(cursor) = next_cursor.get_untracked() {
+            load_more_action.dispatch((cursor, match_filters_updated.get_untracked()));
+        }
+    };
+    let scroll_listener = window_event_listener(ev::scroll, move |_| {
+        let window = window();
+        let scroll_bottom = window.scroll_y().unwrap_or_default()
+            + window
+                .inner_height()
+                .ok()
+                .and_then(|height| height.as_f64())
+                .unwrap_or_default();
+        let page_height = document()
+            .document_element()
+            .map(|element| element.scroll_height() as f64)
+            .unwrap_or_default();
+        if page_height - scroll_bottom < INFINITE_SCROLL_THRESHOLD {
+            load_more();
+        }
+    });
+    on_cleanup(move || scroll_listener.remove());
+
     meta_store.title().set(format!(
         "{}#{} | Matches | Ruche",
         summoner.game_name.as_str(),
@@ -72,6 +152,8 @@ pub fn SummonerMatchesPage() -> impl IntoView {
                     {move || Suspend::new(async move {
                         match matches_resource.await {
                             Ok(matches_result) => {
+                                more_matches.set(vec![]);
+                                next_cursor.set(matches_result.next_cursor);
                                 let total_pages = matches_result.total_pages;
                                 let current_page = page_number().unwrap_or(1);
                                 if total_pages == 0 || total_pages < current_page {
@@ -91,6 +173,20 @@ pub fn SummonerMatchesPage() -> impl IntoView {
                                                 matches_result.matches_result_info.total_matches,
                                             );
                                             view! {
+                                                <label class="flex items-center justify-end text-sm mt-2">
+                                                    <input
+                                                        type="checkbox"
+                                                        class="mr-1"
+                                                        prop:checked=move || infinite_scroll().unwrap_or_default()
+                                                        on:change=move |e| {
+                                                            set_page_number(None);
+                                                            set_infinite_scroll(
+                                                                event_target_checked(&e).then_some(true),
+                                                            );
+                                                        }
+                                                    />
+                                                    Infinite scroll
+                                                </label>
                                                 <div class="my-2 flex my-card w-fit">
                                                     <div class="flex flex-col">
                                                         <div>
@@ -134,8 +230,43 @@ pub fn SummonerMatchesPage() -> impl IntoView {
                                                         <MatchCard match_=match_ />
                                                     </For>
                                                 </div>
-                                                <Show when=move || (total_pages > 1)>
-                                                    <Pagination max_page=total_pages />
+                                                <Show
+                                                    when=move || infinite_scroll().unwrap_or_default()
+                                                    fallback=move || {
+                                                        view! {
+                                                            <Show when=move || (total_pages > 1)>
+                                                                <Pagination max_page=total_pages />
+                                                            </Show>
+                                                        }
+                                                    }
+                                                >
+                                                    <div class="text-gray-200 space-y-2 mt-2">
+                                                        <For
+                                                            each=move || more_matches.get()
+                                                            key=|match_| match_.match_id
+                                                            let:match_
+                                                        >
+                                                            <MatchCard match_=match_ />
+                                                        </For>
+                                                    </div>
+                                                    <Show when=move || {
+                                                        next_cursor.get().is_some()
+                                                            || load_more_action.pending().get()
+                                                    }>
+                                                        <button
+                                                            class="my-button my-2"
+                                                            disabled=move || load_more_action.pending().get()
+                                                            on:click=move |_| load_more()
+                                                        >
+                                                            {move || {
+                                                                if load_more_action.pending().get() {
+                                                                    "Loading..."
+                                                                } else {
+                                                                    "Load more"
+                                                                }
+                                                            }}
+                                                        </button>
+                                                    </Show>
                                                 </Show>
                                             }
                                         }),
@@ -153,17 +284,17 @@ pub fn SummonerMatchesPage() -> impl IntoView {
 
 #[component]
 pub fn MatchCard(match_: SummonerMatch) -> impl IntoView {
-    let summoner = expect_context::<Summoner>();
     let (show_details, set_show_details) = signal(false);
     let champion = Champion::from(match_.champion_id);
     let summoner_spell1 = SummonerSpell::from(match_.summoner_spell1_id);
     let summoner_spell2 = SummonerSpell::from(match_.summoner_spell2_id);
     let primary_perk_selection = Perk::from(match_.perk_primary_selection_id);
     let sub_perk_style = Perk::from(match_.perk_sub_style_id);
-    if primary_perk_selection == Perk::UNKNOWN {
+    // arena and swarm have no runes
+    if primary_perk_selection == Perk::UNKNOWN && match_.perk_primary_selection_id != 0 {
         log!("{:?}", match_.perk_primary_selection_id);
     }
-    if sub_perk_style == Perk::UNKNOWN {
+    if sub_perk_style == Perk::UNKNOWN && match_.perk_sub_style_id != 0 {
         log!("{:?}", match_.perk_sub_style_id);
     }
     let items = [
@@ -178,6 +309,27 @@ pub fn MatchCard(match_: SummonerMatch) -> impl IntoView {
     .iter()
     .filter_map(|id| Item::try_from(*id).ok())
     .collect::<Vec<_>>();
+    let is_arena = match_.queue.is_arena();
+    let augments = [
+        match_.augment1_id,
+        match_.augment2_id,
+        match_.augment3_id,
+        match_.augment4_id,
+        match_.augment5_id,
+        match_.augment6_id,
+    ]
+    .iter()
+    .filter_map(|id| Augment::try_from(*id).ok())
+    .collect::<Vec<_>>();
+    let participants_view = if is_arena {
+        Either::Left(view! {
+            <ArenaSubteams participants=match_.participants summoner_id=match_.summoner_id />
+        })
+    } else {
+        Either::Right(view! {
+            <MatchParticipants participants=match_.participants summoner_id=match_.summoner_id />
+        })
+    };
 
     view! {
         <div class="flex flex-col">
@@ -197,6 +349,7 @@ pub fn MatchCard(match_: SummonerMatch) -> impl IntoView {
                         queue=match_.queue
                         match_ended_since=match_.match_ended_since
                         match_duration=match_.match_duration
+                        placement=match_.subteam_placement
                     />
                     <MatchSummonerCard
                         champion=champion
@@ -211,74 +364,9 @@ pub fn MatchCard(match_: SummonerMatch) -> impl IntoView {
                         won=match_.won
                         kill_participation=match_.kill_participation
                         items=items
+                        augments=augments
                     />
-
-                    <div
-                        class="flex gap-x-2 gap-y-0.5 w-[266px] max-h-[89px]"
-                        style="flex-flow:column wrap"
-                    >
-                        {match_
-                            .participants
-                            .into_iter()
-                            .map(|participant| {
-                                let champion = Champion::from(participant.champion_id);
-                                view! {
-                                    <div class="flex items-center gap-1 w-[130px]">
-                                        <ImgChampion
-                                            champion
-                                            parent_class="sprite-wrapper w-4 h-4".to_string()
-                                            class="rounded scale-33 sprite-inner".to_string()
-                                        />
-                                        {(participant.encounter_count > 1)
-                                            .then(|| {
-                                                view! {
-                                                    <a
-                                                        href=summoner_encounter_url(
-                                                            summoner.platform.as_ref(),
-                                                            summoner.game_name.as_str(),
-                                                            summoner.tag_line.as_str(),
-                                                            participant.platform.as_ref(),
-                                                            participant.game_name.as_str(),
-                                                            participant.tag_line.as_str(),
-                                                        )
-                                                        class="text-xs bg-green-800 rounded px-0.5 text-center"
-                                                    >
-                                                        {participant.encounter_count}
-                                                    </a>
-                                                }
-                                            })}
-                                        {participant
-                                            .pro_player_slug
-                                            .map(|pps| {
-                                                view! {
-                                                    <a
-                                                        target="_blank"
-                                                        href=format!("https://lolpros.gg/player/{}", pps.as_ref())
-                                                        class="text-xs bg-purple-800 rounded px-0.5 text-center"
-                                                    >
-                                                        pro
-                                                    </a>
-                                                }
-                                            })}
-                                        <a
-                                            target="_blank"
-                                            href=summoner_url(
-                                                participant.platform.as_ref(),
-                                                participant.game_name.as_str(),
-                                                participant.tag_line.as_str(),
-                                            )
-                                            class:text-white=participant.summoner_id
-                                                == match_.summoner_id
-
-                                            class="text-ellipsis overflow-hidden whitespace-nowrap "
-                                        >
-                                            {participant.game_name.clone()}
-                                        </a>
-                                    </div>
-                                }
-                            })
-                            .collect::<Vec<_>>()}
-                    </div>
+                    {participants_view}
                 </div>
                 <div class="w-[40px] flex relative flex-col">
                     <button
@@ -328,6 +416,133 @@ pub fn MatchCard(match_: SummonerMatch) -> impl IntoView {
     }
 }
 
+#[component]
+pub fn MatchParticipants(
+    participants: Vec<SummonerMatchParticipant>,
+    summoner_id: i32,
+) -> impl IntoView {
+    let summoner = expect_context::<Summoner>();
+    view! {
+        <div class="flex gap-x-2 gap-y-0.5 w-[266px] max-h-[89px]" style="flex-flow:column wrap">
+            {participants
+                .into_iter()
+                .map(|participant| {
+                    let champion = Champion::from(participant.champion_id);
+                    view! {
+                        <div class="flex items-center gap-1 w-[130px]">
+                            <ImgChampion
+                                champion
+                                parent_class="sprite-wrapper w-4 h-4".to_string()
+                                class="rounded scale-33 sprite-inner".to_string()
+                            />
+                            {(participant.encounter_count > 1)
+                                .then(|| {
+                                    view! {
+                                        <a
+                                            href=summoner_encounter_url(
+                                                summoner.platform.as_ref(),
+                                                summoner.game_name.as_str(),
+                                                summoner.tag_line.as_str(),
+                                                participant.platform.as_ref(),
+                                                participant.game_name.as_str(),
+                                                participant.tag_line.as_str(),
+                                            )
+                                            class="text-xs bg-green-800 rounded px-0.5 text-center"
+                                        >
+                                            {participant.encounter_count}
+                                        </a>
+                                    }
+                                })}
+                            {participant
+                                .pro_player_slug
+                                .map(|pps| {
+                                    view! {
+                                        <a
+                                            target="_blank"
+                                            href=format!("https://lolpros.gg/player/{}", pps.as_ref())
+                                            class="text-xs bg-purple-800 rounded px-0.5 text-center"
+                                        >
+                                            pro
+                                        </a>
+                                    }
+                                })}
+                            <a
+                                target="_blank"
+                                href=summoner_url(
+                                    participant.platform.as_ref(),
+                                    participant.game_name.as_str(),
+                                    participant.tag_line.as_str(),
+                                )
+                                class:text-white=participant.summoner_id == summoner_id
+
+                                class="text-ellipsis overflow-hidden whitespace-nowrap "
+                            >
+                                {participant.game_name.clone()}
+                            </a>
+                        </div>
+                    }
+                })
+                .collect::<Vec<_>>()}
+        </div>
+    }
+}
+
+/// Arena participants, one row per 2 players subteam ordered by placement.
+#[component]
+pub fn ArenaSubteams(participants: Vec<SummonerMatchParticipant>, summoner_id: i32) -> impl IntoView {
+    let subteams = participants
+        .into_iter()
+        .chunk_by(|participant| participant.player_subteam_id)
+        .into_iter()
+        .map(|(_, subteam)| subteam.collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    view! {
+        <div class="flex gap-x-2 gap-y-0.5 w-[266px] max-h-[89px]" style="flex-flow:column wrap">
+            {subteams
+                .into_iter()
+                .map(|subteam| {
+                    let placement = subteam.first().map(|p| p.subteam_placement).unwrap_or_default();
+                    let is_summoner_subteam = subteam.iter().any(|p| p.summoner_id == summoner_id);
+                    view! {
+                        <div class="flex items-center gap-1 w-[130px]">
+                            <span class="w-[14px] text-gray-400" class:text-white=is_summoner_subteam>
+                                {placement}
+                            </span>
+                            {subteam
+                                .into_iter()
+                                .map(|participant| {
+                                    let champion = Champion::from(participant.champion_id);
+                                    view! {
+                                        <div class="flex items-center gap-0.5 w-[56px]">
+                                            <ImgChampion
+                                                champion
+                                                parent_class="sprite-wrapper w-4 h-4 shrink-0".to_string()
+                                                class="rounded scale-33 sprite-inner".to_string()
+                                            />
+                                            <a
+                                                target="_blank"
+                                                href=summoner_url(
+                                                    participant.platform.as_ref(),
+                                                    participant.game_name.as_str(),
+                                                    participant.tag_line.as_str(),
+                                                )
+                                                class:text-white=participant.summoner_id == summoner_id
+                                                class="text-ellipsis overflow-hidden whitespace-nowrap"
+                                            >
+                                                {participant.game_name.clone()}
+                                            </a>
+                                        </div>
+                                    }
+                                })
+                                .collect::<Vec<_>>()}
+                        </div>
+                    }
+                })
+                .collect::<Vec<_>>()}
+        </div>
+    }
+}
+
 #[component]
 pub fn MatchSummonerCard(
     champion: Champion,
@@ -342,6 +557,9 @@ pub fn MatchSummonerCard(
     won: bool,
     kill_participation: u16,
     items: Vec<Item>,
+    /// Arena augments, shown instead of the runes.
+    #[prop(optional)]
+    augments: Vec<Augment>,
     #[prop(optional)] encounter_is_self: Option<bool>,
 ) -> impl IntoView {
     let has_encounter = encounter_is_self.is_some();
@@ -379,18 +597,37 @@ pub fn MatchSummonerCard(
                             class="rounded".to_string()
                         />
                     </div>
-                    <div class="flex flex-col gap-0.5 items-center">
-                        <ImgPerk
-                            perk=primary_perk_selection
-                            class="scale-78 sprite-inner rounded-full".to_string()
-                            parent_class="w-[22px] h-[22px] sprite-wrapper".to_string()
-                        />
-                        <ImgPerk
-                            perk=sub_perk_style
-                            class="scale-78 sprite-inner rounded-full".to_string()
-                            parent_class="w-[22px] h-[22px] sprite-wrapper".to_string()
-                        />
-                    </div>
+                    {if augments.is_empty() {
+                        Either::Left(
+                            view! {
+                                <div class="flex flex-col gap-0.5 items-center">
+                                    <ImgPerk
+                                        perk=primary_perk_selection
+                                        class="scale-78 sprite-inner rounded-full".to_string()
+                                        parent_class="w-[22px] h-[22px] sprite-wrapper".to_string()
+                                    />
+                                    <ImgPerk
+                                        perk=sub_perk_style
+                                        class="scale-78 sprite-inner rounded-full".to_string()
+                                        parent_class="w-[22px] h-[22px] sprite-wrapper".to_string()
+                                    />
+                                </div>
+                            },
+                        )
+                    } else {
+                        Either::Right(
+                            view! {
+                                <div class="grid grid-cols-3 gap-0.5">
+                                    {augments
+                                        .into_iter()
+                                        .map(|augment| {
+                                            view! { <ImgAugment augment class="rounded".to_string() /> }
+                                        })
+                                        .collect::<Vec<_>>()}
+                                </div>
+                            },
+                        )
+                    }}
                 </div>
                 <div class="flex flex-col w-[85px] items-start gap-1">
                     <div class="text-base">
@@ -432,6 +669,9 @@ pub fn MatchInfoCard(
     queue: Queue,
     match_ended_since: DurationSince,
     match_duration: Option<i32>,
+    /// Arena subteam placement, 0 for the other queues.
+    #[prop(optional)]
+    placement: u16,
 ) -> impl IntoView {
     view! {
         <div class="flex flex-col w-[108px] gap-2">
@@ -451,18 +691,96 @@ pub fn MatchInfoCard(
                 class="w-1/2"
             />
             <div class="flex flex-col items-start w-[108px]">
-                {won.map(|won| view! { <div>{if won { "Victory" } else { "Defeat" }}</div> })}
+                {if placement > 0 {
+                    Either::Left(view! { <div>{format_placement(placement)}" place"</div> })
+                } else {
+                    Either::Right(
+                        won.map(|won| view! { <div>{if won { "Victory" } else { "Defeat" }}</div> }),
+                    )
+                }}
                 <div>{format_duration(match_duration)}</div>
             </div>
         </div>
     }
 }
 
+/// Distance to the bottom of the page, in pixels, at which the next matches are loaded.
+const INFINITE_SCROLL_THRESHOLD: f64 = 600.0;
+
+#[derive(Default)]
+struct PageCursors {
+    version: SSEMatchUpdateVersion,
+    filters: BackEndMatchFiltersSearch,
+    cursors: BTreeMap<u16, MatchesCursor>,
+}
+
+impl PageCursors {
+    /// Returns the cursor of the closest page up to `page_number`.
+    fn get(
+        &self,
+        version: SSEMatchUpdateVersion,
+        filters: &BackEndMatchFiltersSearch,
+        page_number: u16,
+    ) -> Option<MatchesCursor> {
+        (self.version == version && &self.filters == filters)
+            .then(|| {
+                self.cursors
+                    .range(..=page_number)
+                    .next_back()
+                    .map(|(_, cursor)| *cursor)
+            })
+            .flatten()
+    }
+
+    fn set(
+        &mut self,
+        version: SSEMatchUpdateVersion,
+        filters: BackEndMatchFiltersSearch,
+        cursor: MatchesCursor,
+    ) {
+        if self.version != version || self.filters != filters {
+            self.version = version;
+            self.filters = filters;
+            self.cursors.clear();
+        }
+        self.cursors.insert(cursor.page, cursor);
+    }
+}
+
 #[derive(Clone, Default, Encode, Decode)]
 pub struct GetSummonerMatchesResult {
     pub total_pages: u16,
     pub matches: Vec<SummonerMatch>,
     pub matches_result_info: MatchesResultInfo,
+    /// Cursor of the following page, none on the last page.
+    pub next_cursor: Option<MatchesCursor>,
+}
+
+/// Keyset position in the matches list, `(match_end, lol_match_id)` of the last match of a page.
+#[derive(Clone, Copy, Debug, PartialEq, Encode, Decode)]
+pub struct MatchesCursor {
+    /// Unix timestamp in microseconds, the precision of `lol_matches.match_end`.
+    pub match_end: i64,
+    pub lol_match_id: i32,
+    /// Page starting right after the cursor.
+    pub page: u16,
+}
+
+#[cfg(feature = "ssr")]
+impl MatchesCursor {
+    pub fn new(match_end: chrono::NaiveDateTime, lol_match_id: i32, page: u16) -> Self {
+        Self {
+            match_end: match_end.and_utc().timestamp_micros(),
+            lol_match_id,
+            page,
+        }
+    }
+
+    pub fn match_end_to_naive(&self) -> chrono::NaiveDateTime {
+        chrono::DateTime::from_timestamp_micros(self.match_end)
+            .unwrap_or_default()
+            .naive_utc()
+    }
 }
 
 #[derive(Clone, Default, Encode, Decode)]
@@ -487,6 +805,14 @@ pub struct SummonerMatch {
     pub item4_id: u32,
     pub item5_id: u32,
     pub item6_id: u32,
+    pub player_subteam_id: u16,
+    pub subteam_placement: u16,
+    pub augment1_id: u16,
+    pub augment2_id: u16,
+    pub augment3_id: u16,
+    pub augment4_id: u16,
+    pub augment5_id: u16,
+    pub augment6_id: u16,
     pub champ_level: u16,
     pub kill_participation: u16,
     pub champion_id: u16,
@@ -510,6 +836,8 @@ pub struct SummonerMatchParticipant {
     pub summoner_id: i32,
     pub champion_id: u16,
     pub team_id: u16,
+    pub player_subteam_id: u16,
+    pub subteam_placement: u16,
     pub encounter_count: u16,
     pub game_name: String,
     pub pro_player_slug: Option<ProPlayerSlug>,
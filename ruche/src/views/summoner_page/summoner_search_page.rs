@@ -1,17 +1,28 @@
-use crate::backend::server_fns::search_summoner::SearchSummoner;
-use crate::views::PendingLoading;
+use crate::backend::server_fns::search_summoner::{SearchSummoner, SearchSummonerAnyPlatform};
+use crate::backend::server_fns::suggest_summoners::suggest_summoners;
+use crate::utils::{summoner_url, ProPlayerSlug};
+use crate::views::{ImgSrc, PendingLoading};
+use bitcode::{Decode, Encode};
 use common::consts::platform_route::{PlatformRoute, PLATFORM_ROUTE_OPTIONS};
+use common::consts::profile_icon::ProfileIcon;
+use common::consts::HasStaticSrcAsset;
 use leptos::ev::SubmitEvent;
 use leptos::html::{Input, Select};
 use leptos::prelude::*;
 use leptos::{component, view, IntoView};
 use leptos_router::hooks::{use_params_map, use_query_map};
+use std::time::Duration;
+
+pub const SUGGESTIONS_MIN_QUERY_LEN: usize = 2;
+const SUGGESTIONS_DEBOUNCE: Duration = Duration::from_millis(250);
+const ANY_PLATFORM_OPTION: &str = "ANY";
 
 #[component]
 pub fn SummonerSearchPage(is_summoner_page: Signal<bool>) -> impl IntoView {
     let query = use_query_map();
     let params = use_params_map();
     let search_summoner = ServerAction::<SearchSummoner>::new();
+    let search_summoner_any_platform = ServerAction::<SearchSummonerAnyPlatform>::new();
     let (pending, set_pending) = signal(false);
 
     let game_name = move || query.read().get("game_name").unwrap_or_default();
@@ -23,6 +34,33 @@ pub fn SummonerSearchPage(is_summoner_page: Signal<bool>) -> impl IntoView {
             .unwrap_or(PlatformRoute::EUW1.to_string())
     };
 
+    let (suggestion_query, set_suggestion_query) = signal(String::new());
+    let (show_suggestions, set_show_suggestions) = signal(false);
+    let suggestion_timeout = StoredValue::new(None::<TimeoutHandle>);
+
+    let suggestions_resource = Resource::new_bitcode(
+        move || (platform_type(), suggestion_query.get()),
+        |(platform_type, query)| async move {
+            if query.trim().chars().count() < SUGGESTIONS_MIN_QUERY_LEN {
+                Ok(vec![])
+            } else {
+                suggest_summoners(PlatformRoute::from(platform_type.as_str()), query).await
+            }
+        },
+    );
+
+    let on_game_name_input = move |ev: leptos::ev::Event| {
+        let value = event_target_value(&ev);
+        if let Some(handle) = suggestion_timeout.get_value() {
+            handle.clear();
+        }
+        set_show_suggestions(true);
+        suggestion_timeout.set_value(
+            set_timeout_with_handle(move || set_suggestion_query(value), SUGGESTIONS_DEBOUNCE)
+                .ok(),
+        );
+    };
+
     let game_name_node = NodeRef::<Input>::new();
     let tag_line_node = NodeRef::<Input>::new();
     let platform_type_node = NodeRef::<Select>::new();
@@ -30,24 +68,41 @@ pub fn SummonerSearchPage(is_summoner_page: Signal<bool>) -> impl IntoView {
     let on_submit = move |ev: SubmitEvent| {
         ev.prevent_default();
         set_pending(true);
-        search_summoner.dispatch(SearchSummoner {
-            platform_route: PlatformRoute::from(
-                platform_type_node
-                    .get()
-                    .expect("platform_type not valid")
-                    .value()
-                    .as_str(),
-            ),
-            game_name: game_name_node.get().expect("game_name not valid").value(),
-            tag_line: tag_line_node.get().expect("tag_line not valid").value(),
-        });
+        let selected_platform = platform_type_node
+            .get()
+            .expect("platform_type not valid")
+            .value();
+        let game_name = game_name_node.get().expect("game_name not valid").value();
+        let tag_line = tag_line_node.get().expect("tag_line not valid").value();
+        if selected_platform == ANY_PLATFORM_OPTION {
+            search_summoner_any_platform.dispatch(SearchSummonerAnyPlatform {
+                platform_route: PlatformRoute::from(platform_type().as_str()),
+                game_name,
+                tag_line,
+            });
+        } else {
+            search_summoner.dispatch(SearchSummoner {
+                platform_route: PlatformRoute::from(selected_platform.as_str()),
+                game_name,
+                tag_line,
+            });
+        }
     };
 
     Effect::new(move |_| {
         let _ = search_summoner.version().get();
+        let _ = search_summoner_any_platform.version().get();
         set_pending(false);
     });
 
+    let platform_candidates = move || {
+        search_summoner_any_platform
+            .value()
+            .get()
+            .and_then(|candidates| candidates.ok())
+            .filter(|candidates| candidates.len() > 1)
+    };
+
     view! {
         <div class=" w-full flex my-2 justify-center">
             <form
@@ -68,14 +123,55 @@ pub fn SummonerSearchPage(is_summoner_page: Signal<bool>) -> impl IntoView {
                             }
                         })
                 }}
-                <input
-                    class="my-input"
-                    type="text"
-                    node_ref=game_name_node
-                    placeholder="Game Name"
-                    value=game_name
-                    name="game_name"
-                />
+                <div class="relative">
+                    <input
+                        class="my-input"
+                        type="text"
+                        node_ref=game_name_node
+                        placeholder="Game Name"
+                        value=game_name
+                        name="game_name"
+                        autocomplete="off"
+                        on:input=on_game_name_input
+                        on:focus=move |_| set_show_suggestions(true)
+                        on:blur=move |_| set_show_suggestions(false)
+                        on:keydown=move |ev| {
+                            if ev.key() == "Escape" {
+                                set_show_suggestions(false);
+                            }
+                        }
+                    />
+                    <Show when=move || show_suggestions()>
+                        <Transition fallback=|| ()>
+                            {move || Suspend::new(async move {
+                                suggestions_resource
+                                    .await
+                                    .ok()
+                                    .filter(|suggestions| !suggestions.is_empty())
+                                    .map(|suggestions| {
+                                        view! {
+                                            <div
+                                                class="absolute z-10 mt-1 w-[320px] bg-gray-800 border border-gray-600 rounded shadow-lg"
+                                                on:mousedown=|ev| ev.prevent_default()
+                                            >
+                                                {suggestions
+                                                    .into_iter()
+                                                    .map(|suggestion| {
+                                                        view! {
+                                                            <SummonerSuggestionItem
+                                                                suggestion
+                                                                on_select=move || set_show_suggestions(false)
+                                                            />
+                                                        }
+                                                    })
+                                                    .collect::<Vec<_>>()}
+                                            </div>
+                                        }
+                                    })
+                            })}
+                        </Transition>
+                    </Show>
+                </div>
                 <input
                     class="my-input"
                     type="text"
@@ -91,6 +187,7 @@ pub fn SummonerSearchPage(is_summoner_page: Signal<bool>) -> impl IntoView {
                     name="platform_type"
                     prop:value=platform_type
                 >
+                    <option value=ANY_PLATFORM_OPTION>Any</option>
                     {PLATFORM_ROUTE_OPTIONS
                         .iter()
                         .map(|pt| {
@@ -110,5 +207,91 @@ pub fn SummonerSearchPage(is_summoner_page: Signal<bool>) -> impl IntoView {
                 </button>
             </form>
         </div>
+        {move || {
+            platform_candidates()
+                .map(|candidates| {
+                    view! {
+                        <div class="w-full flex my-2 justify-center">
+                            <div class="my-card w-[320px] text-left">
+                                <div class="text-sm text-gray-400 px-2 pb-1">
+                                    Found on several platforms
+                                </div>
+                                {candidates
+                                    .into_iter()
+                                    .map(|suggestion| {
+                                        view! {
+                                            <SummonerSuggestionItem
+                                                suggestion
+                                                on_select=move || {
+                                                    search_summoner_any_platform.value().set(None)
+                                                }
+                                            />
+                                        }
+                                    })
+                                    .collect::<Vec<_>>()}
+                            </div>
+                        </div>
+                    }
+                })
+        }}
+    }
+}
+
+#[component]
+pub fn SummonerSuggestionItem(
+    suggestion: SummonerSuggestion,
+    on_select: impl Fn() + 'static,
+) -> impl IntoView {
+    view! {
+        <a
+            href=summoner_url(
+                suggestion.platform.as_ref(),
+                suggestion.game_name.as_str(),
+                suggestion.tag_line.as_str(),
+            )
+            on:click=move |_| on_select()
+            class="flex items-center px-2 py-1 hover:bg-gray-700"
+        >
+            <ImgSrc
+                alt=ProfileIcon(suggestion.profile_icon_id).to_string()
+                src=ProfileIcon(suggestion.profile_icon_id).get_static_asset_url()
+                width=32
+                height=32
+                class="w-8 h-8 rounded".to_string()
+            />
+            <div class="flex flex-col items-start ml-2 text-sm w-full">
+                <div class="flex justify-between w-full">
+                    <span class="truncate">
+                        {suggestion.game_name.clone()}
+                        <span class="text-gray-400">#{suggestion.tag_line.clone()}</span>
+                    </span>
+                    <span class="bg-gray-600 rounded px-1 text-xs">
+                        {suggestion.platform.to_string()}
+                    </span>
+                </div>
+                <div class="flex text-xs text-gray-400">
+                    <span>lvl. {suggestion.summoner_level}</span>
+                    {suggestion
+                        .pro_slug
+                        .map(|_| {
+                            view! {
+                                <span class="bg-purple-800 text-gray-200 rounded px-1 ml-1">
+                                    PRO
+                                </span>
+                            }
+                        })}
+                </div>
+            </div>
+        </a>
     }
 }
+
+#[derive(Clone, PartialEq, Eq, Debug, Decode, Encode)]
+pub struct SummonerSuggestion {
+    pub game_name: String,
+    pub tag_line: String,
+    pub platform: PlatformRoute,
+    pub profile_icon_id: u16,
+    pub summoner_level: u16,
+    pub pro_slug: Option<ProPlayerSlug>,
+}